@@ -58,8 +58,8 @@ use uuid::Uuid;
 /// * `RUST_LOG=error` sets the logging level for logs and spans. See
 ///   [`tracing_subscriber::EnvFilter`].
 ///
-/// * `SENTRY_DSN` sets the Sentry DSN to enable Sentry. Required if
-///   `ENABLE_SENTRY_TRACES=true`.
+/// * `SENTRY_DSN` sets the Sentry DSN to enable Sentry, or `SENTRY_DSN_FILE`
+///   the file to read it from. Required if `ENABLE_SENTRY_TRACES=true`.
 ///
 /// * `SENTRY_TRACES_SAMPLE_RATE=0.01` sets the Sentry traces sample rate.
 ///
@@ -161,6 +161,477 @@ pub fn with_instruments(_args: TokenStream, item: TokenStream) -> TokenStream {
     result.into()
 }
 
+/// Derives `From<T> for bitski_common::Error` for a service-specific error.
+///
+/// Each variant is mapped to the [`bitski_common::Error`] variant named by
+/// `#[error(code = "...")]`, where the code is the name of the constructor,
+/// such as `not_found` or `invalid_argument`. A code on the type applies to
+/// variants without one. The message is the [`Display`][std::fmt::Display]
+/// output of the error unless overridden with `message = "..."`, and the
+/// error itself is kept as the source, so the type must implement
+/// [`std::error::Error`].
+///
+/// Examples:
+///
+/// ```rust
+/// use bitski_common::IntoCommonError;
+///
+/// #[derive(Debug, IntoCommonError)]
+/// #[error(code = "internal")]
+/// enum UserError {
+///     #[error(code = "not_found")]
+///     NotFound(String),
+///     #[error(code = "permission_denied", message = "Access denied")]
+///     Forbidden { user_id: String },
+///     Database,
+/// }
+///
+/// impl std::fmt::Display for UserError {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         match self {
+///             Self::NotFound(id) => write!(f, "User {id} not found"),
+///             Self::Forbidden { user_id } => write!(f, "User {user_id} is forbidden"),
+///             Self::Database => f.write_str("Database error"),
+///         }
+///     }
+/// }
+///
+/// impl std::error::Error for UserError {}
+///
+/// let err = bitski_common::Error::from(UserError::NotFound("1234".into()));
+/// assert!(err.is_not_found());
+/// assert_eq!(err.to_string(), "User 1234 not found");
+///
+/// let err = bitski_common::Error::from(UserError::Forbidden { user_id: "1234".into() });
+/// assert!(err.is_permission_denied());
+/// assert_eq!(err.to_string(), "Access denied");
+///
+/// assert!(bitski_common::Error::from(UserError::Database).is_internal());
+/// ```
+///
+/// `thiserror` rejects `#[error(...)]` attributes without a format string, so
+/// types also deriving `thiserror::Error` set their codes with
+/// `#[common_error(...)]` instead:
+///
+/// ```rust
+/// use bitski_common::IntoCommonError;
+///
+/// #[derive(Debug, thiserror::Error, IntoCommonError)]
+/// #[common_error(code = "internal")]
+/// enum UserError {
+///     #[error("User {0} not found")]
+///     #[common_error(code = "not_found")]
+///     NotFound(String),
+///     #[error("Invalid user id {}", .0)]
+///     #[common_error(code = "invalid_argument")]
+///     InvalidId(String),
+///     #[error(transparent)]
+///     Io(#[from] std::io::Error),
+/// }
+///
+/// let err = bitski_common::Error::from(UserError::NotFound("1234".into()));
+/// assert!(err.is_not_found());
+/// assert_eq!(err.to_string(), "User 1234 not found");
+///
+/// let err = bitski_common::Error::from(UserError::InvalidId("abc".into()));
+/// assert!(err.is_invalid_argument());
+/// assert_eq!(err.to_string(), "Invalid user id abc");
+///
+/// let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+/// assert!(bitski_common::Error::from(UserError::from(io)).is_internal());
+/// ```
+#[proc_macro_derive(IntoCommonError, attributes(error, common_error))]
+pub fn derive_into_common_error(item: TokenStream) -> TokenStream {
+    let input: syn::DeriveInput = match syn::parse(item) {
+        Ok(it) => it,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
+    match expand_into_common_error(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+/// `bitski_common::Error` constructors that may be used as codes.
+const ERROR_CODES: &[&str] = &[
+    "cancelled",
+    "unknown",
+    "invalid_argument",
+    "deadline_exceeded",
+    "not_found",
+    "already_exists",
+    "permission_denied",
+    "resource_exhausted",
+    "failed_precondition",
+    "aborted",
+    "out_of_range",
+    "unimplemented",
+    "internal",
+    "unavailable",
+    "data_loss",
+    "unauthenticated",
+];
+
+/// The arguments of an `#[error(code = "...", message = "...")]` attribute.
+#[derive(Default)]
+struct ErrorArgs {
+    code: Option<syn::Ident>,
+    message: Option<syn::LitStr>,
+}
+
+impl ErrorArgs {
+    /// Parses the `#[error(...)]` and `#[common_error(...)]` attributes with a
+    /// `code` or `message`.
+    ///
+    /// Other `#[error(...)]` attributes, such as the `thiserror` format
+    /// strings, are ignored.
+    fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut args = Self::default();
+
+        for attr in attrs {
+            let list = if attr.path.is_ident("common_error") {
+                match attr.parse_meta()? {
+                    syn::Meta::List(list) => list,
+                    meta => {
+                        let msg = "expected `#[common_error(code = \"...\")]`";
+                        return Err(syn::Error::new_spanned(meta, msg));
+                    }
+                }
+            } else if attr.path.is_ident("error") {
+                match attr.parse_meta() {
+                    Ok(syn::Meta::List(list)) => list,
+                    _ => continue,
+                }
+            } else {
+                continue;
+            };
+
+            for nested in list.nested {
+                let name_value = match nested {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)) => name_value,
+                    _ => continue,
+                };
+                let lit = match name_value.lit {
+                    syn::Lit::Str(lit) => lit,
+                    lit => return Err(syn::Error::new_spanned(lit, "expected a string literal")),
+                };
+
+                if name_value.path.is_ident("code") {
+                    if !ERROR_CODES.contains(&lit.value().as_str()) {
+                        let msg = format!(
+                            "unknown error code, expected one of: {}",
+                            ERROR_CODES.join(", ")
+                        );
+                        return Err(syn::Error::new_spanned(lit, msg));
+                    }
+                    args.code = Some(syn::Ident::new(&lit.value(), lit.span()));
+                } else if name_value.path.is_ident("message") {
+                    args.message = Some(lit);
+                } else {
+                    let msg = "unknown argument, expected `code` or `message`";
+                    return Err(syn::Error::new_spanned(name_value.path, msg));
+                }
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+fn expand_into_common_error(input: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let default_args = ErrorArgs::from_attrs(&input.attrs)?;
+
+    let conversion = |args: ErrorArgs, span: &dyn ToTokens| -> syn::Result<_> {
+        let code = args
+            .code
+            .or_else(|| default_args.code.clone())
+            .ok_or_else(|| {
+                syn::Error::new_spanned(span, "missing `#[error(code = \"...\")]` attribute")
+            })?;
+        let message = match args.message.or_else(|| default_args.message.clone()) {
+            Some(message) => quote! { #message },
+            None => quote! { ::std::string::ToString::to_string(&err) },
+        };
+        Ok(quote! { bitski_common::Error::#code().with_message(#message) })
+    };
+
+    let error = match &input.data {
+        syn::Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let variant_ident = &variant.ident;
+                    let error = conversion(ErrorArgs::from_attrs(&variant.attrs)?, variant)?;
+                    Ok(quote! { #ident::#variant_ident { .. } => #error, })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! {
+                match &err {
+                    #(#arms)*
+                }
+            }
+        }
+        syn::Data::Struct(_) => conversion(ErrorArgs::default(), ident)?,
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "`IntoCommonError` can only be derived for enums and structs",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::From<#ident #ty_generics> for bitski_common::Error
+        #where_clause
+        {
+            fn from(err: #ident #ty_generics) -> Self {
+                let error = #error;
+                error.with_source(err)
+            }
+        }
+    })
+}
+
+/// Derives [`bitski_common::env::FromEnv`], reading each field from an env
+/// variable with the [`bitski_common::env`] helpers.
+///
+/// The variable of a field is its name in upper case, unless renamed with
+/// `#[env(name = "...")]`. Fields are parsed with [`FromStr`][std::str::FromStr],
+/// or for `bool` with [`parse_env_bool`][bitski_common::env::parse_env_bool],
+/// and are required unless they are:
+///
+/// * `Option<T>`, which is `None` when the variable is unset or empty.
+///
+/// * `Vec<T>`, parsed from a comma-separated list.
+///
+/// * given a default with `#[env(default = ...)]`, or `#[env(default)]` for
+///   [`Default::default()`]. Lists only support `#[env(default)]`.
+///
+/// Every missing or invalid variable is reported at once, see
+/// [`bitski_common::validate_all!`].
+///
+/// Examples:
+///
+/// ```rust
+/// use bitski_common::FromEnv;
+///
+/// #[derive(Debug, FromEnv)]
+/// struct Config {
+///     #[env(name = "CANDY_NAME")]
+///     name: String,
+///     #[env(default = 8000)]
+///     port: u16,
+///     #[env(default)]
+///     flavors: Vec<String>,
+///     api_key: Option<String>,
+///     #[env(default)]
+///     debug: bool,
+/// }
+///
+/// std::env::set_var("CANDY_NAME", "Gobstopper");
+/// std::env::set_var("FLAVORS", "cherry, lime");
+/// std::env::set_var("DEBUG", "yes");
+///
+/// let config = Config::from_env().unwrap();
+/// assert_eq!(config.name, "Gobstopper");
+/// assert_eq!(config.port, 8000);
+/// assert_eq!(config.flavors, ["cherry", "lime"]);
+/// assert_eq!(config.api_key, None);
+/// assert!(config.debug);
+///
+/// std::env::set_var("PORT", "not a port");
+/// assert!(Config::from_env().is_err());
+/// ```
+#[proc_macro_derive(FromEnv, attributes(env))]
+pub fn derive_from_env(item: TokenStream) -> TokenStream {
+    let input: syn::DeriveInput = match syn::parse(item) {
+        Ok(it) => it,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
+    match expand_from_env(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+/// The default of an `#[env(default)]` or `#[env(default = ...)]` attribute.
+enum EnvDefault {
+    Default,
+    Value(syn::Lit),
+}
+
+/// The arguments of an `#[env(name = "...", default = ...)]` attribute.
+#[derive(Default)]
+struct EnvArgs {
+    name: Option<syn::LitStr>,
+    default: Option<EnvDefault>,
+}
+
+impl EnvArgs {
+    /// Parses the `#[env(...)]` attributes of a field.
+    fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut args = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("env")) {
+            let list = match attr.parse_meta()? {
+                syn::Meta::List(list) => list,
+                meta => return Err(syn::Error::new_spanned(meta, "expected `#[env(...)]`")),
+            };
+
+            for nested in list.nested {
+                match nested {
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("default") => {
+                        args.default = Some(EnvDefault::Default);
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                        if name_value.path.is_ident("name") =>
+                    {
+                        match name_value.lit {
+                            syn::Lit::Str(lit) => args.name = Some(lit),
+                            lit => {
+                                return Err(syn::Error::new_spanned(
+                                    lit,
+                                    "expected a string literal",
+                                ))
+                            }
+                        }
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                        if name_value.path.is_ident("default") =>
+                    {
+                        args.default = Some(EnvDefault::Value(name_value.lit));
+                    }
+                    nested => {
+                        let msg = "unknown argument, expected `name` or `default`";
+                        return Err(syn::Error::new_spanned(nested, msg));
+                    }
+                }
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+/// Gets the type argument of `ty` if it is `wrapper<T>`, such as `Option<T>`.
+fn wrapped_type<'a>(ty: &'a syn::Type, wrapper: &str) -> Option<&'a syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != wrapper {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+            match arguments.args.first()? {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Checks whether `ty` is `bool`.
+fn is_bool(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(path) if path.qself.is_none() && path.path.is_ident("bool"))
+}
+
+fn expand_from_env(input: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "`FromEnv` can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let initializers = fields
+        .iter()
+        .map(|field| {
+            let field_ident = field.ident.as_ref().expect("Named field");
+            let args = EnvArgs::from_attrs(&field.attrs)?;
+            let name = args.name.unwrap_or_else(|| {
+                let name = field_ident
+                    .to_string()
+                    .trim_start_matches("r#")
+                    .to_uppercase();
+                syn::LitStr::new(&name, field_ident.span())
+            });
+
+            let option = wrapped_type(&field.ty, "Option");
+            let list = wrapped_type(&field.ty, "Vec");
+            let value = match (args.default, option, list) {
+                (Some(_), Some(_), _) => {
+                    let msg = "`Option` fields can't have a default";
+                    return Err(syn::Error::new_spanned(field, msg));
+                }
+                (None, Some(ty), _) if is_bool(ty) => {
+                    quote! { bitski_common::env::parse_env_bool(#name) }
+                }
+                (None, Some(ty), _) => match wrapped_type(ty, "Vec") {
+                    Some(_) => quote! { bitski_common::env::parse_env_list(#name) },
+                    None => quote! { bitski_common::env::parse_env(#name) },
+                },
+                (None, None, Some(_)) => quote! { bitski_common::env::require_env_list(#name) },
+                (Some(EnvDefault::Default), None, Some(_)) => {
+                    quote! { bitski_common::env::parse_env_list_or_default(#name) }
+                }
+                (Some(EnvDefault::Value(lit)), None, Some(_)) => {
+                    let msg = "lists only support `#[env(default)]`";
+                    return Err(syn::Error::new_spanned(lit, msg));
+                }
+                (None, None, None) if is_bool(&field.ty) => {
+                    quote! { bitski_common::env::require_env_bool(#name) }
+                }
+                (Some(EnvDefault::Default), None, None) if is_bool(&field.ty) => {
+                    quote! { bitski_common::env::parse_env_bool_or_default(#name) }
+                }
+                (Some(EnvDefault::Value(lit)), None, None) if is_bool(&field.ty) => {
+                    quote! { bitski_common::env::parse_env_bool_or(#name, #lit) }
+                }
+                (None, None, None) => quote! { bitski_common::env::require_env(#name) },
+                (Some(EnvDefault::Default), None, None) => {
+                    quote! { bitski_common::env::parse_env_or_default(#name) }
+                }
+                (Some(EnvDefault::Value(lit)), None, None) => {
+                    quote! { bitski_common::env::parse_env_or(#name, #lit) }
+                }
+            };
+            let ty = &field.ty;
+            Ok(quote! { let #field_ident: #ty = #value; })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let field_idents = fields.iter().map(|field| &field.ident);
+
+    Ok(quote! {
+        impl #impl_generics bitski_common::env::FromEnv for #ident #ty_generics #where_clause {
+            fn from_env() -> bitski_common::Result<Self> {
+                bitski_common::validate_all! {
+                    #(#initializers)*
+                }
+                Ok(Self {
+                    #(#field_idents,)*
+                })
+            }
+        }
+    })
+}
+
 fn token_stream_with_error(mut tokens: TokenStream, error: syn::Error) -> TokenStream {
     tokens.extend(TokenStream::from(error.into_compile_error()));
     tokens
@@ -0,0 +1,140 @@
+//! # Utilities for reporting build information.
+//!
+//! [`build_info()`] reports the version and enabled features of
+//! `bitski-common` and the git revision of the service, so that services can
+//! expose which library versions are running in an info endpoint. Other
+//! crates can be added with [`BuildInfo::with_crate`].
+//!
+//! The git revision is configurable with the following env variables:
+//!
+//! * `GIT_SHA=?` Sets the git revision of the service. Read when the service
+//!   is built, or at runtime if it wasn't set at build time.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bitski_common::build_info;
+//!
+//! let info = build_info().with_crate("my-service", env!("CARGO_PKG_VERSION"), vec![]);
+//!
+//! let bitski_common = info.get("bitski-common").unwrap();
+//! assert_eq!(bitski_common.version, env!("CARGO_PKG_VERSION"));
+//! assert!(info.get("my-service").is_some());
+//! ```
+
+use crate::env::parse_env;
+
+/// The features of `bitski-common` and whether they are enabled.
+const FEATURES: &[(&str, bool)] = &[
+    ("actix", cfg!(feature = "actix")),
+    ("actix-web", cfg!(feature = "actix-web")),
+    ("awc", cfg!(feature = "awc")),
+    ("bcrypt", cfg!(feature = "bcrypt")),
+    ("cli", cfg!(feature = "cli")),
+    ("dependency-policies", cfg!(feature = "dependency-policies")),
+    ("diesel", cfg!(feature = "diesel")),
+    ("encryption", cfg!(feature = "encryption")),
+    ("humantime", cfg!(feature = "humantime")),
+    ("lettre", cfg!(feature = "lettre")),
+    ("oauth2", cfg!(feature = "oauth2")),
+    ("openapi", cfg!(feature = "openapi")),
+    ("postgres", cfg!(feature = "postgres")),
+    ("r2d2", cfg!(feature = "r2d2")),
+    ("reqwest", cfg!(feature = "reqwest")),
+    ("request-signing", cfg!(feature = "request-signing")),
+    ("secrets", cfg!(feature = "secrets")),
+    ("strum", cfg!(feature = "strum")),
+    ("test", cfg!(feature = "test")),
+    ("tonic", cfg!(feature = "tonic")),
+    ("tower", cfg!(feature = "tower")),
+    ("transcoding", cfg!(feature = "transcoding")),
+    ("url", cfg!(feature = "url")),
+    ("validator", cfg!(feature = "validator")),
+];
+
+/// Build information of a service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// The git revision of the service, if known.
+    pub git_sha: Option<String>,
+
+    /// The crates built into the service.
+    pub crates: Vec<CrateInfo>,
+}
+
+/// Build information of a crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrateInfo {
+    /// The crate name.
+    pub name: &'static str,
+
+    /// The crate version.
+    pub version: &'static str,
+
+    /// The enabled features of the crate.
+    pub features: Vec<&'static str>,
+}
+
+impl BuildInfo {
+    /// Adds a crate.
+    pub fn with_crate(
+        mut self,
+        name: &'static str,
+        version: &'static str,
+        features: Vec<&'static str>,
+    ) -> Self {
+        self.crates.push(CrateInfo {
+            name,
+            version,
+            features,
+        });
+        self
+    }
+
+    /// Gets a crate by name.
+    pub fn get(&self, name: &str) -> Option<&CrateInfo> {
+        self.crates.iter().find(|info| info.name == name)
+    }
+
+    /// Converts the build information to JSON.
+    #[cfg(feature = "serde_json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+    pub fn to_json(&self) -> serde_json::Value {
+        let crates: serde_json::Map<_, _> = self
+            .crates
+            .iter()
+            .map(|info| {
+                let value = serde_json::json!({
+                    "version": info.version,
+                    "features": info.features,
+                });
+                (info.name.to_owned(), value)
+            })
+            .collect();
+
+        serde_json::json!({
+            "git_sha": self.git_sha,
+            "crates": crates,
+        })
+    }
+}
+
+/// Gets the build information of `bitski-common` and the service.
+pub fn build_info() -> BuildInfo {
+    let git_sha = option_env!("GIT_SHA")
+        .filter(|git_sha| !git_sha.is_empty())
+        .map(str::to_owned)
+        .or_else(|| parse_env("GIT_SHA").ok().flatten());
+
+    let features = FEATURES
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| *name)
+        .collect();
+
+    BuildInfo {
+        git_sha,
+        crates: vec![],
+    }
+    .with_crate(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), features)
+}
@@ -0,0 +1,98 @@
+//! # Utilities for command line tools.
+//!
+//! Operational binaries (migration runners, backfill jobs, ...) usually take
+//! the same configuration as the services they support. Declare arguments
+//! with [`clap`] and give each of them an `env` fallback: flags take
+//! precedence over env variables, which take precedence over defaults.
+//!
+//! ```rust,no_run
+//! use anyhow::Result;
+//! use bitski_common::{
+//!     cli::{clap, parse_args, TelemetryArgs},
+//!     with_instruments,
+//! };
+//!
+//! #[derive(Debug, clap::Parser)]
+//! struct Args {
+//!     /// Number of rows to process per batch.
+//!     #[clap(long, env = "BATCH_SIZE", default_value = "100")]
+//!     batch_size: usize,
+//!
+//!     #[clap(flatten)]
+//!     telemetry: TelemetryArgs,
+//! }
+//!
+//! fn main() -> Result<()> {
+//!     let args: Args = parse_args();
+//!     args.telemetry.export();
+//!     run(args)
+//! }
+//!
+//! #[with_instruments]
+//! #[tokio::main]
+//! async fn run(args: Args) -> Result<()> {
+//!     tracing::info!("Processing in batches of {}", args.batch_size);
+//!     Ok(())
+//! }
+//! ```
+
+use std::env;
+
+pub use clap;
+use clap::Parser;
+
+use crate::env::init_env;
+use crate::Result;
+
+/// Parses command line arguments after loading env variables from .env files.
+///
+/// Exits the program with a usage message if the arguments are invalid.
+///
+/// See [`clap::Parser::parse`].
+pub fn parse_args<T: Parser>() -> T {
+    init_env();
+    T::parse()
+}
+
+/// Parses command line arguments after loading env variables from .env files.
+///
+/// See [`clap::Parser::try_parse`].
+pub fn try_parse_args<T: Parser>() -> Result<T> {
+    init_env();
+    Ok(T::try_parse()?)
+}
+
+/// Common telemetry arguments for command line tools.
+///
+/// Flatten into the arguments of a tool and call [`TelemetryArgs::export`]
+/// before instruments are initialized so that flags override the env
+/// variables read by [`with_instruments`][`bitski_common_macros::with_instruments`].
+#[derive(Clone, Debug, Default, clap::Args)]
+pub struct TelemetryArgs {
+    /// Sets the logging level for logs and spans.
+    #[clap(long = "log", env = "RUST_LOG")]
+    pub rust_log: Option<String>,
+
+    /// Sets the Otel `service.name` resource value.
+    #[clap(long, env = "SERVICE_NAME")]
+    pub service_name: Option<String>,
+
+    /// Sets the target to which the exporter is going to send spans or metrics.
+    #[clap(long, env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    pub otel_exporter_otlp_endpoint: Option<String>,
+}
+
+impl TelemetryArgs {
+    /// Exports the arguments as env variables for telemetry initialization.
+    pub fn export(&self) {
+        if let Some(rust_log) = self.rust_log.as_ref() {
+            env::set_var("RUST_LOG", rust_log);
+        }
+        if let Some(service_name) = self.service_name.as_ref() {
+            env::set_var("SERVICE_NAME", service_name);
+        }
+        if let Some(endpoint) = self.otel_exporter_otlp_endpoint.as_ref() {
+            env::set_var("OTEL_EXPORTER_OTLP_ENDPOINT", endpoint);
+        }
+    }
+}
@@ -0,0 +1,737 @@
+//! # Utilities for configuring calls to downstream dependencies.
+//!
+//! [`DependencyPolicies`] holds a [`DependencyPolicy`] per named dependency
+//! so that timeouts, retries and circuit breaker thresholds can be tuned
+//! without code changes in each caller.
+//!
+//! Policies are applied with [`DependencyPolicy::retry`] and
+//! [`CircuitBreaker::call`], or with the `timeout_layer`, `retry_layer` and
+//! `circuit_breaker_layer` methods of [`DependencyPolicy`] when `tower` is
+//! enabled.
+//!
+//! Policies are configured as a JSON object keyed by dependency name. The
+//! `default` entry applies to dependencies without an entry and provides the
+//! values for fields missing from other entries. Durations use the
+//! [`humantime`] format:
+//!
+//! ```json
+//! {
+//!   "default": { "timeout": "5s", "retries": 2, "backoff": ["100ms", "1s"] },
+//!   "users": {
+//!     "timeout": "500ms",
+//!     "circuit_breaker": { "failure_threshold": 10, "reset_timeout": "1m" }
+//!   }
+//! }
+//! ```
+//!
+//! [`DependencyPolicies::from_env`] is configurable with the following env
+//! variables:
+//!
+//! * `DEPENDENCY_POLICIES_FILE=/etc/bitski/dependencies.json` Sets the path of
+//!   a JSON file with dependency policies.
+//!
+//! * `DEPENDENCY_POLICIES={"users":{"retries":0}}` Sets dependency policies as
+//!   JSON. Entries override the entries of the same name in
+//!   `DEPENDENCY_POLICIES_FILE`.
+//!
+//! * `RETRY_BACKOFF=100ms,1s,5s` Sets the default delays before each retry
+//!   made by [`DependencyPolicy::retry`] or the retry layer. The `backoff` of
+//!   the `default` entry takes precedence.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! # use anyhow::Result;
+//! use bitski_common::dependency_policies::DependencyPolicies;
+//!
+//! # fn main() -> Result<()> {
+//! let policies = DependencyPolicies::from_json(
+//!     r#"{"default": {"retries": 3}, "users": {"timeout": "500ms"}}"#,
+//! )?;
+//!
+//! let users = policies.get("users");
+//! assert_eq!(users.timeout, Duration::from_millis(500));
+//! assert_eq!(users.retries, 3);
+//!
+//! let payments = policies.get("payments");
+//! assert_eq!(payments.timeout, Duration::from_secs(10));
+//! assert_eq!(payments.retries, 3);
+//! assert_eq!(payments.backoff(0), Duration::from_millis(100));
+//! assert_eq!(payments.backoff(9), Duration::from_secs(5));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::env::{parse_env, parse_env_duration_list};
+use crate::{Error, Result};
+
+/// The name of the policy entry used for dependencies without an entry.
+pub const DEFAULT_DEPENDENCY_POLICY: &str = "default";
+
+const DEFAULT_DEPENDENCY_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_DEPENDENCY_RETRIES: u32 = 2;
+const DEFAULT_DEPENDENCY_BACKOFF: [Duration; 3] = [
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+    Duration::from_secs(5),
+];
+const DEFAULT_CIRCUIT_BREAKER_FAILURE_THRESHOLD: u32 = 5;
+const DEFAULT_CIRCUIT_BREAKER_RESET_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Policies for calls to downstream dependencies, by dependency name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DependencyPolicies {
+    default: DependencyPolicy,
+    policies: HashMap<String, DependencyPolicy>,
+}
+
+impl DependencyPolicies {
+    /// Creates dependency policies from env variables.
+    pub fn from_env() -> Result<Self> {
+        let mut entries = Map::new();
+
+        if let Some(path) = parse_env::<PathBuf>("DEPENDENCY_POLICIES_FILE")? {
+            let json = std::fs::read_to_string(&path).map_err(|err| {
+                Error::invalid_argument()
+                    .with_message(format!(
+                        "Error reading env DEPENDENCY_POLICIES_FILE {}: {err}",
+                        path.display()
+                    ))
+                    .with_source(err)
+            })?;
+            entries.extend(parse_entries("DEPENDENCY_POLICIES_FILE", &json)?);
+        }
+
+        if let Some(json) = parse_env::<String>("DEPENDENCY_POLICIES")? {
+            entries.extend(parse_entries("DEPENDENCY_POLICIES", &json)?);
+        }
+
+        if let Some(backoff) = parse_env_duration_list("RETRY_BACKOFF")? {
+            let backoff = backoff
+                .iter()
+                .map(|delay| humantime::format_duration(*delay).to_string())
+                .collect::<Vec<_>>();
+            let mut default = serde_json::json!({ "backoff": backoff });
+            if let Some(entry) = entries.remove(DEFAULT_DEPENDENCY_POLICY) {
+                merge_json(&mut default, entry);
+            }
+            entries.insert(DEFAULT_DEPENDENCY_POLICY.to_owned(), default);
+        }
+
+        Self::from_entries(entries)
+    }
+
+    /// Creates dependency policies from a JSON object keyed by dependency name.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Self::from_entries(parse_entries("dependency policies", json)?)
+    }
+
+    /// Sets the policy for a dependency.
+    pub fn with_policy<N: Into<String>>(mut self, name: N, policy: DependencyPolicy) -> Self {
+        let name = name.into();
+        if name == DEFAULT_DEPENDENCY_POLICY {
+            self.default = policy;
+        } else {
+            self.policies.insert(name, policy);
+        }
+        self
+    }
+
+    /// Gets the policy for a dependency, or the default policy if the
+    /// dependency has no entry.
+    pub fn get(&self, name: &str) -> &DependencyPolicy {
+        self.policies.get(name).unwrap_or(&self.default)
+    }
+
+    fn from_entries(mut entries: Map<String, Value>) -> Result<Self> {
+        let mut default = serde_json::to_value(DependencyPolicy::default()).map_err(|err| {
+            Error::internal()
+                .with_message("Error serializing default dependency policy")
+                .with_source(err)
+        })?;
+        if let Some(entry) = entries.remove(DEFAULT_DEPENDENCY_POLICY) {
+            merge_json(&mut default, entry);
+        }
+
+        let mut policies = Self {
+            default: parse_policy(DEFAULT_DEPENDENCY_POLICY, default.clone())?,
+            policies: HashMap::new(),
+        };
+
+        for (name, entry) in entries {
+            let mut policy = default.clone();
+            merge_json(&mut policy, entry);
+            let policy = parse_policy(&name, policy)?;
+            policies = policies.with_policy(name, policy);
+        }
+
+        Ok(policies)
+    }
+}
+
+/// The policy for calls to a downstream dependency.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DependencyPolicy {
+    /// The timeout of a single attempt.
+    #[serde(with = "humantime_serde")]
+    pub timeout: Duration,
+
+    /// The number of retries after the first attempt fails.
+    pub retries: u32,
+
+    /// The delays before each retry. The last delay repeats for further
+    /// retries.
+    #[serde(with = "humantime_serde::list")]
+    pub backoff: Vec<Duration>,
+
+    /// The circuit breaker thresholds.
+    pub circuit_breaker: CircuitBreakerPolicy,
+}
+
+impl Default for DependencyPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_DEPENDENCY_TIMEOUT,
+            retries: DEFAULT_DEPENDENCY_RETRIES,
+            backoff: DEFAULT_DEPENDENCY_BACKOFF.to_vec(),
+            circuit_breaker: CircuitBreakerPolicy::default(),
+        }
+    }
+}
+
+impl DependencyPolicy {
+    /// Gets the delay before the given retry, starting at 0, or zero without
+    /// a backoff schedule.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::{Duration, Instant};
+    ///
+    /// # use anyhow::Result;
+    /// use bitski_common::dependency_policies::DependencyPolicies;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// std::env::set_var("RETRY_BACKOFF", "10ms,20ms");
+    /// let policies = DependencyPolicies::from_env()?;
+    ///
+    /// let users = policies.get("users");
+    /// assert_eq!(users.backoff(0), Duration::from_millis(10));
+    /// assert_eq!(users.backoff(5), Duration::from_millis(20));
+    ///
+    /// // Retried twice after 10ms and 20ms
+    /// let start = Instant::now();
+    /// let result: Result<(), &str> = users.retry(|| async { Err("unavailable") }).await;
+    /// assert!(result.is_err());
+    /// assert!(start.elapsed() >= Duration::from_millis(30));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let index = (retry as usize).min(self.backoff.len().saturating_sub(1));
+        self.backoff.get(index).copied().unwrap_or_default()
+    }
+
+    /// Calls `f` until it succeeds, retrying up to `retries` times after the
+    /// delays of the backoff schedule.
+    ///
+    /// Every error is retried, so `f` should be idempotent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use anyhow::Result;
+    /// use bitski_common::dependency_policies::DependencyPolicies;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let policies = DependencyPolicies::from_json(
+    ///     r#"{"users": {"retries": 2, "backoff": ["1ms"]}}"#,
+    /// )?;
+    ///
+    /// let mut attempts = 0;
+    /// let result: Result<u32, &str> = policies
+    ///     .get("users")
+    ///     .retry(|| {
+    ///         attempts += 1;
+    ///         let result = if attempts < 3 { Err("unavailable") } else { Ok(attempts) };
+    ///         async move { result }
+    ///     })
+    ///     .await;
+    /// assert_eq!(result, Ok(3));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn retry<T, E, F, Fut>(&self, mut f: F) -> std::result::Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = std::result::Result<T, E>>,
+    {
+        let mut retry = 0;
+        loop {
+            match f().await {
+                Err(_) if retry < self.retries => {
+                    tokio::time::sleep(self.backoff(retry)).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Creates a [`CircuitBreaker`] with the thresholds of this policy.
+    pub fn circuit_breaker(&self) -> CircuitBreaker {
+        CircuitBreaker::new(self.circuit_breaker.clone())
+    }
+
+    /// Creates a [`reqwest::ClientBuilder`] configured with the timeout of
+    /// this policy.
+    ///
+    /// Retries and the circuit breaker are applied around requests with
+    /// [`DependencyPolicy::retry`] and [`CircuitBreaker::call`].
+    #[cfg(feature = "reqwest")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
+    pub fn reqwest_client_builder(&self) -> reqwest::ClientBuilder {
+        reqwest::Client::builder().timeout(self.timeout)
+    }
+
+    /// Creates a [`tower::timeout::TimeoutLayer`] configured with this policy.
+    #[cfg(feature = "tower")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+    pub fn timeout_layer(&self) -> tower::timeout::TimeoutLayer {
+        tower::timeout::TimeoutLayer::new(self.timeout)
+    }
+
+    /// Creates a [`tower::retry::RetryLayer`] retrying failed requests with
+    /// this policy, see [`DependencyPolicy::retry`].
+    ///
+    /// Only requests implementing [`Clone`] can be retried.
+    #[cfg(feature = "tower")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+    pub fn retry_layer(&self) -> tower::retry::RetryLayer<RetryPolicy> {
+        tower::retry::RetryLayer::new(RetryPolicy {
+            policy: Arc::new(self.clone()),
+            retry: 0,
+        })
+    }
+
+    /// Creates a [`CircuitBreakerLayer`] with the thresholds of this policy.
+    ///
+    /// Services created by the layer share its circuit breaker.
+    #[cfg(feature = "tower")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+    pub fn circuit_breaker_layer(&self) -> CircuitBreakerLayer {
+        CircuitBreakerLayer::new(self.circuit_breaker())
+    }
+}
+
+/// The thresholds of a circuit breaker for a downstream dependency.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CircuitBreakerPolicy {
+    /// The number of consecutive failures after which the circuit opens.
+    pub failure_threshold: u32,
+
+    /// How long the circuit stays open before a trial call is allowed.
+    #[serde(with = "humantime_serde")]
+    pub reset_timeout: Duration,
+}
+
+impl Default for CircuitBreakerPolicy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: DEFAULT_CIRCUIT_BREAKER_FAILURE_THRESHOLD,
+            reset_timeout: DEFAULT_CIRCUIT_BREAKER_RESET_TIMEOUT,
+        }
+    }
+}
+
+/// A circuit breaker for calls to a downstream dependency.
+///
+/// The circuit opens after `failure_threshold` consecutive failures, failing
+/// calls without making them. Once `reset_timeout` passes, a single trial call
+/// is allowed while the circuit stays open for other calls. Its success
+/// closes the circuit and its failure keeps it open for another
+/// `reset_timeout`. A trial call that never records a result, such as a
+/// cancelled one, is replaced after `reset_timeout`. A threshold of 0 never
+/// opens the circuit.
+///
+/// Clones share the state of the circuit.
+///
+/// # Examples
+///
+/// ```rust
+/// # use anyhow::Result;
+/// use bitski_common::dependency_policies::DependencyPolicies;
+/// use bitski_common::Error;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let policies = DependencyPolicies::from_json(
+///     r#"{"users": {"circuit_breaker": {"failure_threshold": 2}}}"#,
+/// )?;
+/// let breaker = policies.get("users").circuit_breaker();
+///
+/// for _ in 0..2 {
+///     let result = breaker.call(|| async { Err::<(), _>(Error::unavailable()) }).await;
+///     assert!(result.is_err());
+/// }
+/// assert!(breaker.is_open());
+///
+/// let result = breaker.call(|| async { Ok::<_, Error>(()) }).await;
+/// assert!(result.unwrap_err().is_unavailable());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker {
+    policy: CircuitBreakerPolicy,
+    state: Arc<Mutex<CircuitState>>,
+}
+
+#[derive(Debug, Default)]
+struct CircuitState {
+    failures: u32,
+    opened_at: Option<Instant>,
+    trial_started_at: Option<Instant>,
+}
+
+impl CircuitState {
+    fn is_open(&self, reset_timeout: Duration) -> bool {
+        [self.opened_at, self.trial_started_at]
+            .iter()
+            .flatten()
+            .any(|at| at.elapsed() < reset_timeout)
+    }
+}
+
+impl CircuitBreaker {
+    /// Creates a closed circuit breaker.
+    pub fn new(policy: CircuitBreakerPolicy) -> Self {
+        Self {
+            policy,
+            state: Default::default(),
+        }
+    }
+
+    /// Whether the circuit is open and calls fail without being made,
+    /// including while a trial call is in flight.
+    pub fn is_open(&self) -> bool {
+        self.lock_state().is_open(self.policy.reset_timeout)
+    }
+
+    /// Records a successful call, closing the circuit.
+    pub fn record_success(&self) {
+        *self.lock_state() = CircuitState::default();
+    }
+
+    /// Records a failed call, opening the circuit once the failure threshold
+    /// is reached.
+    pub fn record_failure(&self) {
+        let mut state = self.lock_state();
+        state.failures = state.failures.saturating_add(1);
+        if self.policy.failure_threshold > 0 && state.failures >= self.policy.failure_threshold {
+            state.opened_at = Some(Instant::now());
+            state.trial_started_at = None;
+        }
+    }
+
+    /// Whether a call may be made, which starts the trial call once
+    /// `reset_timeout` has passed.
+    fn try_acquire(&self) -> bool {
+        let mut state = self.lock_state();
+        if state.is_open(self.policy.reset_timeout) {
+            return false;
+        }
+        if state.opened_at.is_some() {
+            state.trial_started_at = Some(Instant::now());
+        }
+        true
+    }
+
+    /// Calls `f` unless the circuit is open, recording its result.
+    ///
+    /// Fails with [`Error::unavailable`] while the circuit is open.
+    pub async fn call<T, E, F, Fut>(&self, f: F) -> std::result::Result<T, E>
+    where
+        E: From<Error>,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = std::result::Result<T, E>>,
+    {
+        if !self.try_acquire() {
+            return Err(Error::unavailable()
+                .with_message("Circuit breaker is open")
+                .into());
+        }
+
+        let result = f().await;
+        match &result {
+            Ok(_) => self.record_success(),
+            Err(_) => self.record_failure(),
+        }
+        result
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, CircuitState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn parse_entries(source: &str, json: &str) -> Result<Map<String, Value>> {
+    serde_json::from_str(json).map_err(|err| {
+        Error::invalid_argument()
+            .with_message(format!("Error parsing {source}: {err}"))
+            .with_source(err)
+    })
+}
+
+fn parse_policy(name: &str, value: Value) -> Result<DependencyPolicy> {
+    serde_json::from_value(value).map_err(|err| {
+        Error::invalid_argument()
+            .with_message(format!("Error parsing dependency policy {name}: {err}"))
+            .with_source(err)
+    })
+}
+
+/// Recursively merges the fields of `overlay` into `base`.
+fn merge_json(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                merge_json(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[cfg(feature = "tower")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+pub use self::layers::{CircuitBreakerLayer, CircuitBreakerService, RetryPolicy};
+
+#[cfg(feature = "tower")]
+mod layers {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+
+    use tower::{BoxError, Layer, Service};
+
+    use super::{CircuitBreaker, DependencyPolicy};
+    use crate::Error;
+
+    /// A [`tower::retry::Policy`] retrying failed requests with a
+    /// [`DependencyPolicy`].
+    ///
+    /// See [`DependencyPolicy::retry_layer`].
+    #[derive(Clone, Debug)]
+    pub struct RetryPolicy {
+        pub(super) policy: Arc<DependencyPolicy>,
+        pub(super) retry: u32,
+    }
+
+    impl<Req: Clone, Res, E> tower::retry::Policy<Req, Res, E> for RetryPolicy {
+        type Future = Pin<Box<dyn Future<Output = Self> + Send>>;
+
+        fn retry(&self, _: &Req, result: std::result::Result<&Res, &E>) -> Option<Self::Future> {
+            if result.is_ok() || self.retry >= self.policy.retries {
+                return None;
+            }
+
+            let delay = self.policy.backoff(self.retry);
+            let next = Self {
+                policy: self.policy.clone(),
+                retry: self.retry + 1,
+            };
+            Some(Box::pin(async move {
+                tokio::time::sleep(delay).await;
+                next
+            }))
+        }
+
+        fn clone_request(&self, request: &Req) -> Option<Req> {
+            Some(request.clone())
+        }
+    }
+
+    /// Layer that fails requests while its [`CircuitBreaker`] is open.
+    ///
+    /// See [`DependencyPolicy::circuit_breaker_layer`].
+    #[derive(Clone, Debug)]
+    pub struct CircuitBreakerLayer {
+        breaker: CircuitBreaker,
+    }
+
+    impl CircuitBreakerLayer {
+        /// Creates a new `CircuitBreakerLayer`.
+        pub fn new(breaker: CircuitBreaker) -> Self {
+            Self { breaker }
+        }
+    }
+
+    impl<S> Layer<S> for CircuitBreakerLayer {
+        type Service = CircuitBreakerService<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            CircuitBreakerService {
+                inner,
+                breaker: self.breaker.clone(),
+            }
+        }
+    }
+
+    /// Middleware that fails requests while its [`CircuitBreaker`] is open.
+    ///
+    /// See [`CircuitBreakerLayer`].
+    #[derive(Clone, Debug)]
+    pub struct CircuitBreakerService<S> {
+        inner: S,
+        breaker: CircuitBreaker,
+    }
+
+    impl<S, Req> Service<Req> for CircuitBreakerService<S>
+    where
+        S: Service<Req>,
+        S::Error: Into<BoxError>,
+        S::Future: Send + 'static,
+    {
+        type Response = S::Response;
+        type Error = BoxError;
+        #[allow(clippy::type_complexity)]
+        type Future =
+            Pin<Box<dyn Future<Output = std::result::Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), BoxError>> {
+            self.inner.poll_ready(cx).map_err(Into::into)
+        }
+
+        fn call(&mut self, request: Req) -> Self::Future {
+            if !self.breaker.try_acquire() {
+                let err = Error::unavailable().with_message("Circuit breaker is open");
+                return Box::pin(async move { Err(err.into()) });
+            }
+
+            let breaker = self.breaker.clone();
+            let response = self.inner.call(request);
+            Box::pin(async move {
+                let result = response.await.map_err(Into::into);
+                match &result {
+                    Ok(_) => breaker.record_success(),
+                    Err(_) => breaker.record_failure(),
+                }
+                result
+            })
+        }
+    }
+}
+
+/// Serializes [`Duration`] values in the [`humantime`] format.
+mod humantime_serde {
+    use std::time::Duration;
+
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&humantime::format_duration(*duration))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        humantime::parse_duration(&s).map_err(de::Error::custom)
+    }
+
+    /// Serializes lists of [`Duration`] values in the [`humantime`] format.
+    pub mod list {
+        use std::time::Duration;
+
+        use serde::{de, Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            durations: &[Duration],
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(
+                durations
+                    .iter()
+                    .map(|duration| humantime::format_duration(*duration).to_string()),
+            )
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Vec<Duration>, D::Error> {
+            Vec::<String>::deserialize(deserializer)?
+                .iter()
+                .map(|s| humantime::parse_duration(s).map_err(de::Error::custom))
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn circuit_breaker_allows_a_single_trial_call() {
+        let reset_timeout = Duration::from_millis(50);
+        let breaker = CircuitBreaker::new(CircuitBreakerPolicy {
+            failure_threshold: 1,
+            reset_timeout,
+        });
+
+        breaker.record_failure();
+        assert!(breaker.is_open());
+        assert!(!breaker.try_acquire());
+
+        // A failed trial call keeps the circuit open
+        std::thread::sleep(reset_timeout);
+        assert!(!breaker.is_open());
+        assert!(breaker.try_acquire());
+        assert!(breaker.is_open());
+        assert!(!breaker.try_acquire());
+        breaker.record_failure();
+        assert!(breaker.is_open());
+        assert!(!breaker.try_acquire());
+
+        // A successful trial call closes the circuit
+        std::thread::sleep(reset_timeout);
+        assert!(breaker.try_acquire());
+        assert!(!breaker.try_acquire());
+        breaker.record_success();
+        assert!(!breaker.is_open());
+        assert!(breaker.try_acquire());
+        assert!(breaker.try_acquire());
+    }
+
+    #[test]
+    fn circuit_breaker_replaces_abandoned_trial_calls() {
+        let reset_timeout = Duration::from_millis(50);
+        let breaker = CircuitBreaker::new(CircuitBreakerPolicy {
+            failure_threshold: 1,
+            reset_timeout,
+        });
+
+        breaker.record_failure();
+        std::thread::sleep(reset_timeout);
+        assert!(breaker.try_acquire());
+        assert!(!breaker.try_acquire());
+
+        std::thread::sleep(reset_timeout);
+        assert!(breaker.try_acquire());
+    }
+}
@@ -3,7 +3,7 @@
 use async_trait::async_trait;
 use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
 
-use crate::env::parse_env_or;
+use crate::env::{parse_env_or, parse_env_or_file};
 use crate::task::spawn_blocking;
 use crate::{Error, Result};
 
@@ -29,7 +29,7 @@ pub trait PgPoolExt {
     /// Diesel is configurable with the following env variables:
     ///
     /// * `DATABASE_URL=postgres://root@localhost:5432/defaultdb` Sets the
-    ///   database URL.
+    ///   database URL, or `DATABASE_URL_FILE` the file to read it from.
     ///
     /// * `DATABASE_POOL_MIN_IDLE=1` Sets the minimum idle connection count
     ///   maintained by the pool.
@@ -45,7 +45,7 @@ pub trait PgPoolExt {
     /// Diesel is configurable with the following env variables:
     ///
     /// * `DATABASE_URL=postgres://root@localhost:5432/defaultdb` Sets the
-    ///   database URL.
+    ///   database URL, or `DATABASE_URL_FILE` the file to read it from.
     #[cfg(feature = "test")]
     #[cfg_attr(docsrs, doc(cfg(feature = "test")))]
     fn for_test() -> Result<Self>
@@ -83,7 +83,8 @@ pub trait PgPoolExt {
 #[async_trait]
 impl PgPoolExt for PgPool {
     fn from_env() -> Result<Self> {
-        let database_url: String = parse_env_or("DATABASE_URL", DEFAULT_DATABASE_URL)?;
+        let database_url =
+            parse_env_or_file("DATABASE_URL")?.unwrap_or_else(|| DEFAULT_DATABASE_URL.to_string());
         let min_idle: u32 = parse_env_or("DATABASE_POOL_MIN_IDLE", DEFAULT_DATABASE_POOL_MIN_IDLE)?;
         let max_size: u32 = parse_env_or("DATABASE_POOL_MAX_SIZE", DEFAULT_DATABASE_POOL_MAX_SIZE)?;
 
@@ -102,7 +103,8 @@ impl PgPoolExt for PgPool {
     fn for_test() -> Result<Self> {
         use diesel::Connection as _;
 
-        let database_url: String = parse_env_or("DATABASE_URL", DEFAULT_DATABASE_URL)?;
+        let database_url =
+            parse_env_or_file("DATABASE_URL")?.unwrap_or_else(|| DEFAULT_DATABASE_URL.to_string());
 
         let manager = ConnectionManager::<PgConnection>::new(database_url);
 
@@ -0,0 +1,333 @@
+//! # Utilities for encrypting fields at rest.
+//!
+//! [`EncryptedString`] and [`EncryptedJson`] hold plaintext values in memory
+//! and are encrypted with AES-256-GCM when written to the database. Each
+//! encrypted value is an envelope of the form
+//! `[key id length][key id][nonce][ciphertext]` so that keys can be rotated
+//! without re-encrypting existing rows.
+//!
+//! The [`Keyring`] is configurable with the following env variables:
+//!
+//! * `ENCRYPTION_KEYS=2022-06:<base64>,2022-01:<base64>` Sets the available
+//!   keys as a comma separated list of `id:key` pairs. Keys are base64
+//!   encoded and must be 32 bytes long.
+//!
+//! * `ENCRYPTION_KEY_ID=2022-06` Sets the key used to encrypt new values.
+//!   Defaults to the first key in `ENCRYPTION_KEYS`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! use bitski_common::encryption::Keyring;
+//!
+//! # fn main() -> Result<()> {
+//! let keyring = Keyring::default().with_key("2022-06", &[7; 32])?;
+//!
+//! let envelope = keyring.encrypt(b"secret")?;
+//! assert_ne!(envelope, b"secret");
+//! assert_eq!(keyring.decrypt(&envelope)?, b"secret");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+#[cfg(feature = "postgres")]
+use diesel::{sql_types::Binary, AsExpression, FromSqlRow};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+
+use crate::env::{parse_env, require_env_list};
+use crate::{Error, Result};
+
+/// AES-GCM nonce length in bytes.
+const NONCE_LENGTH: usize = 12;
+
+/// The global keyring used for database values.
+static KEYRING: OnceCell<Keyring> = OnceCell::new();
+
+/// A set of encryption keys identified by key id.
+#[derive(Clone, Default)]
+pub struct Keyring {
+    current_key_id: Option<String>,
+    keys: HashMap<String, Aes256Gcm>,
+}
+
+impl fmt::Debug for Keyring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keyring")
+            .field("current_key_id", &self.current_key_id)
+            .field("key_ids", &self.keys.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Keyring {
+    /// Creates a keyring from env variables.
+    pub fn from_env() -> Result<Self> {
+        let mut keyring = Self::default();
+
+        for entry in require_env_list::<String>("ENCRYPTION_KEYS")? {
+            let (id, key) = entry.split_once(':').ok_or_else(|| {
+                Error::invalid_argument()
+                    .with_message("Error parsing env ENCRYPTION_KEYS: expected id:key pairs")
+            })?;
+            let key = base64::decode(key).map_err(|err| {
+                Error::invalid_argument()
+                    .with_message(format!("Error parsing env ENCRYPTION_KEYS key {id}: {err}"))
+            })?;
+            keyring = keyring.with_key(id, &key)?;
+        }
+
+        if let Some(id) = parse_env::<String>("ENCRYPTION_KEY_ID")? {
+            keyring = keyring.with_current_key_id(id)?;
+        }
+
+        Ok(keyring)
+    }
+
+    /// Adds a 32 byte key.
+    ///
+    /// The first key added is used for encryption unless
+    /// [`Keyring::with_current_key_id`] is called.
+    pub fn with_key<I: Into<String>>(mut self, id: I, key: &[u8]) -> Result<Self> {
+        let id = id.into();
+        if id.is_empty() || id.len() > usize::from(u8::MAX) {
+            return Err(
+                Error::invalid_argument().with_message(format!("Invalid encryption key id {id:?}"))
+            );
+        }
+
+        let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| {
+            Error::invalid_argument()
+                .with_message(format!("Invalid encryption key {id}: expected 32 bytes"))
+        })?;
+
+        if self.current_key_id.is_none() {
+            self.current_key_id = Some(id.clone());
+        }
+        self.keys.insert(id, cipher);
+        Ok(self)
+    }
+
+    /// Sets the key used to encrypt new values.
+    pub fn with_current_key_id<I: Into<String>>(mut self, id: I) -> Result<Self> {
+        let id = id.into();
+        if !self.keys.contains_key(&id) {
+            return Err(Error::not_found().with_message(format!("Unknown encryption key {id}")));
+        }
+        self.current_key_id = Some(id);
+        Ok(self)
+    }
+
+    /// Encrypts a value with the current key.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let id = self
+            .current_key_id
+            .as_ref()
+            .ok_or_else(|| Error::failed_precondition().with_message("No encryption keys"))?;
+        let cipher = &self.keys[id];
+
+        let mut envelope = Vec::with_capacity(1 + id.len() + NONCE_LENGTH + plaintext.len() + 16);
+        envelope.push(id.len() as u8);
+        envelope.extend_from_slice(id.as_bytes());
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: &envelope,
+                },
+            )
+            .map_err(|_| Error::internal().with_message("Error encrypting value"))?;
+
+        envelope.extend_from_slice(&nonce);
+        envelope.extend_from_slice(&ciphertext);
+        Ok(envelope)
+    }
+
+    /// Decrypts a value with the key identified in its envelope.
+    pub fn decrypt(&self, envelope: &[u8]) -> Result<Vec<u8>> {
+        let invalid_envelope = || Error::data_loss().with_message("Invalid encrypted value");
+
+        let id_length = usize::from(*envelope.first().ok_or_else(invalid_envelope)?);
+        let header_length = 1 + id_length;
+        if envelope.len() < header_length + NONCE_LENGTH {
+            return Err(invalid_envelope());
+        }
+
+        let (header, rest) = envelope.split_at(header_length);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LENGTH);
+
+        let id = std::str::from_utf8(&header[1..]).map_err(|_| invalid_envelope())?;
+        let cipher = self.keys.get(id).ok_or_else(|| {
+            Error::internal().with_message(format!("Unknown encryption key {id}"))
+        })?;
+
+        cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: header,
+                },
+            )
+            .map_err(|_| Error::data_loss().with_message("Error decrypting value"))
+    }
+}
+
+/// Sets the global keyring used for database values.
+///
+/// If not called, the global keyring is created from env variables on first
+/// use.
+pub fn init_keyring(keyring: Keyring) -> Result<()> {
+    KEYRING
+        .set(keyring)
+        .map_err(|_| Error::already_exists().with_message("Keyring is already initialized"))
+}
+
+/// Gets the global keyring used for database values.
+pub fn keyring() -> Result<&'static Keyring> {
+    KEYRING.get_or_try_init(Keyring::from_env)
+}
+
+/// A string that is encrypted at rest.
+///
+/// Serializes as the plaintext string.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "postgres", derive(AsExpression, FromSqlRow))]
+#[cfg_attr(feature = "postgres", sql_type = "Binary")]
+#[serde(transparent)]
+pub struct EncryptedString(String);
+
+impl EncryptedString {
+    /// Returns the plaintext string.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Debug for EncryptedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EncryptedString(***)")
+    }
+}
+
+impl Deref for EncryptedString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<String> for EncryptedString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for EncryptedString {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+/// A JSON value that is encrypted at rest.
+///
+/// Serializes as the plaintext value.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "postgres", derive(AsExpression, FromSqlRow))]
+#[cfg_attr(feature = "postgres", sql_type = "Binary")]
+#[serde(transparent)]
+pub struct EncryptedJson<T>(T);
+
+impl<T> EncryptedJson<T> {
+    /// Creates a new encrypted JSON value.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the plaintext value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Debug for EncryptedJson<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EncryptedJson(***)")
+    }
+}
+
+impl<T> Deref for EncryptedJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use std::io::Write;
+
+    use diesel::deserialize::{self, FromSql};
+    use diesel::pg::Pg;
+    use diesel::serialize::{self, IsNull, Output, ToSql};
+    use diesel::sql_types::Binary;
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use super::{keyring, EncryptedJson, EncryptedString};
+    use crate::Error;
+
+    impl ToSql<Binary, Pg> for EncryptedString {
+        fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+            let envelope = keyring()?.encrypt(self.0.as_bytes())?;
+            out.write_all(&envelope)?;
+            Ok(IsNull::No)
+        }
+    }
+
+    impl FromSql<Binary, Pg> for EncryptedString {
+        fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+            let envelope: Vec<u8> = FromSql::<Binary, Pg>::from_sql(bytes)?;
+            let plaintext = keyring()?.decrypt(&envelope)?;
+            let value = String::from_utf8(plaintext).map_err(|err| {
+                Error::data_loss()
+                    .with_message("Encrypted value is not UTF-8")
+                    .with_source(err)
+            })?;
+            Ok(Self(value))
+        }
+    }
+
+    impl<T: Serialize> ToSql<Binary, Pg> for EncryptedJson<T> {
+        fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+            let plaintext = serde_json::to_vec(&self.0)?;
+            let envelope = keyring()?.encrypt(&plaintext)?;
+            out.write_all(&envelope)?;
+            Ok(IsNull::No)
+        }
+    }
+
+    impl<T: DeserializeOwned> FromSql<Binary, Pg> for EncryptedJson<T> {
+        fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+            let envelope: Vec<u8> = FromSql::<Binary, Pg>::from_sql(bytes)?;
+            let plaintext = keyring()?.decrypt(&envelope)?;
+            let value = serde_json::from_slice(&plaintext).map_err(|err| {
+                Error::data_loss()
+                    .with_message(format!("Error deserializing encrypted value: {err}"))
+                    .with_source(err)
+            })?;
+            Ok(Self(value))
+        }
+    }
+}
@@ -207,6 +207,37 @@ impl Error {
         self
     }
 
+    /// Gets the HTTP status code for this error.
+    ///
+    /// Returns the custom status code if one was set, otherwise the status code
+    /// mapped from the error variant.
+    pub fn http_status_code(&self) -> hyper::StatusCode {
+        #[cfg(feature = "actix-web")]
+        if let Some(status_code) = self.info().http_status_code {
+            return status_code;
+        }
+
+        // https://github.com/googleapis/googleapis/blob/master/google/rpc/code.proto
+        match self {
+            Error::Cancelled(_) => hyper::StatusCode::from_u16(499).unwrap(),
+            Error::Unknown(_) => hyper::StatusCode::INTERNAL_SERVER_ERROR,
+            Error::InvalidArgument(_) => hyper::StatusCode::BAD_REQUEST,
+            Error::DeadlineExceeded(_) => hyper::StatusCode::GATEWAY_TIMEOUT,
+            Error::NotFound(_) => hyper::StatusCode::NOT_FOUND,
+            Error::AlreadyExists(_) => hyper::StatusCode::CONFLICT,
+            Error::PermissionDenied(_) => hyper::StatusCode::FORBIDDEN,
+            Error::ResourceExhausted(_) => hyper::StatusCode::TOO_MANY_REQUESTS,
+            Error::FailedPrecondition(_) => hyper::StatusCode::BAD_REQUEST,
+            Error::Aborted(_) => hyper::StatusCode::CONFLICT,
+            Error::OutOfRange(_) => hyper::StatusCode::BAD_REQUEST,
+            Error::Unimplemented(_) => hyper::StatusCode::NOT_IMPLEMENTED,
+            Error::Internal(_) => hyper::StatusCode::INTERNAL_SERVER_ERROR,
+            Error::Unavailable(_) => hyper::StatusCode::SERVICE_UNAVAILABLE,
+            Error::DataLoss(_) => hyper::StatusCode::INTERNAL_SERVER_ERROR,
+            Error::Unauthenticated(_) => hyper::StatusCode::UNAUTHORIZED,
+        }
+    }
+
     /// The operation was cancelled, typically by the caller.
     ///
     /// HTTP Mapping: 499 Client Closed Request
@@ -619,29 +650,7 @@ impl From<actix_web::error::ReadlinesError> for Error {
 #[cfg_attr(docsrs, doc(cfg(feature = "actix-web")))]
 impl ResponseError for Error {
     fn status_code(&self) -> http::StatusCode {
-        if let Some(status_code) = self.info().http_status_code {
-            return status_code;
-        }
-
-        // https://github.com/googleapis/googleapis/blob/master/google/rpc/code.proto
-        match self {
-            Error::Cancelled(_) => http::StatusCode::from_u16(499).unwrap(),
-            Error::Unknown(_) => http::StatusCode::INTERNAL_SERVER_ERROR,
-            Error::InvalidArgument(_) => http::StatusCode::BAD_REQUEST,
-            Error::DeadlineExceeded(_) => http::StatusCode::GATEWAY_TIMEOUT,
-            Error::NotFound(_) => http::StatusCode::NOT_FOUND,
-            Error::AlreadyExists(_) => http::StatusCode::CONFLICT,
-            Error::PermissionDenied(_) => http::StatusCode::FORBIDDEN,
-            Error::ResourceExhausted(_) => http::StatusCode::TOO_MANY_REQUESTS,
-            Error::FailedPrecondition(_) => http::StatusCode::BAD_REQUEST,
-            Error::Aborted(_) => http::StatusCode::CONFLICT,
-            Error::OutOfRange(_) => http::StatusCode::BAD_REQUEST,
-            Error::Unimplemented(_) => http::StatusCode::NOT_IMPLEMENTED,
-            Error::Internal(_) => http::StatusCode::INTERNAL_SERVER_ERROR,
-            Error::Unavailable(_) => http::StatusCode::SERVICE_UNAVAILABLE,
-            Error::DataLoss(_) => http::StatusCode::INTERNAL_SERVER_ERROR,
-            Error::Unauthenticated(_) => http::StatusCode::UNAUTHORIZED,
-        }
+        self.http_status_code()
     }
 
     fn error_response(&self) -> actix_web::HttpResponse {
@@ -699,6 +708,16 @@ impl From<awc::error::JsonPayloadError> for Error {
     }
 }
 
+#[cfg(feature = "cli")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cli")))]
+impl From<clap::Error> for Error {
+    fn from(err: clap::Error) -> Self {
+        Error::invalid_argument()
+            .with_message(err.to_string())
+            .with_source(err)
+    }
+}
+
 #[cfg(feature = "humantime")]
 #[cfg_attr(docsrs, doc(cfg(feature = "humantime")))]
 impl From<humantime::DurationError> for Error {
@@ -0,0 +1,205 @@
+//! # Utilities for experiments.
+//!
+//! An [`Experiment`] deterministically buckets a subject, such as a tenant or
+//! user id, into one of its weighted variants. The same subject is always
+//! assigned the same variant for as long as the experiment name and variants
+//! don't change.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bitski_common::experiments::{Assignments, Experiment};
+//!
+//! let experiment = Experiment::new("checkout-flow")
+//!     .with_variant("control", 90)
+//!     .with_variant("one-click", 10);
+//!
+//! let assignment = experiment.assign("user-1234").unwrap();
+//! assert_eq!(experiment.assign("user-1234"), Some(assignment.clone()));
+//!
+//! let mut assignments = Assignments::default();
+//! assignments.insert(assignment);
+//! assert!(matches!(
+//!     assignments.variant("checkout-flow"),
+//!     Some("control") | Some("one-click")
+//! ));
+//! ```
+
+use hyper::Request;
+use opentelemetry::KeyValue;
+use tracing_opentelemetry::OtelData;
+use tracing_subscriber::registry::{LookupSpan, Registry};
+
+/// FNV-1a offset basis.
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+
+/// FNV-1a prime.
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// An experiment with weighted variants.
+#[derive(Clone, Debug)]
+pub struct Experiment {
+    name: String,
+    variants: Vec<(String, u32)>,
+}
+
+impl Experiment {
+    /// Creates a new experiment without variants.
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        Self {
+            name: name.into(),
+            variants: vec![],
+        }
+    }
+
+    /// Adds a variant with a relative weight.
+    pub fn with_variant<N: Into<String>>(mut self, name: N, weight: u32) -> Self {
+        self.variants.push((name.into(), weight));
+        self
+    }
+
+    /// Gets the experiment name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Assigns a subject to a variant.
+    ///
+    /// Returns `None` if the experiment has no variants with a positive weight.
+    pub fn assign(&self, subject: &str) -> Option<Assignment> {
+        let total_weight: u64 = self
+            .variants
+            .iter()
+            .map(|(_, weight)| u64::from(*weight))
+            .sum();
+        if total_weight == 0 {
+            return None;
+        }
+
+        let mut bucket = bucket_hash(&self.name, subject) % total_weight;
+        for (variant, weight) in self.variants.iter() {
+            let weight = u64::from(*weight);
+            if bucket < weight {
+                return Some(Assignment {
+                    experiment: self.name.clone(),
+                    variant: variant.clone(),
+                });
+            }
+            bucket -= weight;
+        }
+
+        None
+    }
+}
+
+/// The variant of an experiment assigned to a subject.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Assignment {
+    experiment: String,
+    variant: String,
+}
+
+impl Assignment {
+    /// Gets the experiment name.
+    pub fn experiment(&self) -> &str {
+        &self.experiment
+    }
+
+    /// Gets the assigned variant name.
+    pub fn variant(&self) -> &str {
+        &self.variant
+    }
+
+    /// Records the assignment as an `experiment.<name>` attribute on the
+    /// OpenTelemetry span of the current tracing span.
+    pub fn record(&self) {
+        let attribute = KeyValue::new(
+            format!("experiment.{}", self.experiment),
+            self.variant.clone(),
+        );
+        set_attribute(&tracing::Span::current(), attribute);
+    }
+}
+
+/// Experiment assignments for a request.
+///
+/// Stored in request extensions by [`assign_request`].
+#[derive(Clone, Debug, Default)]
+pub struct Assignments(Vec<Assignment>);
+
+impl Assignments {
+    /// Adds an assignment, replacing any previous assignment for the same
+    /// experiment.
+    pub fn insert(&mut self, assignment: Assignment) {
+        self.0
+            .retain(|existing| existing.experiment != assignment.experiment);
+        self.0.push(assignment);
+    }
+
+    /// Gets the assigned variant for an experiment.
+    pub fn variant(&self, experiment: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|assignment| assignment.experiment == experiment)
+            .map(Assignment::variant)
+    }
+
+    /// Returns an iterator over the assignments.
+    pub fn iter(&self) -> impl Iterator<Item = &Assignment> {
+        self.0.iter()
+    }
+}
+
+/// Assigns a request to variants of the given experiments.
+///
+/// The assignments are recorded on the active span and stored in the request
+/// extensions as [`Assignments`].
+pub fn assign_request<B>(request: &mut Request<B>, subject: &str, experiments: &[Experiment]) {
+    let extensions = request.extensions_mut();
+    let mut assignments = extensions.remove::<Assignments>().unwrap_or_default();
+    for experiment in experiments {
+        if let Some(assignment) = experiment.assign(subject) {
+            assignment.record();
+            assignments.insert(assignment);
+        }
+    }
+    extensions.insert(assignments);
+}
+
+/// Sets an attribute on the OpenTelemetry span that
+/// [`tracing_opentelemetry`] exports for a tracing span, which
+/// [`OpenTelemetrySpanExt`][tracing_opentelemetry::OpenTelemetrySpanExt]
+/// only supports in later versions.
+fn set_attribute(span: &tracing::Span, attribute: KeyValue) {
+    span.with_subscriber(|(id, dispatch)| {
+        let span = dispatch
+            .downcast_ref::<Registry>()
+            .and_then(|registry| registry.span(id));
+        if let Some(span) = span {
+            if let Some(data) = span.extensions_mut().get_mut::<OtelData>() {
+                data.builder
+                    .attributes
+                    .get_or_insert_with(Vec::new)
+                    .push(attribute);
+            }
+        }
+    });
+}
+
+/// Hashes an experiment name and subject with 64-bit FNV-1a.
+///
+/// Each part is prefixed with its length so that no two pairs hash the same
+/// input, such as `a:b` and `c` against `a` and `b:c`. The hash must be
+/// stable across builds and platforms so that subjects stay in the same
+/// bucket.
+fn bucket_hash(experiment: &str, subject: &str) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    for part in [experiment, subject] {
+        let len = (part.len() as u64).to_le_bytes();
+        for byte in len.iter().copied().chain(part.bytes()) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    hash
+}
@@ -4,11 +4,34 @@
 #[cfg(feature = "actix-web")]
 #[cfg_attr(docsrs, doc(cfg(feature = "actix-web")))]
 pub mod actix_web;
+pub mod build_info;
+#[cfg(feature = "cli")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cli")))]
+pub mod cli;
+#[cfg(feature = "dependency-policies")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dependency-policies")))]
+pub mod dependency_policies;
 #[cfg(all(feature = "diesel", feature = "postgres", feature = "r2d2"))]
 #[cfg_attr(docsrs, doc(cfg(feature = "diesel")))]
 pub mod diesel;
+#[cfg(feature = "encryption")]
+#[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+pub mod encryption;
 pub mod env;
 pub mod error;
+pub mod experiments;
+#[cfg(feature = "openapi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "openapi")))]
+pub mod openapi;
+#[cfg(feature = "request-signing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "request-signing")))]
+pub mod request_signing;
+#[cfg(feature = "secrets")]
+#[cfg_attr(docsrs, doc(cfg(feature = "secrets")))]
+pub mod secrets;
+#[cfg(feature = "test")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test")))]
+pub mod snapshot;
 pub mod task;
 pub mod telemetry;
 #[cfg(feature = "tower")]
@@ -19,7 +42,7 @@ pub mod tower;
 #[cfg(feature = "actix-web")]
 #[cfg_attr(docsrs, doc(cfg(feature = "actix-web")))]
 pub use actix_web_opentelemetry;
-pub use bitski_common_macros::with_instruments;
+pub use bitski_common_macros::{with_instruments, FromEnv, IntoCommonError};
 #[cfg(feature = "humantime")]
 #[cfg_attr(docsrs, doc(cfg(feature = "humantime")))]
 pub use humantime;
@@ -30,7 +53,12 @@ pub use sentry;
 pub use sentry_actix;
 pub use sentry_tracing;
 pub use tracing_opentelemetry;
+#[cfg(feature = "url")]
+#[cfg_attr(docsrs, doc(cfg(feature = "url")))]
+pub use url;
 
+pub use crate::build_info::build_info;
+pub use crate::env::FromEnv;
 pub use crate::error::Error;
 
 /// [`Result`] with a default error type of [`Error`].
@@ -0,0 +1,156 @@
+//! # Utilities for OpenAPI documents.
+//!
+//! Annotate Actix Web handlers with [`utoipa::path`] and collect them in a
+//! [`utoipa::OpenApi`] document. [`merge_openapi`] combines the documents of
+//! a service and documents the standard [`Error`][crate::Error] response on
+//! every operation, and [`openapi_json`] serves the result at
+//! `/openapi.json`.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use actix_web::{get, HttpServer};
+//! use anyhow::Result;
+//! use bitski_common::{
+//!     actix_web_app,
+//!     env::{init_env, parse_env_addr_or_default},
+//!     openapi::{merge_openapi, openapi_json, utoipa::OpenApi},
+//!     with_instruments,
+//! };
+//!
+//! /// Says hello.
+//! #[utoipa::path(responses((status = 200, description = "Greeting", body = String)))]
+//! #[get("/hello")]
+//! async fn hello() -> &'static str {
+//!     "Hello World!"
+//! }
+//!
+//! #[derive(OpenApi)]
+//! #[openapi(paths(hello))]
+//! struct ApiDoc;
+//!
+//! #[with_instruments]
+//! #[actix_web::main]
+//! async fn main() -> Result<()> {
+//!     init_env();
+//!
+//!     let openapi = merge_openapi(ApiDoc::openapi(), []);
+//!     let addr = parse_env_addr_or_default()?;
+//!
+//!     HttpServer::new(move || {
+//!         actix_web_app!()
+//!             .service(hello)
+//!             .service(openapi_json(&openapi).unwrap())
+//!     })
+//!     .bind(addr)?
+//!     .run()
+//!     .await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use actix_web::{http::header::ContentType, web, HttpResponse, Resource};
+use serde::{Deserialize, Serialize};
+use utoipa::openapi::{Components, ContentBuilder, OpenApi, Ref, RefOr, Response, ResponseBuilder};
+use utoipa::ToSchema;
+
+pub use utoipa;
+
+use crate::{Error, Result};
+
+/// The path at which [`openapi_json`] serves the document.
+pub const OPENAPI_JSON_PATH: &str = "/openapi.json";
+
+/// The name of the shared error response component.
+pub const ERROR_RESPONSE: &str = "Error";
+
+/// The JSON body of an [`Error`] response.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ErrorBody {
+    /// The error details.
+    pub error: ErrorDetail,
+}
+
+/// The details of an [`Error`] response.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ErrorDetail {
+    /// A description of the error.
+    pub message: String,
+}
+
+impl From<&Error> for ErrorBody {
+    fn from(err: &Error) -> Self {
+        Self {
+            error: ErrorDetail {
+                message: err.to_string(),
+            },
+        }
+    }
+}
+
+/// Merges OpenAPI documents into `base` and adds the standard error response.
+///
+/// The [`ErrorBody`] schema and an [`ERROR_RESPONSE`] response are added to
+/// the components, and every operation without a `default` response is given
+/// one that references it.
+pub fn merge_openapi<I: IntoIterator<Item = OpenApi>>(mut base: OpenApi, docs: I) -> OpenApi {
+    for doc in docs {
+        base.merge(doc);
+    }
+
+    let components = base.components.get_or_insert_with(Components::new);
+    for (name, schema) in [ErrorBody::schema(), ErrorDetail::schema()] {
+        components.schemas.insert(name.to_string(), schema);
+    }
+    components
+        .responses
+        .insert(ERROR_RESPONSE.to_string(), error_response().into());
+
+    for path_item in base.paths.paths.values_mut() {
+        for operation in path_item.operations.values_mut() {
+            operation
+                .responses
+                .responses
+                .entry("default".to_string())
+                .or_insert_with(|| RefOr::Ref(Ref::from_response_name(ERROR_RESPONSE)));
+        }
+    }
+
+    base
+}
+
+/// Creates a resource that serves an OpenAPI document at `/openapi.json`.
+///
+/// The document is serialized once when the resource is created.
+pub fn openapi_json(openapi: &OpenApi) -> Result<Resource> {
+    let json = openapi.to_json().map_err(|err| {
+        Error::internal()
+            .with_message(format!("Error serializing OpenAPI document: {err}"))
+            .with_source(err)
+    })?;
+
+    Ok(
+        web::resource(OPENAPI_JSON_PATH).route(web::get().to(move || {
+            let json = json.clone();
+            async move {
+                HttpResponse::Ok()
+                    .content_type(ContentType::json())
+                    .body(json)
+            }
+        })),
+    )
+}
+
+/// Builds the shared error response.
+fn error_response() -> Response {
+    ResponseBuilder::new()
+        .description("Error response")
+        .content(
+            "application/json",
+            ContentBuilder::new()
+                .schema(Ref::from_schema_name("ErrorBody"))
+                .build(),
+        )
+        .build()
+}
@@ -0,0 +1,552 @@
+//! # Utilities for signing requests between internal services.
+//!
+//! Requests are signed with HMAC-SHA256 over a canonical request string:
+//!
+//! ```text
+//! METHOD
+//! /path?query
+//! TIMESTAMP
+//! hex(sha256(body))
+//! ```
+//!
+//! The signature, key id and timestamp are sent in the
+//! `x-bitski-signature`, `x-bitski-key-id` and `x-bitski-timestamp` headers.
+//! Servers accept any configured key id so that keys can be rotated by adding
+//! the new key to servers before switching clients to it.
+//!
+//! [`RequestSigningKeys`] are configurable with the following env variables:
+//!
+//! * `REQUEST_SIGNING_KEYS=2022-06:<base64>,2022-01:<base64>` Sets the
+//!   available keys as a comma separated list of `id:key` pairs. Keys are
+//!   base64 encoded.
+//!
+//! * `REQUEST_SIGNING_KEY_ID=2022-06` Sets the key used to sign requests.
+//!   Defaults to the first key in `REQUEST_SIGNING_KEYS`.
+//!
+//! * `REQUEST_SIGNING_MAX_SKEW_SECS=300` Sets the maximum difference between
+//!   a request timestamp and the server clock.
+//!
+//! * `REQUEST_SIGNING_MAX_BODY_BYTES=4194304` Sets the maximum size of a
+//!   request body buffered by `RequestVerificationLayer` to verify its
+//!   signature.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use anyhow::Result;
+//! use bitski_common::request_signing::RequestSigningKeys;
+//!
+//! # fn main() -> Result<()> {
+//! let keys = RequestSigningKeys::default().with_key("2022-06", b"secret")?;
+//!
+//! let headers = keys.sign("POST", "/v1/transfers", b"{}")?;
+//! keys.verify("POST", "/v1/transfers", &headers, b"{}")?;
+//! assert!(keys.verify("POST", "/v1/transfers", &headers, b"[]").is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::env::{parse_env, parse_env_or, require_env_list};
+use crate::{Error, Result};
+
+/// Header containing the request signature.
+pub const SIGNATURE_HEADER: &str = "x-bitski-signature";
+
+/// Header containing the id of the key used to sign the request.
+pub const KEY_ID_HEADER: &str = "x-bitski-key-id";
+
+/// Header containing the request timestamp in seconds since the Unix epoch.
+pub const TIMESTAMP_HEADER: &str = "x-bitski-timestamp";
+
+/// Default maximum difference between a request timestamp and the server clock.
+pub const DEFAULT_REQUEST_SIGNING_MAX_SKEW_SECS: u64 = 300;
+
+/// Default maximum size of a request body buffered to verify its signature,
+/// which matches the default maximum message size of Tonic.
+pub const DEFAULT_REQUEST_SIGNING_MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Headers for a signed request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureHeaders {
+    /// The id of the key used to sign the request.
+    pub key_id: String,
+
+    /// The request timestamp in seconds since the Unix epoch.
+    pub timestamp: u64,
+
+    /// The base64 encoded request signature.
+    pub signature: String,
+}
+
+impl SignatureHeaders {
+    /// Returns the headers as `(name, value)` pairs.
+    pub fn to_pairs(&self) -> [(&'static str, String); 3] {
+        [
+            (KEY_ID_HEADER, self.key_id.clone()),
+            (TIMESTAMP_HEADER, self.timestamp.to_string()),
+            (SIGNATURE_HEADER, self.signature.clone()),
+        ]
+    }
+
+    /// Reads the headers from a [`hyper::HeaderMap`].
+    pub fn from_header_map(headers: &hyper::HeaderMap) -> Result<Self> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .ok_or_else(|| {
+                    Error::unauthenticated().with_message(format!("Missing header {name}"))
+                })
+        };
+
+        let timestamp = header(TIMESTAMP_HEADER)?.parse().map_err(|_| {
+            Error::unauthenticated().with_message(format!("Invalid header {TIMESTAMP_HEADER}"))
+        })?;
+
+        Ok(Self {
+            key_id: header(KEY_ID_HEADER)?.to_owned(),
+            timestamp,
+            signature: header(SIGNATURE_HEADER)?.to_owned(),
+        })
+    }
+}
+
+/// A set of request signing keys identified by key id.
+#[derive(Clone)]
+pub struct RequestSigningKeys {
+    current_key_id: Option<String>,
+    keys: HashMap<String, Vec<u8>>,
+    max_skew: Duration,
+    max_body_size: usize,
+}
+
+impl Default for RequestSigningKeys {
+    fn default() -> Self {
+        Self {
+            current_key_id: None,
+            keys: HashMap::new(),
+            max_skew: Duration::from_secs(DEFAULT_REQUEST_SIGNING_MAX_SKEW_SECS),
+            max_body_size: DEFAULT_REQUEST_SIGNING_MAX_BODY_BYTES,
+        }
+    }
+}
+
+impl fmt::Debug for RequestSigningKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestSigningKeys")
+            .field("current_key_id", &self.current_key_id)
+            .field("key_ids", &self.keys.keys().collect::<Vec<_>>())
+            .field("max_skew", &self.max_skew)
+            .field("max_body_size", &self.max_body_size)
+            .finish()
+    }
+}
+
+impl RequestSigningKeys {
+    /// Creates request signing keys from env variables.
+    pub fn from_env() -> Result<Self> {
+        let mut keys = Self::default();
+
+        for entry in require_env_list::<String>("REQUEST_SIGNING_KEYS")? {
+            let (id, key) = entry.split_once(':').ok_or_else(|| {
+                Error::invalid_argument()
+                    .with_message("Error parsing env REQUEST_SIGNING_KEYS: expected id:key pairs")
+            })?;
+            let key = base64::decode(key).map_err(|err| {
+                Error::invalid_argument().with_message(format!(
+                    "Error parsing env REQUEST_SIGNING_KEYS key {id}: {err}"
+                ))
+            })?;
+            keys = keys.with_key(id, &key)?;
+        }
+
+        if let Some(id) = parse_env::<String>("REQUEST_SIGNING_KEY_ID")? {
+            keys = keys.with_current_key_id(id)?;
+        }
+
+        let max_skew: u64 = parse_env_or(
+            "REQUEST_SIGNING_MAX_SKEW_SECS",
+            DEFAULT_REQUEST_SIGNING_MAX_SKEW_SECS,
+        )?;
+
+        let max_body_size = parse_env_or(
+            "REQUEST_SIGNING_MAX_BODY_BYTES",
+            DEFAULT_REQUEST_SIGNING_MAX_BODY_BYTES,
+        )?;
+
+        Ok(keys
+            .with_max_skew(Duration::from_secs(max_skew))
+            .with_max_body_size(max_body_size))
+    }
+
+    /// Adds a key.
+    ///
+    /// The first key added is used for signing unless
+    /// [`RequestSigningKeys::with_current_key_id`] is called.
+    pub fn with_key<I: Into<String>>(mut self, id: I, key: &[u8]) -> Result<Self> {
+        let id = id.into();
+        if id.is_empty() || key.is_empty() {
+            return Err(Error::invalid_argument()
+                .with_message(format!("Invalid request signing key {id:?}")));
+        }
+
+        if self.current_key_id.is_none() {
+            self.current_key_id = Some(id.clone());
+        }
+        self.keys.insert(id, key.to_vec());
+        Ok(self)
+    }
+
+    /// Sets the key used to sign requests.
+    pub fn with_current_key_id<I: Into<String>>(mut self, id: I) -> Result<Self> {
+        let id = id.into();
+        if !self.keys.contains_key(&id) {
+            return Err(
+                Error::not_found().with_message(format!("Unknown request signing key {id}"))
+            );
+        }
+        self.current_key_id = Some(id);
+        Ok(self)
+    }
+
+    /// Sets the maximum difference between a request timestamp and the clock.
+    pub fn with_max_skew(mut self, max_skew: Duration) -> Self {
+        self.max_skew = max_skew;
+        self
+    }
+
+    /// Sets the maximum size of a request body buffered by
+    /// `RequestVerificationLayer`.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Signs a request with the current key and timestamp.
+    pub fn sign(
+        &self,
+        method: &str,
+        path_and_query: &str,
+        body: &[u8],
+    ) -> Result<SignatureHeaders> {
+        self.sign_at(method, path_and_query, body, unix_timestamp())
+    }
+
+    /// Signs a request with the current key at the given timestamp.
+    pub fn sign_at(
+        &self,
+        method: &str,
+        path_and_query: &str,
+        body: &[u8],
+        timestamp: u64,
+    ) -> Result<SignatureHeaders> {
+        let key_id = self
+            .current_key_id
+            .as_ref()
+            .ok_or_else(|| Error::failed_precondition().with_message("No request signing keys"))?;
+
+        let mut mac = self.mac(key_id)?;
+        mac.update(canonical_request(method, path_and_query, timestamp, body).as_bytes());
+
+        Ok(SignatureHeaders {
+            key_id: key_id.clone(),
+            timestamp,
+            signature: base64::encode(mac.finalize().into_bytes()),
+        })
+    }
+
+    /// Verifies the key id and timestamp of a request, which doesn't require
+    /// its body.
+    pub fn verify_headers(&self, headers: &SignatureHeaders) -> Result<()> {
+        let now = unix_timestamp();
+        if now.abs_diff(headers.timestamp) > self.max_skew.as_secs() {
+            return Err(Error::unauthenticated().with_message("Request timestamp is out of range"));
+        }
+
+        if !self.keys.contains_key(&headers.key_id) {
+            return Err(Error::unauthenticated()
+                .with_message(format!("Unknown request signing key {}", headers.key_id)));
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the signature and timestamp of a request.
+    pub fn verify(
+        &self,
+        method: &str,
+        path_and_query: &str,
+        headers: &SignatureHeaders,
+        body: &[u8],
+    ) -> Result<()> {
+        self.verify_headers(headers)?;
+
+        let signature = base64::decode(&headers.signature)
+            .map_err(|_| Error::unauthenticated().with_message("Invalid request signature"))?;
+
+        let mut mac = self.mac(&headers.key_id)?;
+        mac.update(canonical_request(method, path_and_query, headers.timestamp, body).as_bytes());
+        mac.verify_slice(&signature)
+            .map_err(|_| Error::unauthenticated().with_message("Invalid request signature"))
+    }
+
+    /// Signs a [`reqwest::Request`] in place.
+    ///
+    /// Streaming bodies can't be signed.
+    #[cfg(feature = "reqwest")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
+    pub fn sign_reqwest_request(&self, request: &mut reqwest::Request) -> Result<()> {
+        let body = match request.body() {
+            Some(body) => body.as_bytes().ok_or_else(|| {
+                Error::invalid_argument().with_message("Streaming request bodies can't be signed")
+            })?,
+            None => &[],
+        };
+
+        let url = request.url();
+        let path_and_query = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_owned(),
+        };
+
+        let headers = self.sign(request.method().as_str(), &path_and_query, body)?;
+        for (name, value) in headers.to_pairs() {
+            let value = reqwest::header::HeaderValue::from_str(&value).map_err(|err| {
+                Error::internal()
+                    .with_message(format!("Invalid header {name}"))
+                    .with_source(err)
+            })?;
+            request.headers_mut().insert(name, value);
+        }
+
+        Ok(())
+    }
+
+    fn mac(&self, key_id: &str) -> Result<HmacSha256> {
+        let key = self.keys.get(key_id).ok_or_else(|| {
+            Error::not_found().with_message(format!("Unknown request signing key {key_id}"))
+        })?;
+        HmacSha256::new_from_slice(key).map_err(|_| {
+            Error::internal().with_message(format!("Invalid request signing key {key_id}"))
+        })
+    }
+}
+
+/// Builds the canonical request string that is signed.
+pub fn canonical_request(
+    method: &str,
+    path_and_query: &str,
+    timestamp: u64,
+    body: &[u8],
+) -> String {
+    let body_hash = Sha256::digest(body)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!(
+        "{}\n{path_and_query}\n{timestamp}\n{body_hash}",
+        method.to_ascii_uppercase()
+    )
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "tower")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+pub use self::server::{RequestVerification, RequestVerificationLayer};
+
+#[cfg(feature = "tower")]
+mod server {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+
+    use hyper::body::HttpBody;
+    use hyper::{Body, Request, Response, StatusCode};
+    use tower::{Layer, Service};
+
+    use super::{RequestSigningKeys, SignatureHeaders};
+    use crate::{Error, Result};
+
+    /// gRPC status code for `RESOURCE_EXHAUSTED`.
+    const GRPC_STATUS_RESOURCE_EXHAUSTED: &str = "8";
+
+    /// gRPC status code for `UNAUTHENTICATED`.
+    const GRPC_STATUS_UNAUTHENTICATED: &str = "16";
+
+    /// Layer that rejects requests without a valid signature.
+    ///
+    /// Request bodies are buffered in memory to verify the signature, after
+    /// the key id and timestamp are verified. Bodies larger than
+    /// [`RequestSigningKeys::with_max_body_size`] are rejected with
+    /// `413 Payload Too Large`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use anyhow::Result;
+    /// use bitski_common::request_signing::{RequestSigningKeys, RequestVerificationLayer};
+    /// use tonic::transport::Server;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let (_, health_service) = tonic_health::server::health_reporter();
+    ///
+    /// Server::builder()
+    ///     .layer(RequestVerificationLayer::new(RequestSigningKeys::from_env()?))
+    ///     .add_service(health_service)
+    ///     .serve("127.0.0.1:8000".parse()?)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Clone, Debug)]
+    pub struct RequestVerificationLayer {
+        keys: Arc<RequestSigningKeys>,
+    }
+
+    impl RequestVerificationLayer {
+        /// Creates a new `RequestVerificationLayer`.
+        pub fn new(keys: RequestSigningKeys) -> Self {
+            Self {
+                keys: Arc::new(keys),
+            }
+        }
+    }
+
+    impl<S> Layer<S> for RequestVerificationLayer {
+        type Service = RequestVerification<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            RequestVerification {
+                inner,
+                keys: self.keys.clone(),
+            }
+        }
+    }
+
+    /// Middleware that rejects requests without a valid signature.
+    ///
+    /// See [`RequestVerificationLayer`].
+    #[derive(Clone, Debug)]
+    pub struct RequestVerification<S> {
+        inner: S,
+        keys: Arc<RequestSigningKeys>,
+    }
+
+    impl<S, ResBody> Service<Request<Body>> for RequestVerification<S>
+    where
+        S: Service<Request<Body>, Response = Response<ResBody>> + Clone + Send + 'static,
+        S::Future: Send + 'static,
+        ResBody: Default,
+    {
+        type Response = S::Response;
+        type Error = S::Error;
+        #[allow(clippy::type_complexity)]
+        type Future =
+            Pin<Box<dyn Future<Output = std::result::Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(
+            &mut self,
+            cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, request: Request<Body>) -> Self::Future {
+            // The service that was polled ready must handle the request
+            let clone = self.inner.clone();
+            let mut inner = std::mem::replace(&mut self.inner, clone);
+            let keys = self.keys.clone();
+
+            Box::pin(async move {
+                let (parts, body) = request.into_parts();
+                let body = match verify(&keys, &parts, body).await {
+                    Ok(body) => body,
+                    Err(err) => {
+                        tracing::warn!("Rejected request to {}: {err}", parts.uri.path());
+                        return Ok(rejected(&err));
+                    }
+                };
+                inner.call(Request::from_parts(parts, body)).await
+            })
+        }
+    }
+
+    async fn verify(
+        keys: &RequestSigningKeys,
+        parts: &hyper::http::request::Parts,
+        body: Body,
+    ) -> Result<Body> {
+        let headers = SignatureHeaders::from_header_map(&parts.headers)?;
+        keys.verify_headers(&headers)?;
+        let body = read_body(body, keys.max_body_size).await?;
+        let path_and_query = parts
+            .uri
+            .path_and_query()
+            .map(|path_and_query| path_and_query.as_str())
+            .unwrap_or("/");
+
+        keys.verify(parts.method.as_str(), path_and_query, &headers, &body)?;
+        Ok(Body::from(body))
+    }
+
+    /// Reads a body of at most `max_size` bytes.
+    async fn read_body(mut body: Body, max_size: usize) -> Result<Vec<u8>> {
+        let too_large = || {
+            Error::resource_exhausted()
+                .with_message(format!("Request body is larger than {max_size} bytes"))
+        };
+        if body.size_hint().lower() > max_size as u64 {
+            return Err(too_large());
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk.map_err(|err| {
+                Error::invalid_argument()
+                    .with_message("Error reading request body")
+                    .with_source(err)
+            })?;
+            if bytes.len() + chunk.len() > max_size {
+                return Err(too_large());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+
+    fn rejected<B: Default>(err: &Error) -> Response<B> {
+        let (status, grpc_status) = if err.is_resource_exhausted() {
+            (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                GRPC_STATUS_RESOURCE_EXHAUSTED,
+            )
+        } else {
+            (StatusCode::UNAUTHORIZED, GRPC_STATUS_UNAUTHENTICATED)
+        };
+
+        let mut response = Response::new(B::default());
+        *response.status_mut() = status;
+        response.headers_mut().insert(
+            "grpc-status",
+            hyper::header::HeaderValue::from_static(grpc_status),
+        );
+        response
+    }
+}
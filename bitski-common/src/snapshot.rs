@@ -0,0 +1,199 @@
+//! # Utilities for snapshot testing serialized types.
+//!
+//! A snapshot is the pretty printed JSON serialization of a value, stored in
+//! `tests/snapshots/<name>.json` of the crate under test. Volatile fields such
+//! as timestamps or ids can be redacted with [JSON pointers] where `*` matches
+//! any array index or object key.
+//!
+//! Snapshots are configurable with the following env variables:
+//!
+//! * `UPDATE_SNAPSHOTS=false` Sets whether to overwrite snapshots that don't
+//!   match instead of failing. Missing snapshots are always written, and the
+//!   assertion fails so that they are reviewed before being committed.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bitski_common::snapshot::Snapshot;
+//! use serde_json::json;
+//!
+//! let body = json!({
+//!     "error": { "message": "Not found" },
+//!     "request_id": "4c4e0b4e-7d6f-4c43-a5f5-0b1f0f5b0c2e",
+//! });
+//!
+//! Snapshot::new("not_found_error")
+//!     .with_redaction("/request_id")
+//!     .assert_matches(&body);
+//! ```
+//!
+//! [JSON pointers]: https://datatracker.ietf.org/doc/html/rfc6901
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::env::parse_env_bool_or;
+use crate::{Error, Result};
+
+/// The value that replaces redacted fields.
+pub const REDACTED: &str = "[redacted]";
+
+/// A named JSON snapshot.
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    name: String,
+    dir: PathBuf,
+    redactions: Vec<String>,
+}
+
+impl Snapshot {
+    /// Creates a snapshot stored in `tests/snapshots` of the crate under test.
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+        Self {
+            name: name.into(),
+            dir: PathBuf::from(manifest_dir).join("tests").join("snapshots"),
+            redactions: vec![],
+        }
+    }
+
+    /// Sets the directory in which the snapshot is stored.
+    pub fn with_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.dir = dir.into();
+        self
+    }
+
+    /// Redacts the fields matching a JSON pointer.
+    ///
+    /// A `*` segment matches any array index or object key.
+    pub fn with_redaction<P: Into<String>>(mut self, pointer: P) -> Self {
+        self.redactions.push(pointer.into());
+        self
+    }
+
+    /// Gets the path of the snapshot file.
+    pub fn path(&self) -> PathBuf {
+        self.dir.join(format!("{}.json", self.name))
+    }
+
+    /// Serializes a value as redacted, pretty printed JSON.
+    pub fn to_json<T: Serialize + ?Sized>(&self, value: &T) -> Result<String> {
+        let mut value = serde_json::to_value(value).map_err(|err| {
+            Error::invalid_argument()
+                .with_message(format!("Error serializing snapshot {}: {err}", self.name))
+                .with_source(err)
+        })?;
+
+        for pointer in self.redactions.iter() {
+            let segments: Vec<String> = pointer
+                .split('/')
+                .skip(1)
+                .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+                .collect();
+            redact(&mut value, &segments);
+        }
+
+        let mut json = serde_json::to_string_pretty(&value).map_err(|err| {
+            Error::internal()
+                .with_message(format!("Error serializing snapshot {}: {err}", self.name))
+                .with_source(err)
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+
+    /// Asserts that a value matches the stored snapshot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the snapshot is missing or doesn't match, unless
+    /// `UPDATE_SNAPSHOTS=true`.
+    pub fn assert_matches<T: Serialize + ?Sized>(&self, value: &T) {
+        if let Err(err) = self.check(value) {
+            panic!("{err}");
+        }
+    }
+
+    fn check<T: Serialize + ?Sized>(&self, value: &T) -> Result<()> {
+        let actual = self.to_json(value)?;
+        let path = self.path();
+        let update = parse_env_bool_or("UPDATE_SNAPSHOTS", false)?;
+
+        let expected = match fs::read_to_string(&path) {
+            Ok(expected) => Some(expected),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+
+        match expected {
+            Some(expected) if expected == actual => Ok(()),
+            Some(expected) if !update => Err(Error::failed_precondition().with_message(format!(
+                "Snapshot {} does not match {}\n\nexpected:\n{expected}\nactual:\n{actual}\n\
+                 Set UPDATE_SNAPSHOTS=true to update the snapshot",
+                self.name,
+                path.display()
+            ))),
+            expected => {
+                fs::create_dir_all(&self.dir)?;
+                fs::write(&path, &actual)?;
+                if expected.is_none() {
+                    Err(Error::not_found().with_message(format!(
+                        "Snapshot {} was missing and has been written to {}",
+                        self.name,
+                        path.display()
+                    )))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// Asserts that a value matches the stored snapshot of the given name.
+///
+/// See [`Snapshot::assert_matches`].
+pub fn assert_json_snapshot<T: Serialize + ?Sized>(name: &str, value: &T) {
+    Snapshot::new(name).assert_matches(value)
+}
+
+fn redact(value: &mut Value, segments: &[String]) {
+    let (segment, rest) = match segments.split_first() {
+        Some(split) => split,
+        None => {
+            *value = Value::String(REDACTED.to_owned());
+            return;
+        }
+    };
+
+    match value {
+        Value::Object(map) if segment == "*" => {
+            for value in map.values_mut() {
+                redact(value, rest);
+            }
+        }
+        Value::Object(map) => {
+            if let Some(value) = map.get_mut(segment) {
+                redact(value, rest);
+            }
+        }
+        Value::Array(values) if segment == "*" => {
+            for value in values.iter_mut() {
+                redact(value, rest);
+            }
+        }
+        Value::Array(values) => {
+            if let Some(value) = segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| values.get_mut(index))
+            {
+                redact(value, rest);
+            }
+        }
+        _ => {}
+    }
+}
@@ -15,7 +15,7 @@ use sentry::ClientInitGuard;
 use tracing_subscriber::prelude::*;
 use uuid::Uuid;
 
-use crate::env::{parse_env, parse_env_or, parse_env_or_default, parse_env_or_else};
+use crate::env::{parse_env_bool_or_default, parse_env_or, parse_env_or_else, parse_env_or_file};
 use crate::Result;
 
 /// Default target to which the exporter is going to send spans or metrics.
@@ -179,12 +179,12 @@ fn init_tracing_for_test() {
 }
 
 fn init_sentry() -> Result<Option<ClientInitGuard>> {
-    let enable_sentry_traces: bool = parse_env_or_default("ENABLE_SENTRY_TRACES")?;
+    let enable_sentry_traces = parse_env_bool_or_default("ENABLE_SENTRY_TRACES")?;
     if !enable_sentry_traces {
         return Ok(None);
     }
 
-    let dsn: Option<sentry::types::Dsn> = parse_env("SENTRY_DSN")?;
+    let dsn: Option<sentry::types::Dsn> = parse_env_or_file("SENTRY_DSN")?;
     let dsn = if let Some(dsn) = dsn {
         dsn
     } else {
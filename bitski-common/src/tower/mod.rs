@@ -1,6 +1,9 @@
 //! # Utilities for Tower servers.
 
 mod span;
+#[cfg(feature = "transcoding")]
+#[cfg_attr(docsrs, doc(cfg(feature = "transcoding")))]
+pub mod transcoding;
 
 use std::time::Duration;
 
@@ -18,7 +21,7 @@ use tower_http::{
 };
 
 pub use self::span::*;
-use crate::env::parse_env;
+use crate::env::parse_env_aliased;
 use crate::Result;
 
 const DEFAULT_SERVER_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
@@ -81,10 +84,12 @@ impl BitskiLayerExt for BitskiLayer {
     /// The [`BitskiLayer`] is configurable with the following env variables:
     ///
     /// * `SERVER_REQUEST_TIMEOUT_MS=10000` Server request timeout for the Otel `service.namespace` resource.
+    ///   The deprecated `REQ_TIMEOUT_MS` is still read as a fallback.
     fn from_env() -> Result<Self> {
-        let server_request_timeout = parse_env("SERVER_REQUEST_TIMEOUT_MS")?
-            .map(Duration::from_millis)
-            .unwrap_or(DEFAULT_SERVER_REQUEST_TIMEOUT);
+        let server_request_timeout =
+            parse_env_aliased(&["SERVER_REQUEST_TIMEOUT_MS", "REQ_TIMEOUT_MS"])?
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_SERVER_REQUEST_TIMEOUT);
 
         let classifier = GrpcErrorsAsFailures::new()
             .with_success(GrpcCode::InvalidArgument)
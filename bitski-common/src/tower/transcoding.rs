@@ -0,0 +1,660 @@
+//! # Utilities for serving Tonic services over REST.
+//!
+//! [`RestTranscodingLayer`] maps REST routes to Tonic service methods so that
+//! a service can expose a JSON interface from the same implementation as its
+//! gRPC interface. gRPC requests and requests that don't match a route are
+//! passed through to the inner service.
+//!
+//! The request message is deserialized from a JSON object made of the request
+//! body, the query parameters and the path template variables, in increasing
+//! order of precedence. Query parameters and path template variables are
+//! parsed as the type of their request field, such as `?limit=10` for a
+//! `u32` field or `?verbose=true` for a `bool` field. Request bodies larger
+//! than [`DEFAULT_REST_TRANSCODING_MAX_BODY_BYTES`] are rejected with
+//! `413 Payload Too Large`. Errors are returned with the standard error body
+//! `{"error": {"message": "..."}}`.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use anyhow::Result;
+//! use bitski_common::tower::transcoding::{RestRoutes, RestTranscodingLayer};
+//! use hyper::Method;
+//! use serde::Serialize;
+//! use tonic::transport::Server;
+//!
+//! #[derive(Deserialize)]
+//! struct GetUserRequest {
+//!     user_id: String,
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct User {
+//!     user_id: String,
+//! }
+//!
+//! // Usually a method of the service implementation
+//! async fn get_user(
+//!     request: tonic::Request<GetUserRequest>,
+//! ) -> Result<tonic::Response<User>, tonic::Status> {
+//!     let user_id = request.into_inner().user_id;
+//!     Ok(tonic::Response::new(User { user_id }))
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let (_, health_service) = tonic_health::server::health_reporter();
+//!
+//!     let routes = RestRoutes::new().route(Method::GET, "/v1/users/{user_id}", get_user);
+//!
+//!     Server::builder()
+//!         .layer(RestTranscodingLayer::new(routes))
+//!         .add_service(health_service)
+//!         .serve("127.0.0.1:8000".parse()?)
+//!         .await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The messages must implement [`serde::Deserialize`] and [`serde::Serialize`],
+//! for example with `type_attribute` in `tonic_build`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use hyper::body::{Bytes, HttpBody};
+use hyper::{header, Body, HeaderMap, Method, Request, Response, StatusCode};
+use percent_encoding::percent_decode_str;
+use serde::de::value::{MapDeserializer, StringDeserializer};
+use serde::de::{DeserializeOwned, Deserializer, IntoDeserializer, Visitor};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tonic::{body::BoxBody, metadata::MetadataMap};
+use tower::{Layer, Service};
+
+use crate::{Error, Result};
+
+/// Default maximum size of a REST request body, which matches the default
+/// maximum message size of Tonic.
+pub const DEFAULT_REST_TRANSCODING_MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+type Handler = Arc<dyn Fn(HeaderMap, Fields) -> BoxFuture<Result<Value>> + Send + Sync>;
+
+type Fields = Vec<(String, Field)>;
+
+/// A set of REST routes mapped to Tonic service methods.
+#[derive(Clone, Default)]
+pub struct RestRoutes {
+    routes: Vec<Route>,
+}
+
+impl fmt::Debug for RestRoutes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.routes
+                    .iter()
+                    .map(|route| format!("{} {}", route.method, route.template)),
+            )
+            .finish()
+    }
+}
+
+impl RestRoutes {
+    /// Creates an empty set of routes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a route for a Tonic service method.
+    ///
+    /// Path template segments in braces, such as `{user_id}` in
+    /// `/v1/users/{user_id}`, match a single path segment and are set as the
+    /// request field of the same name. Routes are matched in the order they
+    /// are added.
+    ///
+    /// Request headers are passed to the handler as request metadata.
+    pub fn route<Req, Res, F, Fut>(mut self, method: Method, template: &str, handler: F) -> Self
+    where
+        Req: DeserializeOwned + Send + 'static,
+        Res: Serialize + 'static,
+        F: Fn(tonic::Request<Req>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<tonic::Response<Res>, tonic::Status>> + Send + 'static,
+    {
+        let handler: Handler = Arc::new(move |headers, fields: Fields| {
+            let fields = MapDeserializer::new(fields.into_iter());
+            let message = match Req::deserialize(fields) {
+                Ok(message) => message,
+                Err(err) => {
+                    return Box::pin(async move {
+                        Err(Error::invalid_argument()
+                            .with_message(format!("Invalid request: {err}"))
+                            .with_source(err))
+                    });
+                }
+            };
+
+            let mut request = tonic::Request::new(message);
+            *request.metadata_mut() = MetadataMap::from_headers(headers);
+
+            let response = handler(request);
+            Box::pin(async move {
+                let response = response.await?;
+                serde_json::to_value(response.into_inner()).map_err(|err| {
+                    Error::internal()
+                        .with_message(format!("Error serializing response: {err}"))
+                        .with_source(err)
+                })
+            })
+        });
+
+        self.routes.push(Route {
+            method,
+            template: PathTemplate::new(template),
+            handler,
+        });
+        self
+    }
+
+    /// Finds the route handler and path template variables for a request.
+    fn find(&self, method: &Method, path: &str) -> Option<(Handler, Vec<(String, String)>)> {
+        self.routes.iter().find_map(|route| {
+            if route.method != method {
+                return None;
+            }
+            route
+                .template
+                .matches(path)
+                .map(|variables| (route.handler.clone(), variables))
+        })
+    }
+}
+
+#[derive(Clone)]
+struct Route {
+    method: Method,
+    template: PathTemplate,
+    handler: Handler,
+}
+
+/// A path template such as `/v1/users/{user_id}`.
+#[derive(Clone, Debug)]
+struct PathTemplate {
+    template: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Debug)]
+enum Segment {
+    Literal(String),
+    Variable(String),
+}
+
+impl PathTemplate {
+    fn new(template: &str) -> Self {
+        let segments = template
+            .trim_start_matches('/')
+            .split('/')
+            .map(|segment| {
+                match segment
+                    .strip_prefix('{')
+                    .and_then(|segment| segment.strip_suffix('}'))
+                {
+                    Some(name) => Segment::Variable(name.to_owned()),
+                    None => Segment::Literal(segment.to_owned()),
+                }
+            })
+            .collect();
+
+        Self {
+            template: template.to_owned(),
+            segments,
+        }
+    }
+
+    /// Returns the decoded variables if the path matches the template.
+    fn matches(&self, path: &str) -> Option<Vec<(String, String)>> {
+        let parts: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+        if parts.len() != self.segments.len() {
+            return None;
+        }
+
+        let mut variables = vec![];
+        for (segment, part) in self.segments.iter().zip(parts) {
+            match segment {
+                Segment::Literal(literal) if literal == part => {}
+                Segment::Literal(_) => return None,
+                Segment::Variable(_) if part.is_empty() => return None,
+                Segment::Variable(name) => {
+                    let value = percent_decode_str(part).decode_utf8_lossy();
+                    variables.push((name.clone(), value.into_owned()));
+                }
+            }
+        }
+        Some(variables)
+    }
+}
+
+impl fmt::Display for PathTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+/// A request field from the JSON body, or a query parameter or path template
+/// variable that is parsed as the type of the field.
+enum Field {
+    Json(Value),
+    Param(String),
+}
+
+impl Field {
+    fn param(self) -> std::result::Result<String, Value> {
+        match self {
+            Field::Param(param) => Ok(param),
+            Field::Json(value) => Err(value),
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, serde_json::Error> for Field {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// Deserializes JSON fields as JSON and parameters as strings, or as the
+/// number or boolean parsed from them.
+macro_rules! deserialize_field {
+    ($($method:ident => $parse:ty, $visit:ident;)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> std::result::Result<V::Value, Self::Error> {
+                match self.param() {
+                    Ok(param) => match param.parse::<$parse>() {
+                        Ok(value) => visitor.$visit(value),
+                        Err(_) => visitor.visit_string(param),
+                    },
+                    Err(value) => value.$method(visitor),
+                }
+            }
+        )*
+    };
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                $($arg: $ty,)*
+                visitor: V,
+            ) -> std::result::Result<V::Value, Self::Error> {
+                match self.param() {
+                    Ok(param) => string_deserializer(param).$method($($arg,)* visitor),
+                    Err(value) => value.$method($($arg,)* visitor),
+                }
+            }
+        )*
+    };
+}
+
+fn string_deserializer(param: String) -> StringDeserializer<serde_json::Error> {
+    param.into_deserializer()
+}
+
+impl<'de> Deserializer<'de> for Field {
+    type Error = serde_json::Error;
+
+    deserialize_field! {
+        deserialize_bool => bool, visit_bool;
+        deserialize_i8 => i64, visit_i64;
+        deserialize_i16 => i64, visit_i64;
+        deserialize_i32 => i64, visit_i64;
+        deserialize_i64 => i64, visit_i64;
+        deserialize_u8 => u64, visit_u64;
+        deserialize_u16 => u64, visit_u64;
+        deserialize_u32 => u64, visit_u64;
+        deserialize_u64 => u64, visit_u64;
+        deserialize_f32 => f64, visit_f64;
+        deserialize_f64 => f64, visit_f64;
+    }
+
+    deserialize_field! {
+        deserialize_any();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match self {
+            Field::Param(_) => visitor.visit_some(self),
+            Field::Json(value) => value.deserialize_option(visitor),
+        }
+    }
+}
+
+/// Layer that serves Tonic service methods over REST.
+///
+/// See the [module documentation][self].
+#[derive(Clone, Debug)]
+pub struct RestTranscodingLayer {
+    routes: Arc<RestRoutes>,
+    max_body_size: usize,
+}
+
+impl RestTranscodingLayer {
+    /// Creates a new `RestTranscodingLayer`.
+    pub fn new(routes: RestRoutes) -> Self {
+        Self {
+            routes: Arc::new(routes),
+            max_body_size: DEFAULT_REST_TRANSCODING_MAX_BODY_BYTES,
+        }
+    }
+
+    /// Sets the maximum size of a REST request body.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+}
+
+impl<S> Layer<S> for RestTranscodingLayer {
+    type Service = RestTranscoding<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RestTranscoding {
+            inner,
+            routes: self.routes.clone(),
+            max_body_size: self.max_body_size,
+        }
+    }
+}
+
+/// Middleware that serves Tonic service methods over REST.
+///
+/// See [`RestTranscodingLayer`].
+#[derive(Clone, Debug)]
+pub struct RestTranscoding<S> {
+    inner: S,
+    routes: Arc<RestRoutes>,
+    max_body_size: usize,
+}
+
+impl<S, ResBody> Service<Request<Body>> for RestTranscoding<S>
+where
+    S: Service<Request<Body>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ResBody: HttpBody<Data = Bytes> + Send + 'static,
+    ResBody::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<std::result::Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let route = if is_grpc(&request) {
+            None
+        } else {
+            self.routes.find(request.method(), request.uri().path())
+        };
+
+        match route {
+            Some((handler, variables)) => {
+                let max_body_size = self.max_body_size;
+                Box::pin(
+                    async move { Ok(transcode(handler, variables, request, max_body_size).await) },
+                )
+            }
+            None => {
+                // The service that was polled ready must handle the request
+                let clone = self.inner.clone();
+                let mut inner = std::mem::replace(&mut self.inner, clone);
+
+                Box::pin(async move {
+                    let response = inner.call(request).await?;
+                    Ok(response
+                        .map(|body| body.map_err(|err| into_status(err.into())).boxed_unsync()))
+                })
+            }
+        }
+    }
+}
+
+fn into_status(err: Box<dyn std::error::Error + Send + Sync>) -> tonic::Status {
+    match err.downcast::<tonic::Status>() {
+        Ok(status) => *status,
+        Err(err) => tonic::Status::internal(err.to_string()),
+    }
+}
+
+fn is_grpc<B>(request: &Request<B>) -> bool {
+    request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map(|content_type| content_type.starts_with("application/grpc"))
+        .unwrap_or(false)
+}
+
+async fn transcode(
+    handler: Handler,
+    variables: Vec<(String, String)>,
+    request: Request<Body>,
+    max_body_size: usize,
+) -> Response<BoxBody> {
+    let (parts, body) = request.into_parts();
+    let body = match read_body(body, max_body_size).await {
+        Ok(body) => body,
+        Err(err) if err.is_resource_exhausted() => {
+            return error_response(StatusCode::PAYLOAD_TOO_LARGE, &err)
+        }
+        Err(err) => return error_response(err.http_status_code(), &err),
+    };
+
+    match call_handler(handler, variables, parts, &body).await {
+        Ok(value) => json_response(StatusCode::OK, &value),
+        Err(err) => error_response(err.http_status_code(), &err),
+    }
+}
+
+/// Reads a request body of at most `max_size` bytes.
+async fn read_body(mut body: Body, max_size: usize) -> Result<Vec<u8>> {
+    let too_large = || {
+        Error::resource_exhausted()
+            .with_message(format!("Request body is larger than {max_size} bytes"))
+    };
+    if body.size_hint().lower() > max_size as u64 {
+        return Err(too_large());
+    }
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|err| {
+            Error::invalid_argument()
+                .with_message("Error reading request body")
+                .with_source(err)
+        })?;
+        if bytes.len() + chunk.len() > max_size {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+async fn call_handler(
+    handler: Handler,
+    variables: Vec<(String, String)>,
+    parts: hyper::http::request::Parts,
+    body: &[u8],
+) -> Result<Value> {
+    let body = if body.is_empty() {
+        Map::new()
+    } else {
+        match serde_json::from_slice(body) {
+            Ok(Value::Object(fields)) => fields,
+            _ => {
+                return Err(
+                    Error::invalid_argument().with_message("Request body must be a JSON object")
+                );
+            }
+        }
+    };
+
+    let mut fields = BTreeMap::new();
+    for (name, value) in body {
+        fields.insert(name, Field::Json(value));
+    }
+    if let Some(query) = parts.uri.query() {
+        for (name, value) in form_urlencoded::parse(query.as_bytes()) {
+            fields.insert(name.into_owned(), Field::Param(value.into_owned()));
+        }
+    }
+    for (name, value) in variables {
+        fields.insert(name, Field::Param(value));
+    }
+
+    handler(parts.headers, fields.into_iter().collect()).await
+}
+
+fn error_response(status: StatusCode, err: &Error) -> Response<BoxBody> {
+    tracing::warn!("{err}");
+    json_response(
+        status,
+        &serde_json::json!({
+            "error": {
+                "message": err.to_string()
+            }
+        }),
+    )
+}
+
+fn json_response(status: StatusCode, value: &Value) -> Response<BoxBody> {
+    let body = Body::from(value.to_string())
+        .map_err(|err| tonic::Status::internal(err.to_string()))
+        .boxed_unsync();
+
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct ListItemsRequest {
+        user_id: String,
+        limit: u32,
+        verbose: Option<bool>,
+        cursor: Option<String>,
+    }
+
+    async fn list_items(
+        request: tonic::Request<ListItemsRequest>,
+    ) -> Result<tonic::Response<ListItemsRequest>, tonic::Status> {
+        Ok(tonic::Response::new(request.into_inner()))
+    }
+
+    fn routes() -> RestRoutes {
+        RestRoutes::new()
+            .route(Method::GET, "/v1/users/{user_id}/items", list_items)
+            .route(Method::POST, "/v1/users/{user_id}/items", list_items)
+    }
+
+    async fn send(layer: RestTranscodingLayer, request: Request<Body>) -> (StatusCode, Value) {
+        let inner = tower::service_fn(|_: Request<Body>| async {
+            Ok::<_, std::convert::Infallible>(Response::new(Body::empty()))
+        });
+
+        let response = layer.layer(inner).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn parses_params_as_field_types() {
+        let request = Request::get("/v1/users/42/items?limit=10&verbose=true&cursor=7")
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(RestTranscodingLayer::new(routes()), request).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "user_id": "42",
+                "limit": 10,
+                "verbose": true,
+                "cursor": "7",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_params() {
+        let request = Request::get("/v1/users/42/items?limit=ten")
+            .body(Body::empty())
+            .unwrap();
+        let (status, _) = send(RestTranscodingLayer::new(routes()), request).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn rejects_large_bodies() {
+        let layer = RestTranscodingLayer::new(routes()).with_max_body_size(16);
+
+        let request = Request::post("/v1/users/42/items")
+            .body(Body::from(r#"{"limit":10}"#))
+            .unwrap();
+        let (status, _) = send(layer.clone(), request).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let request = Request::post("/v1/users/42/items")
+            .body(Body::from(r#"{"limit":10,"verbose":true}"#))
+            .unwrap();
+        let (status, body) = send(layer, request).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            body["error"]["message"],
+            "Request body is larger than 16 bytes"
+        );
+    }
+}
@@ -1,12 +1,47 @@
 use crate::models::coin_type::CoinType;
 use crate::models::error::Error;
 
+#[cfg(feature = "ethereum")]
+pub use models::ethereum_message::hash_personal_message;
 pub use web3::types as web3_types;
 
 pub mod models;
 #[cfg(test)]
 pub mod tests;
 
+/// The version of this crate.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Returns the enabled features of this crate.
+pub fn features() -> Vec<&'static str> {
+    [
+        (
+            "access-list-provider",
+            cfg!(feature = "access-list-provider"),
+        ),
+        ("bitcoin", cfg!(feature = "bitcoin")),
+        ("cosmos", cfg!(feature = "cosmos")),
+        ("ens-resolver", cfg!(feature = "ens-resolver")),
+        ("ethereum", cfg!(feature = "ethereum")),
+        ("fee-estimator", cfg!(feature = "fee-estimator")),
+        ("price-provider", cfg!(feature = "price-provider")),
+        (
+            "reqwest-selector-resolver",
+            cfg!(feature = "reqwest-selector-resolver"),
+        ),
+        ("selector-resolver", cfg!(feature = "selector-resolver")),
+        ("simulator", cfg!(feature = "simulator")),
+        ("solana", cfg!(feature = "solana")),
+        ("test-vectors", cfg!(feature = "test-vectors")),
+        ("token-info-provider", cfg!(feature = "token-info-provider")),
+        ("web3-signing", cfg!(feature = "web3-signing")),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(name, _)| name)
+    .collect()
+}
+
 pub fn known_transaction_request_type_from_json(
     json: serde_json::Value,
     coin_type: CoinType,
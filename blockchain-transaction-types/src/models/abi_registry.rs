@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+use web3::ethabi::{Contract, Function, Token};
+use web3::types::{Address, TransactionRequest as Web3TransactionRequest, H256};
+
+use crate::models::error::Error;
+use crate::models::ethereum_block::classify_transactions;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::{CallParameter, TransactionInfo};
+
+/// Contract ABIs used to decode the calldata of Ethereum transactions.
+///
+/// ABIs registered for a contract address take precedence over functions
+/// registered by selector.
+///
+/// ```rust
+/// use blockchain_transaction_types::models::abi_registry::AbiRegistry;
+///
+/// let mut registry = AbiRegistry::new();
+/// registry
+///     .register_abi(br#"[{
+///         "type": "function",
+///         "name": "mint",
+///         "inputs": [{ "name": "amount", "type": "uint256" }],
+///         "outputs": [],
+///         "stateMutability": "nonpayable"
+///     }]"#)
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AbiRegistry {
+    contracts: HashMap<Address, HashMap<[u8; 4], Function>>,
+    selectors: HashMap<[u8; 4], Function>,
+}
+
+impl AbiRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the functions of an ABI JSON for calls to `address`.
+    pub fn register_contract(&mut self, address: Address, abi: &[u8]) -> Result<(), Error> {
+        self.contracts
+            .entry(address)
+            .or_default()
+            .extend(load_functions(abi)?);
+        Ok(())
+    }
+
+    /// Registers the functions of an ABI JSON for calls to any address.
+    pub fn register_abi(&mut self, abi: &[u8]) -> Result<(), Error> {
+        self.selectors.extend(load_functions(abi)?);
+        Ok(())
+    }
+
+    /// Gets the function called by `data` on `to`.
+    pub fn function(&self, to: Option<&Address>, data: &[u8]) -> Option<&Function> {
+        let selector: [u8; 4] = data.get(..4)?.try_into().ok()?;
+        to.and_then(|to| self.contracts.get(to))
+            .and_then(|functions| functions.get(&selector))
+            .or_else(|| self.selectors.get(&selector))
+    }
+
+    /// Decodes the calldata of a contract call.
+    ///
+    /// Returns `None` if the function is unknown or the parameters don't
+    /// match its ABI.
+    pub fn decode_call(&self, to: Option<&Address>, data: &[u8]) -> Option<TransactionInfo> {
+        let function = self.function(to, data)?;
+        let tokens = function.decode_input(&data[4..]).ok()?;
+
+        let parameters = function
+            .inputs
+            .iter()
+            .zip(tokens)
+            .map(|(input, token)| CallParameter {
+                name: input.name.clone(),
+                kind: input.kind.to_string(),
+                value: token_to_json(token),
+            })
+            .collect();
+
+        Some(TransactionInfo::ContractCall {
+            function: function.signature(),
+            parameters,
+        })
+    }
+
+    /// Identifies a transaction request, decoding calls that aren't known
+    /// token transfers or approvals with the registered ABIs.
+    pub fn transaction_info(&self, request: &Web3TransactionRequest) -> TransactionInfo {
+        match request.transaction_info() {
+            TransactionInfo::Unknown { value, function } => request
+                .data
+                .as_ref()
+                .and_then(|data| self.decode_call(request.to.as_ref(), &data.0))
+                .unwrap_or(TransactionInfo::Unknown { value, function }),
+            info => info,
+        }
+    }
+
+    /// Classifies every transaction of a block with full transaction objects,
+    /// decoding calls that aren't known token transfers or approvals with the
+    /// registered ABIs.
+    pub fn classify_block(&self, block: Value) -> Result<Vec<(H256, TransactionInfo)>, Error> {
+        classify_transactions(block, Some(self))
+    }
+}
+
+/// Loads the functions of an ABI JSON by selector.
+fn load_functions(abi: &[u8]) -> Result<Vec<([u8; 4], Function)>, Error> {
+    let contract = Contract::load(abi)?;
+    Ok(contract
+        .functions()
+        .map(|function| (function.short_signature(), function.clone()))
+        .collect())
+}
+
+/// Converts a decoded ABI value to JSON.
+///
+/// Numbers and bytes are hex encoded, and arrays and tuples are JSON arrays.
+fn token_to_json(token: Token) -> Value {
+    match token {
+        Token::Address(address) => Value::String(format!("{:#x}", address)),
+        Token::Uint(value) | Token::Int(value) => Value::String(format!("{:#x}", value)),
+        Token::Bool(value) => Value::Bool(value),
+        Token::String(value) => Value::String(value),
+        Token::Bytes(bytes) | Token::FixedBytes(bytes) => {
+            Value::String(format!("0x{}", hex_encode(&bytes)))
+        }
+        Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+            Value::Array(tokens.into_iter().map(token_to_json).collect())
+        }
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -1,6 +1,42 @@
+use std::fmt;
+
 use crate::models::error::Error;
 
 pub trait Account: Sized {
     fn from_public_key(public_key: &[u8]) -> Result<Self, Error>;
     fn address(&self) -> String;
 }
+
+/// An account of any supported chain, such as the sender of a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AccountId {
+    /// The output script spent by a Bitcoin input.
+    #[cfg(feature = "bitcoin")]
+    Bitcoin(Vec<u8>),
+    /// A bech32 encoded Cosmos SDK account address.
+    #[cfg(feature = "cosmos")]
+    Cosmos(String),
+    #[cfg(feature = "ethereum")]
+    Ethereum(web3::types::Address),
+    #[cfg(feature = "solana")]
+    Solana(solana_sdk::pubkey::Pubkey),
+}
+
+impl fmt::Display for AccountId {
+    /// Formats Bitcoin scripts as hex, Cosmos addresses as bech32, Ethereum addresses as `0x` prefixed
+    /// hex and Solana public keys as base58.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            #[cfg(feature = "bitcoin")]
+            AccountId::Bitcoin(ref script) => {
+                script.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+            }
+            #[cfg(feature = "cosmos")]
+            AccountId::Cosmos(ref address) => write!(f, "{}", address),
+            #[cfg(feature = "ethereum")]
+            AccountId::Ethereum(ref address) => write!(f, "{:#x}", address),
+            #[cfg(feature = "solana")]
+            AccountId::Solana(ref pubkey) => write!(f, "{}", pubkey),
+        }
+    }
+}
@@ -0,0 +1,544 @@
+use std::collections::HashSet;
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::models::error::Error;
+#[cfg(feature = "signing")]
+use crate::models::transaction::SignableTransactionRequest;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::{TransactionInfo, TransactionOutput};
+
+/// The magic bytes that start a serialized PSBT.
+const PSBT_MAGIC: &[u8] = b"psbt\xff";
+
+const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+const PSBT_IN_NON_WITNESS_UTXO: u8 = 0x00;
+const PSBT_IN_WITNESS_UTXO: u8 = 0x01;
+const PSBT_IN_SIGHASH_TYPE: u8 = 0x03;
+const PSBT_IN_REDEEM_SCRIPT: u8 = 0x04;
+const PSBT_IN_WITNESS_SCRIPT: u8 = 0x05;
+
+/// The only supported sighash type, which signs all inputs and outputs.
+pub const SIGHASH_ALL: u32 = 0x01;
+
+/// A reference to an output of a previous transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutPoint {
+    /// The transaction id, in internal byte order.
+    pub txid: [u8; 32],
+    pub vout: u32,
+}
+
+/// A transaction input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxIn {
+    pub previous_output: OutPoint,
+    pub script_sig: Vec<u8>,
+    pub sequence: u32,
+}
+
+/// A transaction output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxOut {
+    /// The amount in satoshis.
+    pub value: u64,
+    pub script_pubkey: Vec<u8>,
+}
+
+/// A Bitcoin transaction, without witnesses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitcoinTransaction {
+    pub version: i32,
+    pub inputs: Vec<TxIn>,
+    pub outputs: Vec<TxOut>,
+    pub lock_time: u32,
+}
+
+/// The fields of a PSBT input needed to compute its sighash.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PsbtInput {
+    /// The full transaction spent by the input.
+    pub non_witness_utxo: Option<BitcoinTransaction>,
+    /// The output spent by the input.
+    pub witness_utxo: Option<TxOut>,
+    pub sighash_type: Option<u32>,
+    pub redeem_script: Option<Vec<u8>>,
+    pub witness_script: Option<Vec<u8>>,
+}
+
+/// A [BIP-174][bip-174] partially signed Bitcoin transaction.
+///
+/// Unknown and output fields are ignored.
+///
+/// [bip-174]: https://github.com/bitcoin/bips/blob/master/bip-0174.mediawiki
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Psbt {
+    pub unsigned_tx: BitcoinTransaction,
+    pub inputs: Vec<PsbtInput>,
+}
+
+impl BitcoinTransaction {
+    /// Serializes the transaction without witnesses.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = self.version.to_le_bytes().to_vec();
+        write_compact_size(&mut bytes, self.inputs.len() as u64);
+        for input in self.inputs.iter() {
+            write_out_point(&mut bytes, &input.previous_output);
+            write_var_bytes(&mut bytes, &input.script_sig);
+            bytes.extend_from_slice(&input.sequence.to_le_bytes());
+        }
+        write_compact_size(&mut bytes, self.outputs.len() as u64);
+        for output in self.outputs.iter() {
+            write_tx_out(&mut bytes, output);
+        }
+        bytes.extend_from_slice(&self.lock_time.to_le_bytes());
+        bytes
+    }
+
+    /// Computes the transaction id, in internal byte order.
+    pub fn txid(&self) -> [u8; 32] {
+        sha256d(&self.serialize())
+    }
+
+    fn read(reader: &mut Reader, allow_witness: bool) -> Result<Self, Error> {
+        let version = reader.read_u32()? as i32;
+
+        let segwit = allow_witness && reader.peek(2) == Some(&[0x00, 0x01]);
+        if segwit {
+            reader.read(2)?;
+        }
+
+        let inputs = (0..reader.read_compact_size()?)
+            .map(|_| {
+                Ok(TxIn {
+                    previous_output: OutPoint {
+                        txid: reader.read_array()?,
+                        vout: reader.read_u32()?,
+                    },
+                    script_sig: reader.read_var_bytes()?.to_vec(),
+                    sequence: reader.read_u32()?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let outputs = (0..reader.read_compact_size()?)
+            .map(|_| TxOut::read(reader))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        if segwit {
+            for _ in inputs.iter() {
+                for _ in 0..reader.read_compact_size()? {
+                    reader.read_var_bytes()?;
+                }
+            }
+        }
+
+        Ok(Self {
+            version,
+            inputs,
+            outputs,
+            lock_time: reader.read_u32()?,
+        })
+    }
+}
+
+impl TxOut {
+    fn read(reader: &mut Reader) -> Result<Self, Error> {
+        Ok(Self {
+            value: reader.read_u64()?,
+            script_pubkey: reader.read_var_bytes()?.to_vec(),
+        })
+    }
+}
+
+impl Psbt {
+    /// Parses a binary PSBT.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader { bytes };
+        if reader.read(PSBT_MAGIC.len())? != PSBT_MAGIC {
+            return Err(Error::InvalidData);
+        }
+
+        let mut unsigned_tx = None;
+        for (key, value) in read_map(&mut reader)? {
+            if key == [PSBT_GLOBAL_UNSIGNED_TX] {
+                unsigned_tx = Some(parse_all(value, |reader| {
+                    BitcoinTransaction::read(reader, false)
+                })?);
+            }
+        }
+        let unsigned_tx: BitcoinTransaction = unsigned_tx.ok_or(Error::InvalidData)?;
+        if unsigned_tx
+            .inputs
+            .iter()
+            .any(|input| !input.script_sig.is_empty())
+        {
+            return Err(Error::InvalidData);
+        }
+
+        let inputs = unsigned_tx
+            .inputs
+            .iter()
+            .map(|_| PsbtInput::read(&mut reader))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        for _ in unsigned_tx.outputs.iter() {
+            read_map(&mut reader)?;
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(Error::InvalidData);
+        }
+
+        Ok(Self {
+            unsigned_tx,
+            inputs,
+        })
+    }
+
+    /// Parses a base64 encoded PSBT.
+    pub fn from_base64(psbt: &str) -> Result<Self, Error> {
+        let bytes = base64::decode(psbt.trim()).map_err(|_| Error::InvalidData)?;
+        Self::deserialize(&bytes)
+    }
+
+    /// Gets the output spent by an input.
+    ///
+    /// The `non_witness_utxo` is preferred, and must match the outpoint of
+    /// the input.
+    pub fn spent_output(&self, index: usize) -> Result<TxOut, Error> {
+        let input = self.inputs.get(index).ok_or(Error::InvalidData)?;
+        let outpoint = &self.unsigned_tx.inputs[index].previous_output;
+
+        if let Some(transaction) = &input.non_witness_utxo {
+            if transaction.txid() != outpoint.txid {
+                return Err(Error::InvalidData);
+            }
+            return transaction
+                .outputs
+                .get(outpoint.vout as usize)
+                .cloned()
+                .ok_or(Error::InvalidData);
+        }
+
+        input.witness_utxo.clone().ok_or(Error::InvalidData)
+    }
+
+    /// Computes the fee in satoshis, if the outputs spent by all inputs are
+    /// known.
+    pub fn fee(&self) -> Option<u64> {
+        let inputs = (0..self.inputs.len()).try_fold(0u64, |total, index| {
+            total.checked_add(self.spent_output(index).ok()?.value)
+        })?;
+        let outputs = self
+            .unsigned_tx
+            .outputs
+            .iter()
+            .try_fold(0u64, |total, output| total.checked_add(output.value))?;
+        inputs.checked_sub(outputs)
+    }
+
+    /// Computes the sighash of an input.
+    ///
+    /// Legacy, P2SH, P2WPKH and P2WSH inputs are supported with
+    /// [`SIGHASH_ALL`]. Segwit inputs, including P2SH wrapped ones, use the
+    /// [BIP-143][bip-143] algorithm. Taproot inputs are not supported.
+    ///
+    /// [bip-143]: https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki
+    pub fn sighash(&self, index: usize) -> Result<[u8; 32], Error> {
+        let input = self.inputs.get(index).ok_or(Error::InvalidData)?;
+        let sighash_type = input.sighash_type.unwrap_or(SIGHASH_ALL);
+        if sighash_type != SIGHASH_ALL {
+            return Err(Error::InvalidData);
+        }
+
+        let spent_output = self.spent_output(index)?;
+        let script = if is_p2sh(&spent_output.script_pubkey) {
+            input.redeem_script.as_deref().ok_or(Error::InvalidData)?
+        } else {
+            &spent_output.script_pubkey
+        };
+
+        if is_p2wpkh(script) {
+            let mut script_code = vec![0x76, 0xa9, 0x14];
+            script_code.extend_from_slice(&script[2..]);
+            script_code.extend_from_slice(&[0x88, 0xac]);
+            Ok(self.segwit_v0_sighash(index, &script_code, spent_output.value, sighash_type))
+        } else if is_p2wsh(script) {
+            let script_code = input.witness_script.as_deref().ok_or(Error::InvalidData)?;
+            Ok(self.segwit_v0_sighash(index, script_code, spent_output.value, sighash_type))
+        } else if is_witness_program(script) {
+            Err(Error::InvalidData)
+        } else {
+            Ok(self.legacy_sighash(index, script, sighash_type))
+        }
+    }
+
+    /// Computes the sighashes of all inputs, in order.
+    pub fn sighashes(&self) -> Result<Vec<[u8; 32]>, Error> {
+        (0..self.inputs.len())
+            .map(|index| self.sighash(index))
+            .collect()
+    }
+
+    fn legacy_sighash(&self, index: usize, script_code: &[u8], sighash_type: u32) -> [u8; 32] {
+        let mut transaction = self.unsigned_tx.clone();
+        for (i, input) in transaction.inputs.iter_mut().enumerate() {
+            input.script_sig = if i == index {
+                script_code.to_vec()
+            } else {
+                vec![]
+            };
+        }
+
+        let mut bytes = transaction.serialize();
+        bytes.extend_from_slice(&sighash_type.to_le_bytes());
+        sha256d(&bytes)
+    }
+
+    fn segwit_v0_sighash(
+        &self,
+        index: usize,
+        script_code: &[u8],
+        value: u64,
+        sighash_type: u32,
+    ) -> [u8; 32] {
+        let transaction = &self.unsigned_tx;
+        let input = &transaction.inputs[index];
+
+        let mut prevouts = vec![];
+        let mut sequences = vec![];
+        for input in transaction.inputs.iter() {
+            write_out_point(&mut prevouts, &input.previous_output);
+            sequences.extend_from_slice(&input.sequence.to_le_bytes());
+        }
+        let mut outputs = vec![];
+        for output in transaction.outputs.iter() {
+            write_tx_out(&mut outputs, output);
+        }
+
+        let mut bytes = transaction.version.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&sha256d(&prevouts));
+        bytes.extend_from_slice(&sha256d(&sequences));
+        write_out_point(&mut bytes, &input.previous_output);
+        write_var_bytes(&mut bytes, script_code);
+        bytes.extend_from_slice(&value.to_le_bytes());
+        bytes.extend_from_slice(&input.sequence.to_le_bytes());
+        bytes.extend_from_slice(&sha256d(&outputs));
+        bytes.extend_from_slice(&transaction.lock_time.to_le_bytes());
+        bytes.extend_from_slice(&sighash_type.to_le_bytes());
+        sha256d(&bytes)
+    }
+}
+
+impl PsbtInput {
+    fn read(reader: &mut Reader) -> Result<Self, Error> {
+        let mut input = Self::default();
+        for (key, value) in read_map(reader)? {
+            match key {
+                [PSBT_IN_NON_WITNESS_UTXO] => {
+                    input.non_witness_utxo = Some(parse_all(value, |reader| {
+                        BitcoinTransaction::read(reader, true)
+                    })?);
+                }
+                [PSBT_IN_WITNESS_UTXO] => {
+                    input.witness_utxo = Some(parse_all(value, TxOut::read)?);
+                }
+                [PSBT_IN_SIGHASH_TYPE] => {
+                    input.sighash_type = Some(parse_all(value, Reader::read_u32)?);
+                }
+                [PSBT_IN_REDEEM_SCRIPT] => input.redeem_script = Some(value.to_vec()),
+                [PSBT_IN_WITNESS_SCRIPT] => input.witness_script = Some(value.to_vec()),
+                _ => {}
+            }
+        }
+        Ok(input)
+    }
+}
+
+impl TransactionRequest for Psbt {
+    /// Parses a base64 encoded PSBT string.
+    fn from_json(json: Value) -> Result<Self, Error> {
+        let psbt = json.as_str().ok_or(Error::InvalidData)?;
+        Self::from_base64(psbt)
+    }
+
+    /// Parses a binary or base64 encoded PSBT.
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.starts_with(PSBT_MAGIC) {
+            return Self::deserialize(bytes);
+        }
+        let psbt = std::str::from_utf8(bytes).map_err(|_| Error::InvalidData)?;
+        Self::from_base64(psbt)
+    }
+
+    /// Summarizes the outputs and fee of the transaction.
+    fn transaction_info(&self) -> TransactionInfo {
+        TransactionInfo::UtxoTransfer {
+            outputs: self
+                .unsigned_tx
+                .outputs
+                .iter()
+                .map(|output| TransactionOutput {
+                    script_pubkey: to_hex(&output.script_pubkey),
+                    amount: format!("{:#x}", output.value),
+                })
+                .collect(),
+            fee: self.fee().map(|fee| format!("{:#x}", fee)),
+        }
+    }
+}
+
+#[cfg(feature = "signing")]
+impl SignableTransactionRequest for Psbt {
+    /// Returns the sighash of the only input.
+    ///
+    /// Fails for transactions with several inputs, which must be signed
+    /// input by input with [`Psbt::sighashes`].
+    fn message_hash(&self, _chain_id: u64) -> Result<Vec<u8>, Error> {
+        match self.sighashes()?.as_slice() {
+            [sighash] => Ok(sighash.to_vec()),
+            _ => Err(Error::InvalidData),
+        }
+    }
+}
+
+/// Reads bitcoin encoded values from a byte slice.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn peek(&self, len: usize) -> Option<&'a [u8]> {
+        self.bytes.get(..len)
+    }
+
+    fn read(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let bytes = self.peek(len).ok_or(Error::InvalidData)?;
+        self.bytes = &self.bytes[len..];
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.read(N)?.try_into().unwrap())
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_compact_size(&mut self) -> Result<u64, Error> {
+        match self.read_array::<1>()?[0] {
+            0xfd => Ok(u16::from_le_bytes(self.read_array()?).into()),
+            0xfe => Ok(self.read_u32()?.into()),
+            0xff => self.read_u64(),
+            len => Ok(len.into()),
+        }
+    }
+
+    fn read_var_bytes(&mut self) -> Result<&'a [u8], Error> {
+        let len = self.read_compact_size()?;
+        self.read(usize::try_from(len).map_err(|_| Error::InvalidData)?)
+    }
+}
+
+/// Parses a value that must be fully consumed by `read`.
+fn parse_all<'a, T>(
+    bytes: &'a [u8],
+    read: impl FnOnce(&mut Reader<'a>) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let mut reader = Reader { bytes };
+    let value = read(&mut reader)?;
+    if !reader.bytes.is_empty() {
+        return Err(Error::InvalidData);
+    }
+    Ok(value)
+}
+
+/// The key-value pairs of a PSBT map.
+type Map<'a> = Vec<(&'a [u8], &'a [u8])>;
+
+/// Reads the key-value pairs of a PSBT map, rejecting duplicate keys.
+fn read_map<'a>(reader: &mut Reader<'a>) -> Result<Map<'a>, Error> {
+    let mut keys = HashSet::new();
+    let mut map = vec![];
+    loop {
+        let key = reader.read_var_bytes()?;
+        if key.is_empty() {
+            return Ok(map);
+        }
+        if !keys.insert(key) {
+            return Err(Error::InvalidData);
+        }
+        map.push((key, reader.read_var_bytes()?));
+    }
+}
+
+fn write_compact_size(bytes: &mut Vec<u8>, len: u64) {
+    match len {
+        0..=0xfc => bytes.push(len as u8),
+        0xfd..=0xffff => {
+            bytes.push(0xfd);
+            bytes.extend_from_slice(&(len as u16).to_le_bytes());
+        }
+        0x10000..=0xffff_ffff => {
+            bytes.push(0xfe);
+            bytes.extend_from_slice(&(len as u32).to_le_bytes());
+        }
+        _ => {
+            bytes.push(0xff);
+            bytes.extend_from_slice(&len.to_le_bytes());
+        }
+    }
+}
+
+fn write_var_bytes(bytes: &mut Vec<u8>, value: &[u8]) {
+    write_compact_size(bytes, value.len() as u64);
+    bytes.extend_from_slice(value);
+}
+
+fn write_out_point(bytes: &mut Vec<u8>, out_point: &OutPoint) {
+    bytes.extend_from_slice(&out_point.txid);
+    bytes.extend_from_slice(&out_point.vout.to_le_bytes());
+}
+
+fn write_tx_out(bytes: &mut Vec<u8>, output: &TxOut) {
+    bytes.extend_from_slice(&output.value.to_le_bytes());
+    write_var_bytes(bytes, &output.script_pubkey);
+}
+
+/// Computes the double SHA-256 hash of `bytes`.
+fn sha256d(bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(bytes)).into()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// `OP_HASH160 <20 bytes> OP_EQUAL`
+fn is_p2sh(script: &[u8]) -> bool {
+    script.len() == 23 && script[0] == 0xa9 && script[1] == 0x14 && script[22] == 0x87
+}
+
+/// `OP_0 <20 bytes>`
+fn is_p2wpkh(script: &[u8]) -> bool {
+    script.len() == 22 && script[0] == 0x00 && script[1] == 0x14
+}
+
+/// `OP_0 <32 bytes>`
+fn is_p2wsh(script: &[u8]) -> bool {
+    script.len() == 34 && script[0] == 0x00 && script[1] == 0x20
+}
+
+/// `OP_0..OP_16 <2 to 40 bytes>`
+fn is_witness_program(script: &[u8]) -> bool {
+    matches!(script.first(), Some(0x00) | Some(0x51..=0x60))
+        && (4..=42).contains(&script.len())
+        && usize::from(script[1]) + 2 == script.len()
+}
@@ -0,0 +1,118 @@
+use crate::models::coin_type::CoinType;
+use crate::models::error::Error;
+
+/// A network identified by its EIP-155 chain ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChainInfo {
+    pub chain_id: u64,
+    /// The coin type of the accounts and transactions of the chain.
+    pub coin_type: CoinType,
+    pub name: &'static str,
+    pub native_symbol: &'static str,
+    pub native_decimals: u8,
+    /// The block explorer, without a trailing slash.
+    pub explorer_url: &'static str,
+    pub is_testnet: bool,
+}
+
+const fn evm_chain(
+    chain_id: u64,
+    name: &'static str,
+    native_symbol: &'static str,
+    explorer_url: &'static str,
+    is_testnet: bool,
+) -> ChainInfo {
+    ChainInfo {
+        chain_id,
+        coin_type: CoinType::Ethereum,
+        name,
+        native_symbol,
+        native_decimals: 18,
+        explorer_url,
+        is_testnet,
+    }
+}
+
+/// The built-in chains.
+pub const CHAINS: &[ChainInfo] = &[
+    evm_chain(1, "Ethereum", "ETH", "https://etherscan.io", false),
+    evm_chain(5, "Goerli", "ETH", "https://goerli.etherscan.io", true),
+    evm_chain(
+        10,
+        "OP Mainnet",
+        "ETH",
+        "https://optimistic.etherscan.io",
+        false,
+    ),
+    evm_chain(56, "BNB Smart Chain", "BNB", "https://bscscan.com", false),
+    evm_chain(100, "Gnosis", "xDAI", "https://gnosisscan.io", false),
+    evm_chain(137, "Polygon", "MATIC", "https://polygonscan.com", false),
+    evm_chain(8453, "Base", "ETH", "https://basescan.org", false),
+    evm_chain(42161, "Arbitrum One", "ETH", "https://arbiscan.io", false),
+    evm_chain(
+        43114,
+        "Avalanche C-Chain",
+        "AVAX",
+        "https://snowtrace.io",
+        false,
+    ),
+    evm_chain(
+        80001,
+        "Polygon Mumbai",
+        "MATIC",
+        "https://mumbai.polygonscan.com",
+        true,
+    ),
+    evm_chain(
+        80002,
+        "Polygon Amoy",
+        "POL",
+        "https://amoy.polygonscan.com",
+        true,
+    ),
+    evm_chain(
+        84532,
+        "Base Sepolia",
+        "ETH",
+        "https://sepolia.basescan.org",
+        true,
+    ),
+    evm_chain(
+        421614,
+        "Arbitrum Sepolia",
+        "ETH",
+        "https://sepolia.arbiscan.io",
+        true,
+    ),
+    evm_chain(
+        11155111,
+        "Sepolia",
+        "ETH",
+        "https://sepolia.etherscan.io",
+        true,
+    ),
+    evm_chain(
+        11155420,
+        "OP Sepolia",
+        "ETH",
+        "https://sepolia-optimism.etherscan.io",
+        true,
+    ),
+];
+
+/// Gets a built-in chain.
+pub fn chain_info(chain_id: u64) -> Option<&'static ChainInfo> {
+    CHAINS.iter().find(|chain| chain.chain_id == chain_id)
+}
+
+/// Checks that `chain_id` is a built-in chain of `coin_type`.
+///
+/// Returns [`Error::UnknownChain`] for chains missing from the registry, so
+/// callers accepting custom networks can allow them.
+pub fn validate_chain(coin_type: CoinType, chain_id: u64) -> Result<&'static ChainInfo, Error> {
+    let chain = chain_info(chain_id).ok_or(Error::UnknownChain(chain_id))?;
+    if chain.coin_type != coin_type {
+        return Err(Error::InvalidCoinType);
+    }
+    Ok(chain)
+}
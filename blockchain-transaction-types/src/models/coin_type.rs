@@ -1,6 +1,195 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::models::error::Error;
+
+/// The hardened bit of a BIP-32 derivation path index.
+const HARDENED: u32 = 0x8000_0000;
+
+/// A [SLIP-44] coin type, as used in BIP-44 derivation paths.
+///
+/// Registered coin types without a variant are kept as [`CoinType::Other`].
+/// Coin types are serialized as their number, with `-1` for
+/// [`CoinType::Unknown`], and deserialize from numbers or names.
+///
+/// [SLIP-44]: https://github.com/satoshilabs/slips/blob/master/slip-0044.md
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum CoinType {
-    Ethereum = 60,
-    Solana = 501,
-    Unknown = 0,
+    Bitcoin,
+    Testnet,
+    Litecoin,
+    Dogecoin,
+    Dash,
+    Ethereum,
+    EthereumClassic,
+    Cosmos,
+    Zcash,
+    Monero,
+    Ripple,
+    BitcoinCash,
+    Stellar,
+    Tron,
+    Algorand,
+    Polkadot,
+    Near,
+    Kusama,
+    Filecoin,
+    Solana,
+    Aptos,
+    BinanceChain,
+    Sui,
+    Polygon,
+    Tezos,
+    Cardano,
+    Hedera,
+    Avalanche,
+    Unknown,
+    Other(u32),
+}
+
+/// The coin types with a variant, by number, with their symbol and name.
+const COIN_TYPES: &[(u32, CoinType, &str, &str)] = &[
+    (0, CoinType::Bitcoin, "BTC", "Bitcoin"),
+    (1, CoinType::Testnet, "TEST", "Testnet"),
+    (2, CoinType::Litecoin, "LTC", "Litecoin"),
+    (3, CoinType::Dogecoin, "DOGE", "Dogecoin"),
+    (5, CoinType::Dash, "DASH", "Dash"),
+    (60, CoinType::Ethereum, "ETH", "Ethereum"),
+    (61, CoinType::EthereumClassic, "ETC", "Ethereum Classic"),
+    (118, CoinType::Cosmos, "ATOM", "Cosmos"),
+    (128, CoinType::Monero, "XMR", "Monero"),
+    (133, CoinType::Zcash, "ZEC", "Zcash"),
+    (144, CoinType::Ripple, "XRP", "Ripple"),
+    (145, CoinType::BitcoinCash, "BCH", "Bitcoin Cash"),
+    (148, CoinType::Stellar, "XLM", "Stellar"),
+    (195, CoinType::Tron, "TRX", "Tron"),
+    (283, CoinType::Algorand, "ALGO", "Algorand"),
+    (354, CoinType::Polkadot, "DOT", "Polkadot"),
+    (397, CoinType::Near, "NEAR", "NEAR Protocol"),
+    (434, CoinType::Kusama, "KSM", "Kusama"),
+    (461, CoinType::Filecoin, "FIL", "Filecoin"),
+    (501, CoinType::Solana, "SOL", "Solana"),
+    (637, CoinType::Aptos, "APT", "Aptos"),
+    (714, CoinType::BinanceChain, "BNB", "Binance Chain"),
+    (784, CoinType::Sui, "SUI", "Sui"),
+    (966, CoinType::Polygon, "MATIC", "Polygon"),
+    (1729, CoinType::Tezos, "XTZ", "Tezos"),
+    (1815, CoinType::Cardano, "ADA", "Cardano"),
+    (3030, CoinType::Hedera, "HBAR", "Hedera HBAR"),
+    (9000, CoinType::Avalanche, "AVAX", "Avalanche"),
+];
+
+impl CoinType {
+    /// Gets the SLIP-44 number, or `None` for [`CoinType::Unknown`].
+    pub fn slip44(&self) -> Option<u32> {
+        match self {
+            CoinType::Unknown => None,
+            CoinType::Other(coin_type) => Some(*coin_type),
+            coin_type => COIN_TYPES
+                .iter()
+                .find(|(_, known, _, _)| known == coin_type)
+                .map(|(number, _, _, _)| *number),
+        }
+    }
+
+    /// Gets the registered symbol, such as `ETH`.
+    pub fn symbol(&self) -> Option<&'static str> {
+        self.entry().map(|(_, _, symbol, _)| *symbol)
+    }
+
+    /// Gets the registered name, such as `Ethereum`.
+    pub fn name(&self) -> Option<&'static str> {
+        self.entry().map(|(_, _, _, name)| *name)
+    }
+
+    fn entry(&self) -> Option<&'static (u32, CoinType, &'static str, &'static str)> {
+        COIN_TYPES.iter().find(|(_, known, _, _)| known == self)
+    }
+}
+
+impl From<u32> for CoinType {
+    /// Gets the coin type of a SLIP-44 number, which may be a hardened
+    /// derivation path index.
+    fn from(coin_type: u32) -> Self {
+        let coin_type = coin_type & !HARDENED;
+        COIN_TYPES
+            .iter()
+            .find(|(number, _, _, _)| *number == coin_type)
+            .map_or(CoinType::Other(coin_type), |(_, known, _, _)| *known)
+    }
+}
+
+impl FromStr for CoinType {
+    type Err = Error;
+
+    /// Parses a SLIP-44 number, such as `60` or the path component `60'`, or
+    /// a registered symbol or name, ignoring case.
+    fn from_str(coin_type: &str) -> Result<Self, Error> {
+        let number = coin_type
+            .strip_suffix('\'')
+            .or_else(|| coin_type.strip_suffix('h'))
+            .unwrap_or(coin_type);
+        if let Ok(number) = number.parse::<u32>() {
+            if number < HARDENED {
+                return Ok(number.into());
+            }
+        }
+
+        COIN_TYPES
+            .iter()
+            .find(|(_, _, symbol, name)| {
+                symbol.eq_ignore_ascii_case(coin_type) || name.eq_ignore_ascii_case(coin_type)
+            })
+            .map(|(_, known, _, _)| *known)
+            .ok_or(Error::InvalidCoinType)
+    }
+}
+
+impl Serialize for CoinType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.slip44() {
+            Some(coin_type) => serializer.serialize_u32(coin_type),
+            None => serializer.serialize_i32(-1),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CoinType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(CoinTypeVisitor)
+    }
+}
+
+struct CoinTypeVisitor;
+
+impl<'de> Visitor<'de> for CoinTypeVisitor {
+    type Value = CoinType;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a SLIP-44 coin type number or name")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<CoinType, E> {
+        u32::try_from(value)
+            .map(CoinType::from)
+            .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<CoinType, E> {
+        match value {
+            -1 => Ok(CoinType::Unknown),
+            value if value >= 0 => self.visit_u64(value as u64),
+            value => Err(E::invalid_value(de::Unexpected::Signed(value), &self)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<CoinType, E> {
+        value
+            .parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
 }
@@ -0,0 +1,422 @@
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::models::error::Error;
+#[cfg(feature = "signing")]
+use crate::models::transaction::SignableTransactionRequest;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::TransactionInfo;
+
+/// The type URL of a bank `MsgSend` message.
+pub const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+
+const WIRE_TYPE_VARINT: u8 = 0;
+const WIRE_TYPE_FIXED64: u8 = 1;
+const WIRE_TYPE_LENGTH_DELIMITED: u8 = 2;
+const WIRE_TYPE_FIXED32: u8 = 5;
+
+/// An amount of a denomination, such as `1000uatom`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Coin {
+    pub denom: String,
+    /// The amount as a decimal integer.
+    pub amount: String,
+}
+
+/// A protobuf message of any type, identified by its type URL.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Any {
+    pub type_url: String,
+    pub value: Vec<u8>,
+}
+
+/// The messages of a transaction and their metadata.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TxBody {
+    pub messages: Vec<Any>,
+    pub memo: String,
+    pub timeout_height: u64,
+}
+
+/// The public key and sequence of a signer of a transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SignerInfo {
+    pub public_key: Option<Any>,
+    pub sequence: u64,
+}
+
+/// The fee of a transaction and the accounts paying it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fee {
+    pub amount: Vec<Coin>,
+    pub gas_limit: u64,
+    /// The account paying the fee, when it isn't the first signer.
+    pub payer: String,
+    /// The account granting the fee allowance, if any.
+    pub granter: String,
+}
+
+/// The signers and fee of a transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuthInfo {
+    pub signer_infos: Vec<SignerInfo>,
+    pub fee: Option<Fee>,
+}
+
+/// A bank `MsgSend` message.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MsgSend {
+    pub from_address: String,
+    pub to_address: String,
+    pub amount: Vec<Coin>,
+}
+
+/// The document signed with `SIGN_MODE_DIRECT`.
+///
+/// The body and auth info are kept as their original bytes, since the
+/// signature covers the exact encoding chosen by the sender.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SignDoc {
+    pub body_bytes: Vec<u8>,
+    pub auth_info_bytes: Vec<u8>,
+    pub chain_id: String,
+    pub account_number: u64,
+}
+
+/// A [Cosmos SDK] transaction request, signed with `SIGN_MODE_DIRECT`.
+///
+/// Requests are sent as JSON with base64 encoded `bodyBytes` and
+/// `authInfoBytes`, as passed to Keplr's `signDirect`, or as a raw protobuf
+/// encoded `SignDoc`.
+///
+/// [Cosmos SDK]: https://docs.cosmos.network/main/learn/advanced/transactions
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CosmosTransactionRequest {
+    pub sign_doc: SignDoc,
+    pub body: TxBody,
+    pub auth_info: AuthInfo,
+}
+
+impl SignDoc {
+    /// Decodes a protobuf encoded `SignDoc`.
+    pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let mut sign_doc = SignDoc::default();
+        for field in read_fields(bytes)? {
+            match field {
+                (1, Field::Bytes(bytes)) => sign_doc.body_bytes = bytes.to_vec(),
+                (2, Field::Bytes(bytes)) => sign_doc.auth_info_bytes = bytes.to_vec(),
+                (3, Field::Bytes(bytes)) => sign_doc.chain_id = read_string(bytes)?,
+                (4, Field::Varint(number)) => sign_doc.account_number = number,
+                _ => {}
+            }
+        }
+        Ok(sign_doc)
+    }
+
+    /// Encodes the `SignDoc` in canonical protobuf encoding, omitting empty
+    /// fields.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_bytes(&mut bytes, 1, &self.body_bytes);
+        write_bytes(&mut bytes, 2, &self.auth_info_bytes);
+        write_bytes(&mut bytes, 3, self.chain_id.as_bytes());
+        if self.account_number != 0 {
+            write_varint(&mut bytes, 4 << 3 | u64::from(WIRE_TYPE_VARINT));
+            write_varint(&mut bytes, self.account_number);
+        }
+        bytes
+    }
+
+    /// Hashes the encoded `SignDoc` with SHA-256, as signed by secp256k1
+    /// keys.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        Sha256::digest(self.encode()).into()
+    }
+}
+
+impl TxBody {
+    /// Decodes a protobuf encoded `TxBody`.
+    pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let mut body = TxBody::default();
+        for field in read_fields(bytes)? {
+            match field {
+                (1, Field::Bytes(bytes)) => body.messages.push(Any::decode(bytes)?),
+                (2, Field::Bytes(bytes)) => body.memo = read_string(bytes)?,
+                (3, Field::Varint(number)) => body.timeout_height = number,
+                _ => {}
+            }
+        }
+        Ok(body)
+    }
+}
+
+impl Any {
+    fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let mut any = Any::default();
+        for field in read_fields(bytes)? {
+            match field {
+                (1, Field::Bytes(bytes)) => any.type_url = read_string(bytes)?,
+                (2, Field::Bytes(bytes)) => any.value = bytes.to_vec(),
+                _ => {}
+            }
+        }
+        Ok(any)
+    }
+}
+
+impl AuthInfo {
+    /// Decodes a protobuf encoded `AuthInfo`.
+    pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let mut auth_info = AuthInfo::default();
+        for field in read_fields(bytes)? {
+            match field {
+                (1, Field::Bytes(bytes)) => auth_info.signer_infos.push(SignerInfo::decode(bytes)?),
+                (2, Field::Bytes(bytes)) => auth_info.fee = Some(Fee::decode(bytes)?),
+                _ => {}
+            }
+        }
+        Ok(auth_info)
+    }
+}
+
+impl SignerInfo {
+    fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let mut signer_info = SignerInfo::default();
+        for field in read_fields(bytes)? {
+            match field {
+                (1, Field::Bytes(bytes)) => signer_info.public_key = Some(Any::decode(bytes)?),
+                (3, Field::Varint(number)) => signer_info.sequence = number,
+                _ => {}
+            }
+        }
+        Ok(signer_info)
+    }
+}
+
+impl Fee {
+    fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let mut fee = Fee::default();
+        for field in read_fields(bytes)? {
+            match field {
+                (1, Field::Bytes(bytes)) => fee.amount.push(Coin::decode(bytes)?),
+                (2, Field::Varint(number)) => fee.gas_limit = number,
+                (3, Field::Bytes(bytes)) => fee.payer = read_string(bytes)?,
+                (4, Field::Bytes(bytes)) => fee.granter = read_string(bytes)?,
+                _ => {}
+            }
+        }
+        Ok(fee)
+    }
+}
+
+impl Coin {
+    fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let mut coin = Coin::default();
+        for field in read_fields(bytes)? {
+            match field {
+                (1, Field::Bytes(bytes)) => coin.denom = read_string(bytes)?,
+                (2, Field::Bytes(bytes)) => coin.amount = read_string(bytes)?,
+                _ => {}
+            }
+        }
+        Ok(coin)
+    }
+}
+
+impl MsgSend {
+    /// Decodes a `MsgSend` from a message of the transaction body, or returns
+    /// `None` for messages of other types.
+    pub fn from_any(message: &Any) -> Option<Self> {
+        if message.type_url != MSG_SEND_TYPE_URL {
+            return None;
+        }
+        let mut msg_send = MsgSend::default();
+        for field in read_fields(&message.value).ok()? {
+            match field {
+                (1, Field::Bytes(bytes)) => msg_send.from_address = read_string(bytes).ok()?,
+                (2, Field::Bytes(bytes)) => msg_send.to_address = read_string(bytes).ok()?,
+                (3, Field::Bytes(bytes)) => msg_send.amount.push(Coin::decode(bytes).ok()?),
+                _ => {}
+            }
+        }
+        Some(msg_send)
+    }
+}
+
+impl CosmosTransactionRequest {
+    /// Decodes the body and auth info of a `SignDoc`.
+    pub fn from_sign_doc(sign_doc: SignDoc) -> Result<Self, Error> {
+        Ok(Self {
+            body: TxBody::decode(&sign_doc.body_bytes)?,
+            auth_info: AuthInfo::decode(&sign_doc.auth_info_bytes)?,
+            sign_doc,
+        })
+    }
+
+    /// Gets the `MsgSend` messages of the transaction.
+    pub fn msg_sends(&self) -> Vec<MsgSend> {
+        self.body
+            .messages
+            .iter()
+            .filter_map(MsgSend::from_any)
+            .collect()
+    }
+}
+
+impl TransactionRequest for CosmosTransactionRequest {
+    fn from_json(json: Value) -> Result<Self, Error> {
+        let bytes = |name: &str| -> Result<Vec<u8>, Error> {
+            json[name]
+                .as_str()
+                .and_then(|bytes| base64::decode(bytes).ok())
+                .ok_or_else(|| Error::invalid_field(name, "base64 bytes"))
+        };
+        let account_number = match &json["accountNumber"] {
+            Value::String(number) => number.parse().ok(),
+            number => number.as_u64(),
+        }
+        .ok_or_else(|| Error::invalid_field("accountNumber", "an integer"))?;
+        let chain_id = json["chainId"]
+            .as_str()
+            .ok_or_else(|| Error::invalid_field("chainId", "a string"))?;
+
+        Self::from_sign_doc(SignDoc {
+            body_bytes: bytes("bodyBytes")?,
+            auth_info_bytes: bytes("authInfoBytes")?,
+            chain_id: chain_id.to_owned(),
+            account_number,
+        })
+    }
+
+    /// Parses a protobuf encoded `SignDoc`.
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_sign_doc(SignDoc::decode(bytes)?)
+    }
+
+    /// Classifies each coin sent with `MsgSend` as a token transfer of its
+    /// denomination, in a batch when there are several messages or coins.
+    fn transaction_info(&self) -> TransactionInfo {
+        let mut calls: Vec<_> = self
+            .body
+            .messages
+            .iter()
+            .flat_map(message_transaction_info)
+            .collect();
+        match calls.len() {
+            0 => TransactionInfo::Unknown {
+                value: None,
+                function: None,
+            },
+            1 => calls.remove(0),
+            _ => TransactionInfo::Batch { calls },
+        }
+    }
+}
+
+#[cfg(feature = "signing")]
+impl SignableTransactionRequest for CosmosTransactionRequest {
+    /// Returns the SHA-256 hash of the `SignDoc`. The chain is identified by
+    /// the chain id of the `SignDoc` instead of `chain_id`.
+    fn message_hash(&self, _chain_id: u64) -> Result<Vec<u8>, Error> {
+        Ok(self.sign_doc.signing_hash().to_vec())
+    }
+}
+
+fn message_transaction_info(message: &Any) -> Vec<TransactionInfo> {
+    let unknown = || TransactionInfo::Unknown {
+        value: None,
+        function: Some(message.type_url.clone()),
+    };
+    let msg_send = match MsgSend::from_any(message) {
+        Some(msg_send) => msg_send,
+        None => return vec![unknown()],
+    };
+
+    msg_send
+        .amount
+        .iter()
+        .map(|coin| match coin.amount.parse::<u128>() {
+            Ok(amount) => TransactionInfo::TokenTransfer {
+                from: msg_send.from_address.clone(),
+                to: msg_send.to_address.clone(),
+                amount: format!("{:#x}", amount),
+                token_id: Some(coin.denom.clone()),
+                token_info: None,
+                fiat_value: None,
+            },
+            Err(_) => unknown(),
+        })
+        .collect()
+}
+
+/// The value of a protobuf field.
+enum Field<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    Fixed,
+}
+
+/// Reads the field numbers and values of a protobuf message.
+fn read_fields(mut bytes: &[u8]) -> Result<Vec<(u64, Field<'_>)>, Error> {
+    let mut fields = Vec::new();
+    while !bytes.is_empty() {
+        let key = read_varint(&mut bytes)?;
+        let value = match (key & 0x07) as u8 {
+            WIRE_TYPE_VARINT => Field::Varint(read_varint(&mut bytes)?),
+            WIRE_TYPE_LENGTH_DELIMITED => {
+                let len =
+                    usize::try_from(read_varint(&mut bytes)?).map_err(|_| Error::InvalidData)?;
+                Field::Bytes(read_bytes(&mut bytes, len)?)
+            }
+            WIRE_TYPE_FIXED64 => {
+                read_bytes(&mut bytes, 8)?;
+                Field::Fixed
+            }
+            WIRE_TYPE_FIXED32 => {
+                read_bytes(&mut bytes, 4)?;
+                Field::Fixed
+            }
+            _ => return Err(Error::InvalidData),
+        };
+        fields.push((key >> 3, value));
+    }
+    Ok(fields)
+}
+
+fn read_bytes<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+    let value = bytes.get(..len).ok_or(Error::InvalidData)?;
+    *bytes = &bytes[len..];
+    Ok(value)
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, Error> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_bytes(bytes, 1)?[0];
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::InvalidData)
+}
+
+fn read_string(bytes: &[u8]) -> Result<String, Error> {
+    String::from_utf8(bytes.to_vec()).map_err(|_| Error::InvalidData)
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn write_bytes(bytes: &mut Vec<u8>, field: u64, value: &[u8]) {
+    if !value.is_empty() {
+        write_varint(bytes, field << 3 | u64::from(WIRE_TYPE_LENGTH_DELIMITED));
+        write_varint(bytes, value.len() as u64);
+        bytes.extend_from_slice(value);
+    }
+}
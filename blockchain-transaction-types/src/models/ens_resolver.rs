@@ -0,0 +1,127 @@
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use web3::types::Address;
+
+use crate::models::error::Error;
+use crate::models::transaction_info::TransactionInfo;
+
+/// The ENS primary name of an address.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnsName {
+    /// The reverse resolved name, such as `vitalik.eth`.
+    pub name: String,
+    /// The `avatar` text record of the name.
+    pub avatar: Option<String>,
+}
+
+/// Looks up the ENS names of addresses.
+#[async_trait]
+pub trait EnsResolver {
+    /// Gets the primary name of `address`, or `None` if it has no name.
+    async fn lookup(&self, address: Address) -> Result<Option<EnsName>, Error>;
+}
+
+/// Caches the names found by an [`EnsResolver`], including addresses
+/// without a name.
+///
+/// Failed lookups aren't cached, so they are retried.
+#[derive(Debug, Default)]
+pub struct CachingEnsResolver<R> {
+    resolver: R,
+    cache: Mutex<HashMap<Address, Option<EnsName>>>,
+}
+
+impl<R> CachingEnsResolver<R> {
+    /// Wraps `resolver` with an empty cache.
+    pub fn new(resolver: R) -> Self {
+        Self {
+            resolver,
+            cache: Mutex::default(),
+        }
+    }
+
+    /// Gets the wrapped resolver.
+    pub fn inner(&self) -> &R {
+        &self.resolver
+    }
+
+    /// Forgets all cached names.
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+}
+
+#[async_trait]
+impl<R: EnsResolver + Send + Sync> EnsResolver for CachingEnsResolver<R> {
+    async fn lookup(&self, address: Address) -> Result<Option<EnsName>, Error> {
+        if let Some(name) = self.cache.lock().unwrap().get(&address) {
+            return Ok(name.clone());
+        }
+        let name = self.resolver.lookup(address).await?;
+        self.cache.lock().unwrap().insert(address, name.clone());
+        Ok(name)
+    }
+}
+
+/// Gets the account addresses of a transaction, such as the sender and
+/// recipient of transfers, the owner and spender of approvals and the Safe of
+/// Safe transactions.
+pub fn addresses(info: &TransactionInfo) -> BTreeSet<Address> {
+    let mut addresses = BTreeSet::new();
+    collect_addresses(info, &mut addresses);
+    addresses
+}
+
+fn collect_addresses(info: &TransactionInfo, addresses: &mut BTreeSet<Address>) {
+    let fields: Vec<&String> = match info {
+        TransactionInfo::TokenTransfer { from, to, .. } => vec![from, to],
+        TransactionInfo::TokenMint { to, .. } => vec![to],
+        TransactionInfo::TokenBurn { from, .. } => vec![from],
+        TransactionInfo::TokenBatchTransfer { transfers }
+        | TransactionInfo::TokenBatchMint { transfers }
+        | TransactionInfo::TokenBatchBurn { transfers } => transfers
+            .iter()
+            .flat_map(|transfer| [&transfer.from, &transfer.to])
+            .collect(),
+        TransactionInfo::Approval { owner, spender, .. } => vec![owner, spender],
+        TransactionInfo::TokenSwap { recipient, .. } => vec![recipient],
+        TransactionInfo::SafeTransaction { safe, to, info, .. } => {
+            collect_addresses(info, addresses);
+            vec![safe, to]
+        }
+        TransactionInfo::Batch { calls } => {
+            for call in calls {
+                collect_addresses(call, addresses);
+            }
+            vec![]
+        }
+        _ => vec![],
+    };
+    addresses.extend(
+        fields
+            .into_iter()
+            .filter_map(|address| Address::from_str(address).ok()),
+    );
+}
+
+/// Resolves the names of the account addresses of a transaction for display.
+///
+/// Addresses without a name are left out, and lookup errors are ignored since
+/// names are only annotations.
+pub async fn resolve_names(
+    info: &TransactionInfo,
+    resolver: &(dyn EnsResolver + Sync),
+) -> HashMap<Address, EnsName> {
+    let mut names = HashMap::new();
+    for address in addresses(info) {
+        if let Ok(Some(name)) = resolver.lookup(address).await {
+            names.insert(address, name);
+        }
+    }
+    names
+}
@@ -1,13 +1,78 @@
+use serde::de::DeserializeOwned;
 use thiserror::Error as ThisError;
 
 #[derive(Debug, ThisError)]
 pub enum Error {
     #[error("Could not decode JSON: {0}")]
     Json(#[from] serde_json::Error),
+    /// A JSON value that could not be decoded, with the path of the failing
+    /// field, such as `authorizationList[0].chainId`.
+    #[error("Could not decode JSON at {path}: {source}")]
+    Decode {
+        path: String,
+        source: serde_json::Error,
+    },
+    /// A field that was missing or didn't have the expected type or format.
+    #[error("Invalid field {field}: expected {expected}")]
+    InvalidField { field: String, expected: String },
+    #[error("Could not decode RLP: {0}")]
+    Rlp(#[from] rlp::DecoderError),
     #[error("Unknown coin type")]
     InvalidCoinType,
     #[error("Invalid data")]
     InvalidData,
+    #[error("Unknown chain {0}")]
+    UnknownChain(u64),
+    #[error("Chain ID {payload} of the payload doesn't match chain ID {expected}")]
+    ChainIdMismatch { payload: u64, expected: u64 },
     #[error("Invalid key")]
     Key(secp256k1::Error),
+    #[cfg(feature = "ethereum")]
+    #[error("Invalid ABI: {0}")]
+    Abi(#[from] web3::ethabi::Error),
+    #[cfg(any(
+        feature = "access-list-provider",
+        feature = "fee-estimator",
+        feature = "simulator",
+        feature = "token-info-provider"
+    ))]
+    #[error("Web3 call failed: {0}")]
+    Web3(#[from] web3::Error),
+    #[cfg(feature = "reqwest")]
+    #[error("Request failed: {0}")]
+    Request(#[from] reqwest::Error),
+}
+
+impl Error {
+    pub(crate) fn invalid_field(field: &str, expected: &str) -> Self {
+        Error::InvalidField {
+            field: field.to_owned(),
+            expected: expected.to_owned(),
+        }
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for Error {
+    fn from(error: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        let path = error.path().to_string();
+        let source = error.into_inner();
+        // The path is just `.` for errors outside of any field, such as
+        // syntax errors.
+        if path == "." {
+            Error::Json(source)
+        } else {
+            Error::Decode { path, source }
+        }
+    }
+}
+
+/// Decodes a JSON value, keeping the path of the field that failed to decode.
+pub fn from_json_value<T: DeserializeOwned>(json: serde_json::Value) -> Result<T, Error> {
+    Ok(serde_path_to_error::deserialize(json)?)
+}
+
+/// Decodes JSON text, keeping the path of the field that failed to decode.
+pub fn from_json_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    Ok(serde_path_to_error::deserialize(&mut deserializer)?)
 }
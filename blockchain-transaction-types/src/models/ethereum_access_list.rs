@@ -0,0 +1,156 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "access-list-provider")]
+use async_trait::async_trait;
+use web3::types::{
+    AccessList, AccessListItem, Address, TransactionRequest as Web3TransactionRequest, H256,
+};
+#[cfg(feature = "access-list-provider")]
+use web3::{helpers, Transport, Web3};
+
+#[cfg(feature = "access-list-provider")]
+use crate::models::error::Error;
+
+const EIP_2930_TRANSACTION_TYPE: u64 = 1;
+
+/// Collects the addresses and storage keys touched by a transaction into an
+/// [EIP-2930] access list.
+///
+/// Each address is listed once, with its storage keys deduplicated, in a
+/// deterministic order.
+///
+/// [EIP-2930]: https://eips.ethereum.org/EIPS/eip-2930
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessListBuilder {
+    items: BTreeMap<Address, BTreeSet<H256>>,
+}
+
+impl AccessListBuilder {
+    /// Creates an empty access list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an address without any storage keys.
+    pub fn add_address(&mut self, address: Address) -> &mut Self {
+        self.items.entry(address).or_default();
+        self
+    }
+
+    /// Adds a storage key of `address`.
+    pub fn add_storage_key(&mut self, address: Address, storage_key: H256) -> &mut Self {
+        self.items.entry(address).or_default().insert(storage_key);
+        self
+    }
+
+    /// Adds every address and storage key of an existing access list.
+    pub fn extend(&mut self, access_list: &[AccessListItem]) -> &mut Self {
+        for item in access_list {
+            self.add_address(item.address);
+            for storage_key in &item.storage_keys {
+                self.add_storage_key(item.address, *storage_key);
+            }
+        }
+        self
+    }
+
+    /// Removes an address that is warm anyway, such as the sender or
+    /// recipient, when it has no storage keys.
+    pub fn remove_empty(&mut self, address: &Address) -> &mut Self {
+        if self.items.get(address).map(BTreeSet::is_empty) == Some(true) {
+            self.items.remove(address);
+        }
+        self
+    }
+
+    /// Gets the access list, ordered by address and storage key.
+    pub fn build(&self) -> AccessList {
+        self.items
+            .iter()
+            .map(|(address, storage_keys)| AccessListItem {
+                address: *address,
+                storage_keys: storage_keys.iter().copied().collect(),
+            })
+            .collect()
+    }
+}
+
+/// Sets the access list of a transaction request.
+///
+/// Legacy requests become EIP-2930 requests, since only typed transactions
+/// can carry an access list. EIP-1559 and later requests keep their type.
+pub fn with_access_list(
+    request: &Web3TransactionRequest,
+    access_list: AccessList,
+) -> Web3TransactionRequest {
+    let transaction_type = request
+        .transaction_type
+        .filter(|transaction_type| !transaction_type.is_zero())
+        .unwrap_or_else(|| EIP_2930_TRANSACTION_TYPE.into());
+    Web3TransactionRequest {
+        transaction_type: Some(transaction_type),
+        access_list: Some(access_list),
+        ..request.clone()
+    }
+}
+
+/// Creates access lists by simulating transactions.
+#[cfg(feature = "access-list-provider")]
+#[async_trait]
+pub trait AccessListProvider {
+    /// Gets the addresses and storage keys touched by `request`.
+    async fn create_access_list(
+        &self,
+        request: &Web3TransactionRequest,
+    ) -> Result<AccessList, Error>;
+}
+
+/// Creates access lists with `eth_createAccessList` at the latest block.
+#[cfg(feature = "access-list-provider")]
+#[derive(Clone, Debug)]
+pub struct Web3AccessListProvider<T: Transport> {
+    web3: Web3<T>,
+}
+
+#[cfg(feature = "access-list-provider")]
+impl<T: Transport> Web3AccessListProvider<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            web3: Web3::new(transport),
+        }
+    }
+}
+
+#[cfg(feature = "access-list-provider")]
+#[async_trait]
+impl<T> AccessListProvider for Web3AccessListProvider<T>
+where
+    T: Transport + Send + Sync,
+    T::Out: Send,
+{
+    async fn create_access_list(
+        &self,
+        request: &Web3TransactionRequest,
+    ) -> Result<AccessList, Error> {
+        let params = vec![helpers::serialize(request), helpers::serialize(&"latest")];
+        let result = self
+            .web3
+            .transport()
+            .execute("eth_createAccessList", params)
+            .await?;
+        Ok(serde_json::from_value(result["accessList"].clone())?)
+    }
+}
+
+/// Fills in the access list of a transaction request from a provider, keeping
+/// any entries already listed.
+#[cfg(feature = "access-list-provider")]
+pub async fn fill_access_list(
+    request: &Web3TransactionRequest,
+    provider: &(dyn AccessListProvider + Sync),
+) -> Result<Web3TransactionRequest, Error> {
+    let mut builder = AccessListBuilder::new();
+    builder.extend(request.access_list.as_deref().unwrap_or_default());
+    builder.extend(&provider.create_access_list(request).await?);
+    Ok(with_access_list(request, builder.build()))
+}
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use web3::types::{Bytes, TransactionRequest as Web3TransactionRequest, H256, U256};
+
+use crate::models::error::{from_json_value, Error};
+use crate::models::ethereum_transaction::{
+    decode_raw_transaction, hex_amount, max_total_fee, request_fee_info, EIP_4844_TRANSACTION_TYPE,
+};
+#[cfg(feature = "signing")]
+use crate::models::ethereum_transaction::{
+    encode_transaction_request, raw_signature, SigningMode, TypedTransactionFields,
+};
+#[cfg(feature = "signing")]
+use crate::models::keccak::keccak256;
+#[cfg(feature = "signing")]
+use crate::models::transaction::SignableTransactionRequest;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::{FeeInfo, TransactionInfo};
+
+/// The version of versioned hashes of KZG commitments.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// The length in bytes of KZG commitments and proofs.
+pub const KZG_LENGTH: usize = 48;
+
+/// The blob gas used by each blob.
+pub const GAS_PER_BLOB: u64 = 1 << 17;
+
+/// An [EIP-4844] blob transaction request.
+///
+/// The blobs themselves aren't part of the signed transaction, and are
+/// described by their commitments in an optional sidecar.
+///
+/// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlobTransactionRequest {
+    #[serde(flatten)]
+    pub request: Web3TransactionRequest,
+    #[serde(rename = "maxFeePerBlobGas")]
+    pub max_fee_per_blob_gas: U256,
+    #[serde(rename = "blobVersionedHashes")]
+    pub blob_versioned_hashes: Vec<H256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sidecar: Option<BlobSidecar>,
+}
+
+/// The KZG commitments and proofs of the blobs of a transaction.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BlobSidecar {
+    pub commitments: Vec<Bytes>,
+    pub proofs: Vec<Bytes>,
+}
+
+impl BlobSidecar {
+    /// Computes the versioned hash of each commitment, which is its SHA-256
+    /// hash with the version as first byte.
+    pub fn versioned_hashes(&self) -> Vec<H256> {
+        self.commitments
+            .iter()
+            .map(|commitment| {
+                let mut hash: [u8; 32] = Sha256::digest(&commitment.0).into();
+                hash[0] = VERSIONED_HASH_VERSION_KZG;
+                H256(hash)
+            })
+            .collect()
+    }
+
+    /// Checks that there is a proof for each commitment and that the
+    /// commitments match `versioned_hashes`.
+    pub fn matches(&self, versioned_hashes: &[H256]) -> bool {
+        self.commitments.len() == self.proofs.len()
+            && self
+                .commitments
+                .iter()
+                .chain(&self.proofs)
+                .all(|bytes| bytes.0.len() == KZG_LENGTH)
+            && self.versioned_hashes() == versioned_hashes
+    }
+}
+
+impl BlobTransactionRequest {
+    /// Checks that the request is typed as a blob transaction and that its
+    /// sidecar, if any, matches its versioned hashes.
+    pub fn validate(&self) -> Result<(), Error> {
+        let is_blob_transaction = self.request.transaction_type
+            == Some(EIP_4844_TRANSACTION_TYPE.into())
+            && !self.blob_versioned_hashes.is_empty();
+        let sidecar_matches = self
+            .sidecar
+            .as_ref()
+            .map(|sidecar| sidecar.matches(&self.blob_versioned_hashes))
+            .unwrap_or(true);
+        if is_blob_transaction && sidecar_matches {
+            Ok(())
+        } else {
+            Err(Error::InvalidData)
+        }
+    }
+}
+
+impl TransactionRequest for BlobTransactionRequest {
+    fn from_json(json: Value) -> Result<Self, Error> {
+        let request: Self = from_json_value(json)?;
+        request.validate()?;
+        Ok(request)
+    }
+
+    /// Decodes a signed raw blob transaction or the unsigned payload signed
+    /// by its sender, without blobs.
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
+        let raw = decode_raw_transaction(bytes)?;
+        let (max_fee_per_blob_gas, blob_versioned_hashes) = raw.blob.ok_or(Error::InvalidData)?;
+        Ok(Self {
+            request: raw.request,
+            max_fee_per_blob_gas,
+            blob_versioned_hashes,
+            sidecar: None,
+        })
+    }
+
+    fn transaction_info(&self) -> TransactionInfo {
+        self.request.transaction_info()
+    }
+
+    /// Gets the fees of the request, including the maximum blob gas fee of
+    /// its blobs in the total.
+    fn fee_info(&self) -> Option<FeeInfo> {
+        let blob_fee = U256::from(GAS_PER_BLOB)
+            .checked_mul(U256::from(self.blob_versioned_hashes.len()))
+            .and_then(|gas| gas.checked_mul(self.max_fee_per_blob_gas));
+        let max_total_fee = max_total_fee(&self.request)
+            .zip(blob_fee)
+            .and_then(|(fee, blob_fee)| fee.checked_add(blob_fee));
+
+        Some(FeeInfo {
+            max_fee_per_blob_gas: Some(hex_amount(self.max_fee_per_blob_gas)),
+            max_total_fee: max_total_fee.map(hex_amount),
+            ..request_fee_info(&self.request)
+        })
+    }
+}
+
+#[cfg(feature = "signing")]
+impl SignableTransactionRequest for BlobTransactionRequest {
+    fn message_hash(&self, chain_id: u64) -> Result<Vec<u8>, Error> {
+        self.validate()?;
+        let message = encode_transaction_request(
+            &self.request,
+            SigningMode::Eip155(chain_id),
+            None,
+            Some(TypedTransactionFields::Blob(
+                self.max_fee_per_blob_gas,
+                &self.blob_versioned_hashes,
+            )),
+        )?;
+        Ok(keccak256(&message).to_vec())
+    }
+
+    fn encode_signed(
+        &self,
+        chain_id: u64,
+        signature: &[u8],
+        recovery_id: u64,
+    ) -> Result<Vec<u8>, Error> {
+        self.validate()?;
+        let signature = raw_signature(
+            &self.request,
+            SigningMode::Eip155(chain_id),
+            signature,
+            recovery_id,
+        )?;
+        encode_transaction_request(
+            &self.request,
+            SigningMode::Eip155(chain_id),
+            Some(&signature),
+            Some(TypedTransactionFields::Blob(
+                self.max_fee_per_blob_gas,
+                &self.blob_versioned_hashes,
+            )),
+        )
+    }
+
+    fn transaction_hash(&self, _chain_id: u64, raw: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(keccak256(raw).to_vec())
+    }
+}
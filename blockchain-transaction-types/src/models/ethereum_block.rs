@@ -0,0 +1,66 @@
+use serde::Deserialize;
+use serde_json::Value;
+use web3::types::{Transaction as Web3Transaction, H256};
+
+use crate::models::abi_registry::AbiRegistry;
+use crate::models::error::{from_json_value, Error};
+use crate::models::ethereum_transaction::web3_transaction_info;
+use crate::models::transaction_info::TransactionInfo;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// The transactions of a block fetched with full transaction objects.
+#[derive(Deserialize)]
+struct FullBlock {
+    transactions: Vec<Web3Transaction>,
+}
+
+/// Classifies every transaction of a block, as returned by
+/// `eth_getBlockByNumber` or `eth_getBlockByHash` with full transaction
+/// objects, in block order.
+///
+/// Fails with [`Error::Decode`] for blocks with only transaction hashes.
+pub fn classify_block(block: Value) -> Result<Vec<(H256, TransactionInfo)>, Error> {
+    classify_transactions(block, None)
+}
+
+/// Classifies the transactions of `block`, decoding unknown calls with
+/// `registry`.
+///
+/// The calldata of every transaction is hex encoded into the same buffer.
+pub(crate) fn classify_transactions(
+    block: Value,
+    registry: Option<&AbiRegistry>,
+) -> Result<Vec<(H256, TransactionInfo)>, Error> {
+    let block: FullBlock = from_json_value(block)?;
+    let mut input = String::new();
+
+    Ok(block
+        .transactions
+        .iter()
+        .map(|transaction| {
+            encode_input(&mut input, &transaction.input.0);
+            let info = match web3_transaction_info(transaction, &input) {
+                TransactionInfo::Unknown { value, function } => registry
+                    .and_then(|registry| {
+                        registry.decode_call(transaction.to.as_ref(), &transaction.input.0)
+                    })
+                    .unwrap_or(TransactionInfo::Unknown { value, function }),
+                info => info,
+            };
+            (transaction.hash, info)
+        })
+        .collect())
+}
+
+/// Hex encodes `bytes` with a `0x` prefix into `buffer`, replacing its
+/// contents.
+fn encode_input(buffer: &mut String, bytes: &[u8]) {
+    buffer.clear();
+    buffer.reserve(2 + bytes.len() * 2);
+    buffer.push_str("0x");
+    for byte in bytes {
+        buffer.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        buffer.push(HEX_DIGITS[(byte & 0x0f) as usize] as char);
+    }
+}
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use web3::types::{Address, TransactionRequest as Web3TransactionRequest, U256};
+
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::{CallParameter, TransactionInfo};
+
+const WORD_SIZE: usize = 32;
+
+/// The calldata of a call to an unknown function, split into its selector and
+/// parameters with types guessed from their values, like block explorers show
+/// calls to unverified contracts.
+///
+/// Each 32-byte word of the parameters is guessed as an `address`, `uint256`
+/// or `bytes32`, unless it points past itself to a length followed by data,
+/// which is guessed as the offset of `bytes` or an array. The guesses are for
+/// display only: a small `uint256` may be a `bool`, and `bytes` may be a
+/// `string`.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedCall {
+    /// The hex encoded function selector.
+    pub selector: String,
+    /// The parameters, named by position such as `[0]`.
+    pub parameters: Vec<CallParameter>,
+}
+
+impl DecodedCall {
+    /// Splits calldata into its selector and guessed parameters.
+    ///
+    /// Returns `None` without a selector. Trailing bytes that don't fill a
+    /// word are ignored.
+    pub fn from_calldata(data: &[u8]) -> Option<Self> {
+        let selector = data.get(..4)?;
+        let arguments = &data[4..(data.len() - 4) / WORD_SIZE * WORD_SIZE + 4];
+        let words: Vec<&[u8]> = arguments.chunks(WORD_SIZE).collect();
+
+        // The head ends where the data of the first dynamic parameter starts.
+        let mut head_size = words.len();
+        let mut offsets = vec![];
+        for (index, word) in words.iter().enumerate() {
+            if index >= head_size {
+                break;
+            }
+            if let Some(offset) = tail_offset(arguments, word, index) {
+                head_size = head_size.min(offset / WORD_SIZE);
+                offsets.push((index, offset));
+            }
+        }
+
+        let mut ends: Vec<usize> = offsets.iter().map(|(_, offset)| *offset).collect();
+        ends.push(arguments.len());
+        ends.sort_unstable();
+
+        let parameters = words[..head_size]
+            .iter()
+            .enumerate()
+            .map(|(index, word)| {
+                let (kind, value) = match offsets.iter().find(|(i, _)| *i == index) {
+                    Some((_, offset)) => {
+                        let end = ends
+                            .iter()
+                            .copied()
+                            .find(|end| end > offset)
+                            .unwrap_or(*offset);
+                        guess_dynamic(arguments, *offset, end)
+                    }
+                    None => guess_word(word),
+                };
+                CallParameter {
+                    name: format!("[{}]", index),
+                    kind,
+                    value,
+                }
+            })
+            .collect();
+
+        Some(Self {
+            selector: format!("0x{}", hex_encode(selector)),
+            parameters,
+        })
+    }
+}
+
+/// Decodes the calldata of a request classified as
+/// [`TransactionInfo::Unknown`], for display.
+///
+/// Returns `None` for requests that are classified, or don't call a
+/// function.
+pub fn decoded_call(request: &Web3TransactionRequest) -> Option<DecodedCall> {
+    match request.transaction_info() {
+        TransactionInfo::Unknown { .. } => DecodedCall::from_calldata(&request.data.as_ref()?.0),
+        _ => None,
+    }
+}
+
+/// Gets the offset a head word at `index` points to, if it points past the
+/// word to a length that fits in the calldata.
+fn tail_offset(arguments: &[u8], word: &[u8], index: usize) -> Option<usize> {
+    let offset = word_to_usize(word, arguments.len())?;
+    if offset % WORD_SIZE != 0 || offset <= index * WORD_SIZE {
+        return None;
+    }
+    let data_start = offset + WORD_SIZE;
+    let length = word_to_usize(arguments.get(offset..data_start)?, arguments.len())?;
+    if data_start + length > arguments.len() {
+        return None;
+    }
+    Some(offset)
+}
+
+/// Guesses the dynamic parameter at `offset`, as an array when its length
+/// counts the words up to `end`, or as bytes otherwise.
+fn guess_dynamic(arguments: &[u8], offset: usize, end: usize) -> (String, Value) {
+    let data_start = offset + WORD_SIZE;
+    let length = word_to_usize(&arguments[offset..data_start], arguments.len()).unwrap_or_default();
+
+    if length > 0 && data_start + length * WORD_SIZE == end {
+        let elements: Vec<(String, Value)> = arguments[data_start..end]
+            .chunks(WORD_SIZE)
+            .map(guess_word)
+            .collect();
+        let kind = if elements.iter().all(|(kind, _)| *kind == elements[0].0) {
+            elements[0].0.clone()
+        } else {
+            "bytes32".to_owned()
+        };
+        let values = arguments[data_start..end]
+            .chunks(WORD_SIZE)
+            .zip(elements)
+            .map(|(word, (element_kind, value))| {
+                if element_kind == kind {
+                    value
+                } else {
+                    Value::String(format!("0x{}", hex_encode(word)))
+                }
+            })
+            .collect();
+        return (format!("{}[]", kind), Value::Array(values));
+    }
+
+    let bytes = &arguments[data_start..data_start + length];
+    (
+        "bytes".to_owned(),
+        Value::String(format!("0x{}", hex_encode(bytes))),
+    )
+}
+
+/// Guesses a static parameter from its word.
+///
+/// Words of 20 bytes too large to be amounts are addresses, smaller words are
+/// integers, and full words are hashes or packed data, except the maximum
+/// integer used for unlimited approvals.
+fn guess_word(word: &[u8]) -> (String, Value) {
+    let (kind, value) = if word[..12].iter().all(|byte| *byte == 0) {
+        if word[12..16].iter().any(|byte| *byte != 0) {
+            (
+                "address",
+                format!("{:#x}", Address::from_slice(&word[12..])),
+            )
+        } else {
+            ("uint256", format!("{:#x}", U256::from_big_endian(word)))
+        }
+    } else if word.iter().all(|byte| *byte == 0xff) {
+        ("uint256", format!("{:#x}", U256::MAX))
+    } else {
+        ("bytes32", format!("0x{}", hex_encode(word)))
+    };
+    (kind.to_owned(), Value::String(value))
+}
+
+/// Reads a word as a number no greater than `max`.
+fn word_to_usize(word: &[u8], max: usize) -> Option<usize> {
+    let value = U256::from_big_endian(word);
+    if value > U256::from(max) {
+        return None;
+    }
+    Some(value.as_usize())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
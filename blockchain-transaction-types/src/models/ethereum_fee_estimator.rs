@@ -0,0 +1,160 @@
+use async_trait::async_trait;
+use web3::types::{BlockNumber, CallRequest, TransactionRequest as Web3TransactionRequest, U256};
+use web3::{Transport, Web3};
+
+use crate::models::error::Error;
+
+const EIP_1559_TRANSACTION_TYPE: u64 = 2;
+const EIP_2930_TRANSACTION_TYPE: u64 = 1;
+
+/// The suggested fees per gas of an EIP-1559 transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeSuggestion {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+/// Estimates the gas and fees of transactions.
+#[async_trait]
+pub trait FeeEstimator {
+    /// Estimates the gas used by `request`.
+    async fn estimate_gas(&self, request: &Web3TransactionRequest) -> Result<U256, Error>;
+
+    /// Suggests fees for a transaction to be included in the next blocks.
+    async fn suggest_fees(&self) -> Result<FeeSuggestion, Error>;
+}
+
+/// Fills in the gas and fees of a transaction request before signing.
+///
+/// Requests without a type become EIP-1559 requests, unless they set a gas
+/// price. EIP-2930 requests without a gas price use the suggested maximum fee
+/// as their gas price. Fields already set are kept.
+pub async fn prepare(
+    request: &Web3TransactionRequest,
+    estimator: &(dyn FeeEstimator + Sync),
+) -> Result<Web3TransactionRequest, Error> {
+    let mut prepared = request.clone();
+    if prepared.gas.is_none() {
+        prepared.gas = Some(estimator.estimate_gas(request).await?);
+    }
+    if prepared.gas_price.is_some() {
+        return Ok(prepared);
+    }
+
+    let transaction_type = prepared.transaction_type.map(|t| t.as_u64());
+    let needs_fees = prepared.max_fee_per_gas.is_none()
+        || prepared.max_priority_fee_per_gas.is_none()
+        || transaction_type == Some(EIP_2930_TRANSACTION_TYPE);
+    if !needs_fees {
+        return Ok(prepared);
+    }
+
+    let fees = estimator.suggest_fees().await?;
+    match transaction_type {
+        Some(EIP_2930_TRANSACTION_TYPE) => prepared.gas_price = Some(fees.max_fee_per_gas),
+        _ => {
+            if transaction_type.is_none() {
+                prepared.transaction_type = Some(EIP_1559_TRANSACTION_TYPE.into());
+            }
+            let max_priority_fee_per_gas = *prepared
+                .max_priority_fee_per_gas
+                .get_or_insert(fees.max_priority_fee_per_gas);
+            prepared
+                .max_fee_per_gas
+                .get_or_insert(fees.max_fee_per_gas.max(max_priority_fee_per_gas));
+        }
+    }
+    Ok(prepared)
+}
+
+/// Estimates gas with `eth_estimateGas`, and suggests fees from a percentile
+/// of the priority fees paid in recent blocks with `eth_feeHistory`.
+///
+/// The suggested maximum fee is twice the base fee of the next block plus the
+/// priority fee, which stays includable through five full blocks in a row.
+#[derive(Clone, Debug)]
+pub struct Web3FeeEstimator<T: Transport> {
+    web3: Web3<T>,
+    block_count: u64,
+    reward_percentile: f64,
+}
+
+impl<T: Transport> Web3FeeEstimator<T> {
+    /// Creates an estimator using the median priority fee of the last 10
+    /// blocks.
+    pub fn new(transport: T) -> Self {
+        Self {
+            web3: Web3::new(transport),
+            block_count: 10,
+            reward_percentile: 50.0,
+        }
+    }
+
+    /// Sets the number of recent blocks and the percentile of their priority
+    /// fees used to suggest fees.
+    pub fn with_fee_history(mut self, block_count: u64, reward_percentile: f64) -> Self {
+        self.block_count = block_count;
+        self.reward_percentile = reward_percentile;
+        self
+    }
+}
+
+#[async_trait]
+impl<T> FeeEstimator for Web3FeeEstimator<T>
+where
+    T: Transport + Send + Sync,
+    T::Out: Send,
+{
+    async fn estimate_gas(&self, request: &Web3TransactionRequest) -> Result<U256, Error> {
+        let call = CallRequest {
+            from: Some(request.from),
+            to: request.to,
+            gas: None,
+            gas_price: request.gas_price,
+            value: request.value,
+            data: request.data.clone(),
+            transaction_type: request.transaction_type,
+            access_list: request.access_list.clone(),
+            max_fee_per_gas: request.max_fee_per_gas,
+            max_priority_fee_per_gas: request.max_priority_fee_per_gas,
+        };
+        Ok(self.web3.eth().estimate_gas(call, None).await?)
+    }
+
+    async fn suggest_fees(&self) -> Result<FeeSuggestion, Error> {
+        let history = self
+            .web3
+            .eth()
+            .fee_history(
+                self.block_count.into(),
+                BlockNumber::Latest,
+                Some(vec![self.reward_percentile]),
+            )
+            .await?;
+
+        let next_base_fee = history
+            .base_fee_per_gas
+            .last()
+            .copied()
+            .ok_or(Error::InvalidData)?;
+        let rewards: Vec<U256> = history
+            .reward
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|reward| reward.first().copied())
+            .collect();
+        let max_priority_fee_per_gas = if rewards.is_empty() {
+            U256::zero()
+        } else {
+            rewards
+                .iter()
+                .fold(U256::zero(), |total, reward| total + reward)
+                / rewards.len()
+        };
+
+        Ok(FeeSuggestion {
+            max_fee_per_gas: next_base_fee * 2 + max_priority_fee_per_gas,
+            max_priority_fee_per_gas,
+        })
+    }
+}
@@ -1,43 +1,91 @@
 use crate::models::error::Error;
+use crate::models::keccak::keccak256;
 use crate::models::message::{MessageInfo, SignableMessage};
 use serde::Deserialize;
 use serde_json::Value;
+use web3::types::H256;
 
-#[derive(Deserialize, Clone)]
+/// Hashes a message with the EIP-191 `personal_sign` envelope,
+/// `keccak256("\x19Ethereum Signed Message:\n" + len(message) + message)`.
+pub fn hash_personal_message(message: &[u8]) -> H256 {
+    let mut bytes = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    bytes.extend_from_slice(message);
+    H256(keccak256(&bytes))
+}
+
+/// An Ethereum message to sign.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum Message {
+    /// A `personal_sign` message, either UTF-8 text or `0x` prefixed hex
+    /// which is signed as the decoded bytes.
     String(String),
 }
 
+/// Decodes `0x` prefixed hex, returning `None` for other strings.
+pub(crate) fn decode_prefixed_hex(string: &str) -> Option<Vec<u8>> {
+    let hex = string.strip_prefix("0x")?;
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
 impl crate::models::message::Message for Message {
-    fn from_json(_json: Value) -> Result<Self, Error>
+    /// Parses a `personal_sign` string.
+    fn from_json(json: Value) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        todo!()
+        match json {
+            Value::String(string) => Ok(Message::String(string)),
+            _ => Err(Error::InvalidData),
+        }
     }
 
-    fn from_raw(_bytes: &[u8]) -> Result<Self, Error>
+    /// Parses a UTF-8 message, or hex encodes other bytes.
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        todo!()
+        match std::str::from_utf8(bytes) {
+            Ok(string) => Ok(Message::String(string.to_owned())),
+            Err(_) => Ok(Message::String(format!(
+                "0x{}",
+                bytes
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect::<String>()
+            ))),
+        }
     }
 
+    /// Describes the message and whether it is signed as text or as
+    /// hex-decoded bytes.
     fn message_info(&self) -> MessageInfo {
-        todo!()
+        match self {
+            Message::String(string) => match decode_prefixed_hex(string) {
+                Some(bytes) => MessageInfo::Bytes {
+                    hex: string.clone(),
+                    text: String::from_utf8(bytes).ok(),
+                },
+                None => MessageInfo::String(string.clone()),
+            },
+        }
     }
 }
 
 impl SignableMessage for Message {
     fn message_hash(&self, _chain_id: u64) -> Result<Vec<u8>, Error> {
         match self {
-            Message::String(s) => Ok({
-                let mut s = s.as_bytes().to_vec();
-                let mut vec = format!("\x19Ethereum Signed Message:\n{}", s.len()).into_bytes();
-                vec.append(&mut s);
-                vec
-            }),
+            Message::String(string) => {
+                let bytes =
+                    decode_prefixed_hex(string).unwrap_or_else(|| string.as_bytes().to_vec());
+                Ok(hash_personal_message(&bytes).as_bytes().to_vec())
+            }
         }
     }
 }
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use web3::ethabi::{self, ParamType, Token};
+use web3::types::{Log, TransactionReceipt, H256, U256, U64};
+
+use crate::models::keccak::keccak256;
+use crate::models::transaction_info::{TokenTransferInfo, TransactionInfo};
+
+/// The ERC-20 and ERC-721 `Transfer` event.
+const TRANSFER_EVENT: &str = "Transfer(address,address,uint256)";
+/// The ERC-20 and ERC-721 `Approval` event.
+const APPROVAL_EVENT: &str = "Approval(address,address,uint256)";
+const TRANSFER_SINGLE_EVENT: &str = "TransferSingle(address,address,address,uint256,uint256)";
+const TRANSFER_BATCH_EVENT: &str = "TransferBatch(address,address,address,uint256[],uint256[])";
+
+/// A token event emitted by a contract.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptEvent {
+    /// The contract emitting the event.
+    pub contract: String,
+    pub log_index: Option<String>,
+    pub info: TransactionInfo,
+}
+
+/// What a mined transaction did, decoded from the event logs of its receipt.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptInfo {
+    pub transaction_hash: String,
+    /// Whether the transaction succeeded, or `None` for receipts from before
+    /// the Byzantium fork.
+    pub succeeded: Option<bool>,
+    pub gas_used: Option<String>,
+    /// The token events, skipping logs of other events.
+    pub events: Vec<ReceiptEvent>,
+}
+
+impl ReceiptInfo {
+    /// Classifies what actually happened, as the single token event or a
+    /// batch of the events.
+    pub fn transaction_info(&self) -> TransactionInfo {
+        match self.events.as_slice() {
+            [event] => event.info.clone(),
+            events => TransactionInfo::Batch {
+                calls: events.iter().map(|event| event.info.clone()).collect(),
+            },
+        }
+    }
+}
+
+impl From<&TransactionReceipt> for ReceiptInfo {
+    fn from(receipt: &TransactionReceipt) -> Self {
+        Self {
+            transaction_hash: format!("{:#x}", receipt.transaction_hash),
+            succeeded: receipt.status.map(|status| status == U64::one()),
+            gas_used: receipt.gas_used.map(|gas| format!("{:#x}", gas)),
+            events: receipt
+                .logs
+                .iter()
+                .filter_map(|log| {
+                    Some(ReceiptEvent {
+                        contract: format!("{:#x}", log.address),
+                        log_index: log.log_index.map(|index| format!("{:#x}", index)),
+                        info: decode_log(log)?,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+fn event_topic(signature: &str) -> H256 {
+    H256(keccak256(signature.as_bytes()))
+}
+
+fn topic_address(topic: &H256) -> String {
+    format!("0x{}", hex_encode(&topic.as_bytes()[12..]))
+}
+
+fn topic_uint(topic: &H256) -> U256 {
+    U256::from_big_endian(topic.as_bytes())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decodes `Transfer`, `Approval`, `TransferSingle` and `TransferBatch`
+/// events of ERC-20, ERC-721 and ERC-1155 tokens.
+///
+/// ERC-721 events are told apart from ERC-20 ones by their indexed token ID.
+/// Single transfers from or to the zero address are reported as mints or
+/// burns.
+pub fn decode_log(log: &Log) -> Option<TransactionInfo> {
+    decode_token_log(log).map(TransactionInfo::into_supply_change)
+}
+
+fn decode_token_log(log: &Log) -> Option<TransactionInfo> {
+    let topics = log.topics.as_slice();
+    let data = log.data.0.as_slice();
+    let signature = *topics.first()?;
+
+    if signature == event_topic(TRANSFER_EVENT) {
+        let (amount, token_id) = match topics {
+            [_, _, _] => (word(data)?, None),
+            [_, _, _, token_id] => (U256::one(), Some(topic_uint(token_id))),
+            _ => return None,
+        };
+        Some(TransactionInfo::TokenTransfer {
+            from: topic_address(&topics[1]),
+            to: topic_address(&topics[2]),
+            amount: format!("{:#x}", amount),
+            token_id: token_id.map(|token_id| format!("{:#x}", token_id)),
+            token_info: None,
+            fiat_value: None,
+        })
+    } else if signature == event_topic(APPROVAL_EVENT) {
+        let (amount, is_unlimited) = match topics {
+            [_, _, _] => {
+                let amount = word(data)?;
+                (amount, amount == U256::MAX)
+            }
+            [_, _, _, token_id] => (topic_uint(token_id), false),
+            _ => return None,
+        };
+        Some(TransactionInfo::Approval {
+            owner: topic_address(&topics[1]),
+            spender: topic_address(&topics[2]),
+            amount: Some(format!("{:#x}", amount)),
+            token: None,
+            is_unlimited,
+        })
+    } else if signature == event_topic(TRANSFER_SINGLE_EVENT) {
+        let [_, _, from, to] = <[H256; 4]>::try_from(topics).ok()?;
+        Some(TransactionInfo::TokenTransfer {
+            from: topic_address(&from),
+            to: topic_address(&to),
+            amount: format!("{:#x}", word(data.get(32..)?)?),
+            token_id: Some(format!("{:#x}", word(data)?)),
+            token_info: None,
+            fiat_value: None,
+        })
+    } else if signature == event_topic(TRANSFER_BATCH_EVENT) {
+        let [_, _, from, to] = <[H256; 4]>::try_from(topics).ok()?;
+        let array = ParamType::Array(Box::new(ParamType::Uint(256)));
+        let tokens = ethabi::decode(&[array.clone(), array], data).ok()?;
+        let (ids, amounts) = match tokens.as_slice() {
+            [Token::Array(ids), Token::Array(amounts)] if ids.len() == amounts.len() => {
+                (ids.clone(), amounts.clone())
+            }
+            _ => return None,
+        };
+        let transfers = ids
+            .into_iter()
+            .zip(amounts)
+            .map(|(id, amount)| {
+                Some(TokenTransferInfo {
+                    from: topic_address(&from),
+                    to: topic_address(&to),
+                    amount: format!("{:#x}", amount.into_uint()?),
+                    token_id: Some(format!("{:#x}", id.into_uint()?)),
+                    token_info: None,
+                    fiat_value: None,
+                })
+            })
+            .collect::<Option<_>>()?;
+        Some(TransactionInfo::TokenBatchTransfer { transfers })
+    } else {
+        None
+    }
+}
+
+/// Decodes the first 32 byte word of event data.
+fn word(data: &[u8]) -> Option<U256> {
+    data.get(..32).map(U256::from_big_endian)
+}
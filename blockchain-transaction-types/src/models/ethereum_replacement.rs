@@ -0,0 +1,136 @@
+use web3::types::{TransactionRequest as Web3TransactionRequest, U256};
+
+use crate::models::error::Error;
+
+/// The minimum fee increase in percent for nodes to accept a replacement of
+/// a pending transaction.
+pub const MIN_REPLACEMENT_BUMP_PERCENT: u64 = 10;
+
+/// The gas used by a plain ether transfer.
+const TRANSFER_GAS: u64 = 21_000;
+
+/// How to raise the fees of a replacement transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BumpStrategy {
+    /// Raises every fee by a percentage, which must be at least
+    /// [`MIN_REPLACEMENT_BUMP_PERCENT`].
+    Percent(u64),
+    /// Sets the maximum fee and priority fee per gas, or the gas price of
+    /// legacy transactions to `max_fee_per_gas`.
+    Fees {
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+    },
+}
+
+/// Creates a replacement of a pending transaction with the same nonce and
+/// call, and raised fees.
+pub fn speed_up(
+    original: &Web3TransactionRequest,
+    strategy: BumpStrategy,
+) -> Result<Web3TransactionRequest, Error> {
+    let replacement = bump_fees(original, strategy)?;
+    validate_replacement(original, &replacement)?;
+    Ok(replacement)
+}
+
+/// Creates a replacement of a pending transaction that sends nothing to its
+/// sender, with the same nonce and raised fees.
+pub fn cancel(
+    original: &Web3TransactionRequest,
+    strategy: BumpStrategy,
+) -> Result<Web3TransactionRequest, Error> {
+    let replacement = Web3TransactionRequest {
+        to: Some(original.from),
+        gas: Some(TRANSFER_GAS.into()),
+        value: Some(U256::zero()),
+        data: None,
+        access_list: None,
+        ..bump_fees(original, strategy)?
+    };
+    validate_replacement(original, &replacement)?;
+    Ok(replacement)
+}
+
+/// Checks that `replacement` has the nonce of `original` and raises each of
+/// its fees by at least [`MIN_REPLACEMENT_BUMP_PERCENT`].
+pub fn validate_replacement(
+    original: &Web3TransactionRequest,
+    replacement: &Web3TransactionRequest,
+) -> Result<(), Error> {
+    if original.nonce.is_none() || original.nonce != replacement.nonce {
+        return Err(Error::InvalidData);
+    }
+
+    let fees = [
+        (original.gas_price, replacement.gas_price),
+        (original.max_fee_per_gas, replacement.max_fee_per_gas),
+        (
+            original.max_priority_fee_per_gas,
+            replacement.max_priority_fee_per_gas,
+        ),
+    ];
+    for (original, replacement) in fees {
+        if let Some(original) = original {
+            let minimum = bump(original, MIN_REPLACEMENT_BUMP_PERCENT).ok_or(Error::InvalidData)?;
+            if replacement.unwrap_or_default() < minimum {
+                return Err(Error::InvalidData);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn bump_fees(
+    original: &Web3TransactionRequest,
+    strategy: BumpStrategy,
+) -> Result<Web3TransactionRequest, Error> {
+    if original.nonce.is_none() {
+        return Err(Error::InvalidData);
+    }
+    let is_legacy = original.max_fee_per_gas.is_none();
+    if is_legacy && original.gas_price.is_none() {
+        return Err(Error::InvalidData);
+    }
+
+    let mut replacement = original.clone();
+    match (strategy, is_legacy) {
+        (BumpStrategy::Percent(percent), _) => {
+            if percent < MIN_REPLACEMENT_BUMP_PERCENT {
+                return Err(Error::InvalidData);
+            }
+            let bumped = |fee: Option<U256>| match fee {
+                Some(fee) => bump(fee, percent).map(Some).ok_or(Error::InvalidData),
+                None => Ok(None),
+            };
+            replacement.gas_price = bumped(original.gas_price)?;
+            replacement.max_fee_per_gas = bumped(original.max_fee_per_gas)?;
+            replacement.max_priority_fee_per_gas = bumped(original.max_priority_fee_per_gas)?;
+        }
+        (
+            BumpStrategy::Fees {
+                max_fee_per_gas, ..
+            },
+            true,
+        ) => {
+            replacement.gas_price = Some(max_fee_per_gas);
+        }
+        (
+            BumpStrategy::Fees {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            },
+            false,
+        ) => {
+            replacement.max_fee_per_gas = Some(max_fee_per_gas);
+            replacement.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        }
+    }
+    Ok(replacement)
+}
+
+/// Raises a fee by a percentage, rounding up.
+fn bump(fee: U256, percent: u64) -> Option<U256> {
+    let numerator = fee.checked_mul(U256::from(100 + percent))?;
+    Some((numerator + 99) / 100)
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use web3::types::{Address, Bytes, TransactionRequest as Web3TransactionRequest, U256, U64};
+
+use crate::models::error::{from_json_slice, from_json_value, Error};
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::TransactionInfo;
+
+/// A call of a [`SendCallsRequest`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchCall {
+    pub to: Option<Address>,
+    pub data: Option<Bytes>,
+    pub value: Option<U256>,
+    /// The capabilities of the call, such as whether it is optional.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<Value>,
+}
+
+/// The batch of calls of an [EIP-5792] `wallet_sendCalls` request, which the
+/// wallet sends in one or more transactions.
+///
+/// [EIP-5792]: https://eips.ethereum.org/EIPS/eip-5792
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendCallsRequest {
+    pub version: String,
+    /// The identifier of the batch chosen by the app.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The sender, or `None` to let the wallet choose the account.
+    pub from: Option<Address>,
+    pub chain_id: U64,
+    /// Whether the calls must all be executed in a single transaction.
+    #[serde(default)]
+    pub atomic_required: bool,
+    pub calls: Vec<BatchCall>,
+    /// The capabilities requested from the wallet, such as a paymaster.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<Value>,
+}
+
+impl SendCallsRequest {
+    /// Checks that the batch has at least one call.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.calls.is_empty() {
+            return Err(Error::invalid_field("calls", "at least one call"));
+        }
+        Ok(())
+    }
+
+    /// Gets each call as a transaction request from the sender, leaving the
+    /// gas and fees to be filled in.
+    pub fn transaction_requests(&self) -> Vec<Web3TransactionRequest> {
+        self.calls
+            .iter()
+            .map(|call| Web3TransactionRequest {
+                from: self.from.unwrap_or_default(),
+                to: call.to,
+                value: call.value,
+                data: call.data.clone(),
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
+impl TransactionRequest for SendCallsRequest {
+    /// Parses the first parameter of a `wallet_sendCalls` request.
+    fn from_json(json: Value) -> Result<Self, Error> {
+        let request: Self = from_json_value(json)?;
+        request.validate()?;
+        Ok(request)
+    }
+
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
+        let request: Self = from_json_slice(bytes)?;
+        request.validate()?;
+        Ok(request)
+    }
+
+    /// Classifies each call on its own, as a batch.
+    fn transaction_info(&self) -> TransactionInfo {
+        TransactionInfo::Batch {
+            calls: self
+                .transaction_requests()
+                .iter()
+                .map(|request| request.transaction_info())
+                .collect(),
+        }
+    }
+}
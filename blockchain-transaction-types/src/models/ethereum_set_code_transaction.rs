@@ -0,0 +1,191 @@
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use web3::types::{Address, TransactionRequest as Web3TransactionRequest, H256, U256, U64};
+
+use crate::models::error::{from_json_value, Error};
+use crate::models::ethereum_transaction::{
+    decode_raw_transaction, recover_signer, RawSignature, EIP_7702_TRANSACTION_TYPE,
+};
+#[cfg(feature = "signing")]
+use crate::models::ethereum_transaction::{
+    encode_transaction_request, raw_signature, SigningMode, TypedTransactionFields,
+};
+use crate::models::keccak::keccak256;
+#[cfg(feature = "signing")]
+use crate::models::transaction::SignableTransactionRequest;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::{FeeInfo, TransactionInfo};
+
+/// The prefix of the messages signed by authorities of EIP-7702
+/// authorizations.
+pub const AUTHORIZATION_MAGIC: u8 = 0x05;
+
+/// An [EIP-7702] authorization to set the code of its signer, the
+/// authority, to a delegation to `address`.
+///
+/// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Authorization {
+    /// The chain the authorization is valid on, or zero for all chains.
+    pub chain_id: U256,
+    /// The contract whose code is delegated to.
+    pub address: Address,
+    /// The nonce of the authority.
+    pub nonce: U64,
+    pub y_parity: U64,
+    pub r: U256,
+    pub s: U256,
+}
+
+/// Computes the hash signed by the authority of an authorization, which is
+/// `keccak256(MAGIC || rlp([chain_id, address, nonce]))`.
+pub fn authorization_signing_hash(chain_id: U256, address: Address, nonce: U64) -> H256 {
+    let mut rlp = RlpStream::new_list(3);
+    rlp.append(&chain_id);
+    rlp.append(&address);
+    rlp.append(&nonce);
+
+    let mut message = vec![AUTHORIZATION_MAGIC];
+    message.extend_from_slice(rlp.as_raw());
+    H256(keccak256(&message))
+}
+
+impl Authorization {
+    /// Computes the hash signed by the authority.
+    pub fn signing_hash(&self) -> H256 {
+        authorization_signing_hash(self.chain_id, self.address, self.nonce)
+    }
+
+    /// Recovers the authority that signed the authorization.
+    pub fn authority(&self) -> Result<Address, Error> {
+        let signature = RawSignature {
+            v: self.y_parity.as_u64(),
+            r: self.r,
+            s: self.s,
+        };
+        recover_signer(self.signing_hash().as_bytes(), signature.v, &signature)
+    }
+}
+
+impl Encodable for Authorization {
+    fn rlp_append(&self, rlp: &mut RlpStream) {
+        rlp.begin_list(6);
+        rlp.append(&self.chain_id);
+        rlp.append(&self.address);
+        rlp.append(&self.nonce);
+        rlp.append(&self.y_parity);
+        rlp.append(&self.r);
+        rlp.append(&self.s);
+    }
+}
+
+impl Decodable for Authorization {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 6 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            address: rlp.val_at(1)?,
+            nonce: rlp.val_at(2)?,
+            y_parity: rlp.val_at(3)?,
+            r: rlp.val_at(4)?,
+            s: rlp.val_at(5)?,
+        })
+    }
+}
+
+/// An [EIP-7702] set code transaction request, which sets the code of the
+/// authorities of its authorizations.
+///
+/// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SetCodeTransactionRequest {
+    #[serde(flatten)]
+    pub request: Web3TransactionRequest,
+    #[serde(rename = "authorizationList")]
+    pub authorization_list: Vec<Authorization>,
+}
+
+impl SetCodeTransactionRequest {
+    /// Checks that the request is typed as a set code transaction with a
+    /// recipient and at least one authorization.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.request.transaction_type == Some(EIP_7702_TRANSACTION_TYPE.into())
+            && self.request.to.is_some()
+            && !self.authorization_list.is_empty()
+        {
+            Ok(())
+        } else {
+            Err(Error::InvalidData)
+        }
+    }
+}
+
+impl TransactionRequest for SetCodeTransactionRequest {
+    fn from_json(json: Value) -> Result<Self, Error> {
+        let request: Self = from_json_value(json)?;
+        request.validate()?;
+        Ok(request)
+    }
+
+    /// Decodes a signed raw set code transaction or the unsigned payload
+    /// signed by its sender.
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
+        let raw = decode_raw_transaction(bytes)?;
+        Ok(Self {
+            request: raw.request,
+            authorization_list: raw.authorization_list.ok_or(Error::InvalidData)?,
+        })
+    }
+
+    fn transaction_info(&self) -> TransactionInfo {
+        self.request.transaction_info()
+    }
+
+    fn fee_info(&self) -> Option<FeeInfo> {
+        self.request.fee_info()
+    }
+}
+
+#[cfg(feature = "signing")]
+impl SignableTransactionRequest for SetCodeTransactionRequest {
+    fn message_hash(&self, chain_id: u64) -> Result<Vec<u8>, Error> {
+        self.validate()?;
+        let message = encode_transaction_request(
+            &self.request,
+            SigningMode::Eip155(chain_id),
+            None,
+            Some(TypedTransactionFields::SetCode(&self.authorization_list)),
+        )?;
+        Ok(keccak256(&message).to_vec())
+    }
+
+    fn encode_signed(
+        &self,
+        chain_id: u64,
+        signature: &[u8],
+        recovery_id: u64,
+    ) -> Result<Vec<u8>, Error> {
+        self.validate()?;
+        let signature = raw_signature(
+            &self.request,
+            SigningMode::Eip155(chain_id),
+            signature,
+            recovery_id,
+        )?;
+        encode_transaction_request(
+            &self.request,
+            SigningMode::Eip155(chain_id),
+            Some(&signature),
+            Some(TypedTransactionFields::SetCode(&self.authorization_list)),
+        )
+    }
+
+    fn transaction_hash(&self, _chain_id: u64, raw: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(keccak256(raw).to_vec())
+    }
+}
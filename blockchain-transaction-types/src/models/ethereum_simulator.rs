@@ -0,0 +1,369 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use web3::types::{Address, Log, TransactionRequest as Web3TransactionRequest, U256};
+use web3::{helpers, Transport, Web3};
+
+use crate::models::error::{from_json_value, Error};
+use crate::models::ethereum_receipt::{decode_log, ReceiptEvent};
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::{TokenTransferInfo, TransactionInfo};
+
+/// A change of the balance of an account in one token.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    /// The token contract, or `None` for ether.
+    pub token: Option<String>,
+    pub token_id: Option<String>,
+    /// Whether the account receives the amount, rather than sends it.
+    pub received: bool,
+    pub amount: String,
+}
+
+/// The predicted effects of a transaction, before it is signed.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedEffects {
+    pub succeeded: bool,
+    /// The revert reason or error of a failed simulation.
+    pub error: Option<String>,
+    pub gas_used: Option<String>,
+    /// The ether sent by the transaction and its internal calls.
+    pub transfers: Vec<TokenTransferInfo>,
+    /// The token events emitted by the transaction.
+    pub events: Vec<ReceiptEvent>,
+}
+
+impl SimulatedEffects {
+    /// Classifies the effects, as the single ether transfer or token event, or
+    /// a batch of them.
+    pub fn transaction_info(&self) -> TransactionInfo {
+        let mut calls: Vec<TransactionInfo> = self
+            .transfers
+            .iter()
+            .map(|transfer| TransactionInfo::TokenTransfer {
+                from: transfer.from.clone(),
+                to: transfer.to.clone(),
+                amount: transfer.amount.clone(),
+                token_id: None,
+                token_info: None,
+                fiat_value: None,
+            })
+            .collect();
+        calls.extend(self.events.iter().map(|event| event.info.clone()));
+        match calls.len() {
+            1 => calls.remove(0),
+            _ => TransactionInfo::Batch { calls },
+        }
+    }
+
+    /// Gets what `account` sends and receives, netted per token, in the order
+    /// of the token addresses with ether first.
+    ///
+    /// Approvals don't change balances and are skipped.
+    pub fn balance_changes(&self, account: &Address) -> Vec<BalanceChange> {
+        let account = format!("{:#x}", account);
+        let mut balances: BTreeMap<(Option<String>, Option<String>), (U256, U256)> =
+            BTreeMap::new();
+        let mut add = |token: Option<&String>, transfer: &TokenTransferInfo| {
+            let amount = match U256::from_str_radix(transfer.amount.trim_start_matches("0x"), 16) {
+                Ok(amount) => amount,
+                Err(_) => return,
+            };
+            let key = (token.cloned(), transfer.token_id.clone());
+            let (received, sent) = balances.entry(key).or_default();
+            if transfer.to.eq_ignore_ascii_case(&account) {
+                *received += amount;
+            }
+            if transfer.from.eq_ignore_ascii_case(&account) {
+                *sent += amount;
+            }
+        };
+
+        for transfer in &self.transfers {
+            add(None, transfer);
+        }
+        for event in &self.events {
+            match &event.info {
+                TransactionInfo::TokenTransfer {
+                    from,
+                    to,
+                    amount,
+                    token_id,
+                    ..
+                } => add(
+                    Some(&event.contract),
+                    &TokenTransferInfo {
+                        from: from.clone(),
+                        to: to.clone(),
+                        amount: amount.clone(),
+                        token_id: token_id.clone(),
+                        token_info: None,
+                        fiat_value: None,
+                    },
+                ),
+                TransactionInfo::TokenBatchTransfer { transfers }
+                | TransactionInfo::TokenBatchMint { transfers }
+                | TransactionInfo::TokenBatchBurn { transfers } => {
+                    for transfer in transfers {
+                        add(Some(&event.contract), transfer);
+                    }
+                }
+                TransactionInfo::TokenMint {
+                    to,
+                    amount,
+                    token_id,
+                    ..
+                } => add(
+                    Some(&event.contract),
+                    &TokenTransferInfo {
+                        from: format!("{:#x}", Address::zero()),
+                        to: to.clone(),
+                        amount: amount.clone(),
+                        token_id: token_id.clone(),
+                        token_info: None,
+                        fiat_value: None,
+                    },
+                ),
+                TransactionInfo::TokenBurn {
+                    from,
+                    amount,
+                    token_id,
+                    ..
+                } => add(
+                    Some(&event.contract),
+                    &TokenTransferInfo {
+                        from: from.clone(),
+                        to: format!("{:#x}", Address::zero()),
+                        amount: amount.clone(),
+                        token_id: token_id.clone(),
+                        token_info: None,
+                        fiat_value: None,
+                    },
+                ),
+                _ => {}
+            }
+        }
+
+        balances
+            .into_iter()
+            .filter(|(_, (received, sent))| received != sent)
+            .map(|((token, token_id), (received, sent))| BalanceChange {
+                token,
+                token_id,
+                received: received > sent,
+                amount: format!(
+                    "{:#x}",
+                    if received > sent {
+                        received - sent
+                    } else {
+                        sent - received
+                    }
+                ),
+            })
+            .collect()
+    }
+}
+
+/// Simulates transactions against the latest block, to preview their effects
+/// before signing.
+#[async_trait]
+pub trait Simulator {
+    /// Predicts the effects of `request`.
+    ///
+    /// Reverting transactions aren't errors, but effects that didn't succeed.
+    async fn simulate(&self, request: &Web3TransactionRequest) -> Result<SimulatedEffects, Error>;
+}
+
+/// Simulates transactions with `eth_call`.
+///
+/// `eth_call` only tells whether the transaction reverts, so the effects are
+/// the ether value and the token transfer or approval the request is
+/// classified as. Token transfers made by other contract calls are missed.
+#[derive(Clone, Debug)]
+pub struct Web3CallSimulator<T: Transport> {
+    web3: Web3<T>,
+}
+
+impl<T: Transport> Web3CallSimulator<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            web3: Web3::new(transport),
+        }
+    }
+}
+
+#[async_trait]
+impl<T> Simulator for Web3CallSimulator<T>
+where
+    T: Transport + Send + Sync,
+    T::Out: Send,
+{
+    async fn simulate(&self, request: &Web3TransactionRequest) -> Result<SimulatedEffects, Error> {
+        let params = vec![helpers::serialize(request), helpers::serialize(&"latest")];
+        let error = match self.web3.transport().execute("eth_call", params).await {
+            Ok(_) => None,
+            Err(web3::Error::Rpc(error)) => Some(error.message),
+            Err(error) => return Err(error.into()),
+        };
+        if error.is_some() {
+            return Ok(SimulatedEffects {
+                succeeded: false,
+                error,
+                gas_used: None,
+                transfers: vec![],
+                events: vec![],
+            });
+        }
+
+        let from = format!("{:#x}", request.from);
+        let transfers = match (request.to, request.value) {
+            (Some(to), Some(value)) if !value.is_zero() => vec![TokenTransferInfo {
+                from,
+                to: format!("{:#x}", to),
+                amount: format!("{:#x}", value),
+                token_id: None,
+                token_info: None,
+                fiat_value: None,
+            }],
+            _ => vec![],
+        };
+        let has_data = request
+            .data
+            .as_ref()
+            .map(|data| !data.0.is_empty())
+            .unwrap_or_default();
+        let events = match (request.to, request.transaction_info()) {
+            (Some(contract), info) if has_data && is_token_event(&info) => vec![ReceiptEvent {
+                contract: format!("{:#x}", contract),
+                log_index: None,
+                info,
+            }],
+            _ => vec![],
+        };
+
+        Ok(SimulatedEffects {
+            succeeded: true,
+            error: None,
+            gas_used: None,
+            transfers,
+            events,
+        })
+    }
+}
+
+fn is_token_event(info: &TransactionInfo) -> bool {
+    matches!(
+        info,
+        TransactionInfo::TokenTransfer { .. }
+            | TransactionInfo::TokenBatchTransfer { .. }
+            | TransactionInfo::TokenMint { .. }
+            | TransactionInfo::TokenBurn { .. }
+            | TransactionInfo::TokenBatchMint { .. }
+            | TransactionInfo::TokenBatchBurn { .. }
+            | TransactionInfo::Approval { token: None, .. }
+    )
+}
+
+/// Simulates transactions with the `callTracer` of `debug_traceCall`, which
+/// reports the ether sent and the events emitted by every internal call.
+///
+/// Calls that revert are skipped along with their internal calls and events.
+#[derive(Clone, Debug)]
+pub struct Web3TraceSimulator<T: Transport> {
+    web3: Web3<T>,
+}
+
+impl<T: Transport> Web3TraceSimulator<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            web3: Web3::new(transport),
+        }
+    }
+}
+
+#[async_trait]
+impl<T> Simulator for Web3TraceSimulator<T>
+where
+    T: Transport + Send + Sync,
+    T::Out: Send,
+{
+    async fn simulate(&self, request: &Web3TransactionRequest) -> Result<SimulatedEffects, Error> {
+        let params = vec![
+            helpers::serialize(request),
+            helpers::serialize(&"latest"),
+            serde_json::json!({
+                "tracer": "callTracer",
+                "tracerConfig": { "withLog": true },
+            }),
+        ];
+        let result = self
+            .web3
+            .transport()
+            .execute("debug_traceCall", params)
+            .await?;
+        let frame: CallFrame = from_json_value(result)?;
+
+        let mut effects = SimulatedEffects {
+            succeeded: frame.error.is_none(),
+            error: frame.revert_reason.clone().or_else(|| frame.error.clone()),
+            gas_used: frame.gas_used.map(|gas| format!("{:#x}", gas)),
+            transfers: vec![],
+            events: vec![],
+        };
+        frame.collect(&mut effects);
+        Ok(effects)
+    }
+}
+
+/// A call of a `callTracer` trace.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CallFrame {
+    #[serde(rename = "type")]
+    kind: String,
+    from: Address,
+    to: Option<Address>,
+    value: Option<U256>,
+    gas_used: Option<U256>,
+    error: Option<String>,
+    revert_reason: Option<String>,
+    #[serde(default)]
+    calls: Vec<CallFrame>,
+    #[serde(default)]
+    logs: Vec<Log>,
+}
+
+impl CallFrame {
+    fn collect(&self, effects: &mut SimulatedEffects) {
+        if self.error.is_some() {
+            return;
+        }
+        let sends_value = !matches!(self.kind.as_str(), "DELEGATECALL" | "STATICCALL");
+        match (self.to, self.value) {
+            (Some(to), Some(value)) if sends_value && !value.is_zero() => {
+                effects.transfers.push(TokenTransferInfo {
+                    from: format!("{:#x}", self.from),
+                    to: format!("{:#x}", to),
+                    amount: format!("{:#x}", value),
+                    token_id: None,
+                    token_info: None,
+                    fiat_value: None,
+                })
+            }
+            _ => {}
+        }
+        effects.events.extend(self.logs.iter().filter_map(|log| {
+            Some(ReceiptEvent {
+                contract: format!("{:#x}", log.address),
+                log_index: None,
+                info: decode_log(log)?,
+            })
+        }));
+        for call in &self.calls {
+            call.collect(effects);
+        }
+    }
+}
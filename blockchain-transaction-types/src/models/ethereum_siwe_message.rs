@@ -0,0 +1,256 @@
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde_json::Value;
+use web3::types::Address;
+
+use crate::models::error::Error;
+use crate::models::ethereum_message::hash_personal_message;
+use crate::models::message::{MessageInfo, SignableMessage};
+
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+
+/// A [Sign-In with Ethereum] message.
+///
+/// The message is signed with `personal_sign`, so its hash is the EIP-191
+/// hash of the original text.
+///
+/// [Sign-In with Ethereum]: https://eips.ethereum.org/EIPS/eip-4361
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SiweMessage {
+    pub scheme: Option<String>,
+    pub domain: String,
+    pub address: Address,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: String,
+    pub expiration_time: Option<String>,
+    pub not_before: Option<String>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+    #[serde(skip)]
+    message: String,
+}
+
+impl SiweMessage {
+    /// Gets the original message text.
+    pub fn as_str(&self) -> &str {
+        &self.message
+    }
+
+    /// Checks that the message was issued by `domain` with `nonce`, and that
+    /// it is valid at `now`.
+    pub fn validate(&self, domain: &str, nonce: &str, now: SystemTime) -> Result<(), Error> {
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| Error::InvalidData)?
+            .as_secs() as i64;
+        let expired = match &self.expiration_time {
+            Some(time) => parse_timestamp(time)? <= now,
+            None => false,
+        };
+        let premature = match &self.not_before {
+            Some(time) => parse_timestamp(time)? > now,
+            None => false,
+        };
+
+        if self.domain != domain || self.nonce != nonce || expired || premature {
+            return Err(Error::InvalidData);
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = Error;
+
+    fn from_str(message: &str) -> Result<Self, Error> {
+        let mut lines = message.split('\n').peekable();
+
+        let origin = lines
+            .next()
+            .and_then(|line| line.strip_suffix(PREAMBLE))
+            .ok_or(Error::InvalidData)?;
+        let (scheme, domain) = match origin.split_once("://") {
+            Some((scheme, domain)) => (Some(scheme.to_owned()), domain.to_owned()),
+            None => (None, origin.to_owned()),
+        };
+        let address = lines
+            .next()
+            .and_then(|line| line.strip_prefix("0x"))
+            .and_then(|address| address.parse().ok())
+            .ok_or(Error::InvalidData)?;
+
+        if lines.next() != Some("") {
+            return Err(Error::InvalidData);
+        }
+        let statement = match lines.peek() {
+            Some(&"") => None,
+            Some(statement) => {
+                let statement = statement.to_string();
+                lines.next();
+                Some(statement)
+            }
+            None => return Err(Error::InvalidData),
+        };
+        if lines.next() != Some("") {
+            return Err(Error::InvalidData);
+        }
+
+        let mut field = |name: &str, required: bool| -> Result<Option<String>, Error> {
+            let prefix = format!("{}: ", name);
+            match lines.peek().and_then(|line| line.strip_prefix(&prefix)) {
+                Some(value) => {
+                    let value = value.to_owned();
+                    lines.next();
+                    Ok(Some(value))
+                }
+                None if required => Err(Error::invalid_field(name, "a value")),
+                None => Ok(None),
+            }
+        };
+        let uri = field("URI", true)?.unwrap_or_default();
+        let version = field("Version", true)?.unwrap_or_default();
+        let chain_id = field("Chain ID", true)?
+            .and_then(|chain_id| chain_id.parse().ok())
+            .ok_or_else(|| Error::invalid_field("Chain ID", "an integer"))?;
+        let nonce = field("Nonce", true)?.unwrap_or_default();
+        let issued_at = field("Issued At", true)?.unwrap_or_default();
+        let expiration_time = field("Expiration Time", false)?;
+        let not_before = field("Not Before", false)?;
+        let request_id = field("Request ID", false)?;
+
+        let mut resources = Vec::new();
+        if lines.peek() == Some(&"Resources:") {
+            lines.next();
+            for line in lines.by_ref() {
+                let resource = line.strip_prefix("- ").ok_or(Error::InvalidData)?;
+                resources.push(resource.to_owned());
+            }
+        }
+
+        let valid_nonce = nonce.len() >= 8 && nonce.chars().all(|c| c.is_ascii_alphanumeric());
+        let valid_times = std::iter::once(&issued_at)
+            .chain(&expiration_time)
+            .chain(&not_before)
+            .all(|time| parse_timestamp(time).is_ok());
+        if lines.next().is_some() || version != "1" || !valid_nonce || !valid_times {
+            return Err(Error::InvalidData);
+        }
+
+        Ok(SiweMessage {
+            scheme,
+            domain,
+            address,
+            statement,
+            uri,
+            version,
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+            message: message.to_owned(),
+        })
+    }
+}
+
+/// Parses an RFC 3339 timestamp, such as `2021-12-07T18:28:18.807Z`, to Unix
+/// seconds.
+fn parse_timestamp(timestamp: &str) -> Result<i64, Error> {
+    let number = |range: std::ops::Range<usize>| -> Result<i64, Error> {
+        let digits = timestamp.get(range).ok_or(Error::InvalidData)?;
+        if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(Error::InvalidData);
+        }
+        digits.parse().map_err(|_| Error::InvalidData)
+    };
+    let separators = [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')];
+    let bytes = timestamp.as_bytes();
+    if bytes.len() < 20
+        || !matches!(bytes[10], b'T' | b't')
+        || separators.iter().any(|&(i, byte)| bytes[i] != byte)
+    {
+        return Err(Error::InvalidData);
+    }
+
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 {
+        return Err(Error::InvalidData);
+    }
+
+    let mut offset = &timestamp[19..];
+    if let Some(fraction) = offset.strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return Err(Error::InvalidData);
+        }
+        offset = &fraction[digits..];
+    }
+    let offset = match offset {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match offset.get(..1) {
+                Some("+") => 1,
+                Some("-") => -1,
+                _ => return Err(Error::InvalidData),
+            };
+            let (hours, minutes) = offset[1..].split_once(':').ok_or(Error::InvalidData)?;
+            let hours: i64 = hours.parse().map_err(|_| Error::InvalidData)?;
+            let minutes: i64 = minutes.parse().map_err(|_| Error::InvalidData)?;
+            sign * (hours * 3600 + minutes * 60)
+        }
+    };
+
+    // Days since the epoch of the proleptic Gregorian calendar date.
+    let (year, month) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    Ok(days * 86_400 + hour * 3600 + minute * 60 + second.min(59) - offset)
+}
+
+impl crate::models::message::Message for SiweMessage {
+    fn from_json(json: Value) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        json.as_str().ok_or(Error::InvalidData)?.parse()
+    }
+
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        std::str::from_utf8(bytes)
+            .map_err(|_| Error::InvalidData)?
+            .parse()
+    }
+
+    fn message_info(&self) -> MessageInfo {
+        MessageInfo::Json(serde_json::to_value(self).unwrap_or_default())
+    }
+}
+
+impl SignableMessage for SiweMessage {
+    fn message_hash(&self, _chain_id: u64) -> Result<Vec<u8>, Error> {
+        Ok(hash_personal_message(self.message.as_bytes())
+            .as_bytes()
+            .to_vec())
+    }
+}
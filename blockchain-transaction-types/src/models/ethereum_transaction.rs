@@ -1,34 +1,99 @@
-#[cfg(feature = "signing")]
-use rlp::RlpStream;
+use std::cell::Cell;
+
+use rlp::{Rlp, RlpStream};
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, Secp256k1};
 use serde_json::Value;
 use web3::types::{
-    Address, Transaction as Web3Transaction, TransactionParameters as Web3TransactionParameters,
-    TransactionRequest as Web3TransactionRequest, U256,
+    AccessList, AccessListItem, Address, Bytes, Transaction as Web3Transaction,
+    TransactionParameters as Web3TransactionParameters,
+    TransactionRequest as Web3TransactionRequest, H256, U256, U64,
 };
 
-use crate::models::error::Error;
+use crate::models::account::Account;
+use crate::models::error::{from_json_value, Error};
+use crate::models::ethereum_set_code_transaction::Authorization;
+use crate::models::keccak::keccak256;
 #[cfg(feature = "signing")]
 use crate::models::transaction::SignableTransactionRequest;
 use crate::models::transaction::{IdentifyableTransction, Transaction, TransactionRequest};
-use crate::models::transaction_info::TransactionInfo;
+use crate::models::transaction_info::{
+    is_zero_address, FeeInfo, TokenTransferInfo, TransactionInfo,
+};
 
-#[cfg(feature = "signing")]
 const EIP_1559_TRANSACTION_TYPE: u64 = 2;
-#[cfg(feature = "signing")]
 const EIP_2930_TRANSACTION_TYPE: u64 = 1;
+pub(crate) const EIP_4844_TRANSACTION_TYPE: u64 = 3;
+pub(crate) const EIP_7702_TRANSACTION_TYPE: u64 = 4;
+/// The `v` of legacy signatures without replay protection, plus the
+/// recovery ID.
+const LEGACY_V_OFFSET: u64 = 27;
+/// The `v` of [EIP-155] signatures, plus twice the chain ID and the recovery
+/// ID.
+///
+/// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+const EIP_155_V_OFFSET: u64 = 35;
 const METHOD_LENGTH: usize = 10;
 
+/// How a transaction request is protected against replay on other chains.
+#[cfg(feature = "signing")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SigningMode {
+    /// Signed for a chain ID, as defined in [EIP-155] for legacy
+    /// transactions and required by typed transactions.
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    Eip155(u64),
+    /// Signed without a chain ID, with a `v` of 27 or 28. Only legacy
+    /// transactions can be signed this way, for chains and tools predating
+    /// EIP-155.
+    PreEip155,
+}
+
+#[cfg(feature = "signing")]
+impl SigningMode {
+    fn chain_id(&self) -> Option<u64> {
+        match self {
+            SigningMode::Eip155(chain_id) => Some(*chain_id),
+            SigningMode::PreEip155 => None,
+        }
+    }
+}
+
 impl Transaction for Web3Transaction {
     type Account = Address;
 
     fn from_json(json: Value) -> Result<Self, Error> {
-        let transaction = serde_json::from_value(json)?;
+        let transaction = from_json_value(json)?;
         Ok(transaction)
     }
 
+    /// Decodes a signed raw transaction, recovering its sender.
     fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
-        let transaction = serde_json::from_slice(bytes)?;
-        Ok(transaction)
+        let RawTransaction {
+            request, signature, ..
+        } = decode_raw_transaction(bytes)?;
+        let signature = signature.ok_or(Error::InvalidData)?;
+
+        Ok(Web3Transaction {
+            hash: H256(keccak256(bytes)),
+            nonce: request.nonce.unwrap_or_default(),
+            from: Some(request.from),
+            to: request.to,
+            value: request.value.unwrap_or_default(),
+            gas_price: request.gas_price,
+            gas: request.gas.unwrap_or_default(),
+            input: request.data.unwrap_or_default(),
+            v: Some(signature.v.into()),
+            r: Some(signature.r),
+            s: Some(signature.s),
+            raw: Some(bytes.to_vec().into()),
+            transaction_type: request.transaction_type,
+            access_list: request.access_list,
+            max_fee_per_gas: request.max_fee_per_gas,
+            max_priority_fee_per_gas: request.max_priority_fee_per_gas,
+            ..Default::default()
+        })
     }
 
     fn hash(&self) -> Vec<u8> {
@@ -57,53 +122,755 @@ fn safe_transfer_from_transaction_info(data: &str) -> TransactionInfo {
         amount: format!("0x{}", value),
         token_id: Some(format!("0x{}", id)),
         token_info: None,
+        fiat_value: None,
     }
 }
 
 const SAFE_TRANSFER_FROM: &str = "0xf242432a";
+const ERC20_TRANSFER: &str = "0xa9059cbb";
+const ERC20_TRANSFER_FROM: &str = "0x23b872dd";
+const ERC20_APPROVE: &str = "0x095ea7b3";
+const ERC20_PERMIT: &str = "0xd505accf";
+const ERC1155_SAFE_BATCH_TRANSFER_FROM: &str = "0x2eb2c2d6";
+const ERC721_SAFE_TRANSFER_FROM: &str = "0x42842e0e";
+const ERC721_SAFE_TRANSFER_FROM_WITH_DATA: &str = "0xb88d4fde";
+const SET_APPROVAL_FOR_ALL: &str = "0xa22cb465";
+const PERMIT2_APPROVE: &str = "0x87517c45";
+const SAFE_EXEC_TRANSACTION: &str = "0x6a761202";
+const MULTICALL_AGGREGATE: &str = "0x252dba42";
+const MULTICALL_TRY_AGGREGATE: &str = "0xbce38bd7";
+const MULTICALL_AGGREGATE3: &str = "0x82ad56cb";
+const MULTICALL: &str = "0xac9650d8";
+const MULTICALL_WITH_DEADLINE: &str = "0x5ae401dc";
+const V2_SWAP_EXACT_TOKENS_FOR_TOKENS: &str = "0x38ed1739";
+const V2_SWAP_EXACT_TOKENS_FOR_TOKENS_FEE_ON_TRANSFER: &str = "0x5c11d795";
+const V2_SWAP_EXACT_TOKENS_FOR_ETH: &str = "0x18cbafe5";
+const V2_SWAP_EXACT_TOKENS_FOR_ETH_FEE_ON_TRANSFER: &str = "0x791ac947";
+const V2_SWAP_EXACT_ETH_FOR_TOKENS: &str = "0x7ff36ab5";
+const V2_SWAP_EXACT_ETH_FOR_TOKENS_FEE_ON_TRANSFER: &str = "0xb6f9de95";
+const ROUTER02_SWAP_EXACT_TOKENS_FOR_TOKENS: &str = "0x472b43f3";
+const V3_EXACT_INPUT_SINGLE: &str = "0x414bf389";
+const V3_EXACT_INPUT: &str = "0xc04b8d59";
+const ROUTER02_EXACT_INPUT_SINGLE: &str = "0x04e45aaf";
+const ROUTER02_EXACT_INPUT: &str = "0xb858183f";
+const UNIVERSAL_ROUTER_EXECUTE: &str = "0x24856bc3";
+const UNIVERSAL_ROUTER_EXECUTE_WITH_DEADLINE: &str = "0x3593564c";
+const UNIVERSAL_ROUTER_COMMAND_MASK: u8 = 0x3f;
+const UNIVERSAL_ROUTER_V3_SWAP_EXACT_IN: u8 = 0x00;
+const UNIVERSAL_ROUTER_V2_SWAP_EXACT_IN: u8 = 0x08;
+const ADDRESS_LENGTH: usize = 20;
+/// The length of a fee and token address in a Uniswap V3 path.
+const V3_PATH_HOP_LENGTH: usize = 23;
+const WETH_DEPOSIT: &str = "0xd0e30db0";
+const WETH_WITHDRAW: &str = "0x2e1a7d4d";
+const WORD_LENGTH: usize = 64;
+const SAFE_TRANSFER_FROM_LENGTH: usize = METHOD_LENGTH + 4 * WORD_LENGTH;
+/// The deepest nesting of multicall and Safe calls that is decoded.
+const MAX_CALL_DEPTH: usize = 4;
+/// The most nested calls that are decoded for a single transaction.
+const MAX_NESTED_CALLS: usize = 256;
+
+/// Limits the nested calls decoded from multicall and Safe transactions,
+/// which would otherwise let crafted calldata nest or repeat calls without
+/// bound.
+#[derive(Clone, Copy)]
+struct CallBudget<'a> {
+    depth: usize,
+    calls: &'a Cell<usize>,
+}
+
+impl<'a> CallBudget<'a> {
+    fn new(calls: &'a Cell<usize>) -> Self {
+        calls.set(MAX_NESTED_CALLS);
+        Self {
+            depth: MAX_CALL_DEPTH,
+            calls,
+        }
+    }
+
+    /// Takes a nested call from the budget, or `None` once it is used up.
+    fn nested(self) -> Option<Self> {
+        let depth = self.depth.checked_sub(1)?;
+        let calls = self.calls.get().checked_sub(1)?;
+        self.calls.set(calls);
+        Some(Self {
+            depth,
+            calls: self.calls,
+        })
+    }
+}
+
+/// The canonical wrapped native token of each chain, by chain ID.
+const WRAPPED_NATIVE_TOKENS: [(u64, &str); 8] = [
+    (1, "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+    (5, "0xb4fbf271143f4fbf7b91a5ded31805e42b2208d6"),
+    (10, "0x4200000000000000000000000000000000000006"),
+    (137, "0x0d500b1d8e8ef31e21c99d1db9a6444d3adf1270"),
+    (8453, "0x4200000000000000000000000000000000000006"),
+    (42161, "0x82af49447d8a07e3bd95bd0d56f35241523fbab1"),
+    (80001, "0x9c3c9283d3e44854697cd22d3faa240cfb032889"),
+    (11155111, "0xfff9976782d46cc05630d1f6ebab18b2324d6b14"),
+];
+
+/// Gets the canonical wrapped native token contract of a chain, such as WETH
+/// on Ethereum mainnet.
+pub fn wrapped_native_token(chain_id: u64) -> Option<Address> {
+    WRAPPED_NATIVE_TOKENS
+        .iter()
+        .find(|(id, _)| *id == chain_id)
+        .and_then(|(_, token)| token.parse().ok())
+}
+
+/// Gets the hex encoded 32 byte ABI parameter at `index` of `input`.
+fn parameter(input: &str, index: usize) -> Option<&str> {
+    let start = METHOD_LENGTH + index * WORD_LENGTH;
+    input.get(start..start + WORD_LENGTH)
+}
+
+/// Decodes an `address` ABI parameter.
+fn address_parameter(input: &str, index: usize) -> Option<String> {
+    let word = parameter(input, index)?;
+    word.get(24..).map(|address| format!("0x{}", address))
+}
+
+/// Decodes a `uint256` ABI parameter.
+fn uint_value_parameter(input: &str, index: usize) -> Option<U256> {
+    U256::from_str_radix(parameter(input, index)?, 16).ok()
+}
+
+/// Decodes a `uint256` ABI parameter as a hex quantity.
+fn uint_parameter(input: &str, index: usize) -> Option<String> {
+    uint_value_parameter(input, index).map(|value| format!("{:#x}", value))
+}
+
+/// Decodes a `uint256` ABI parameter that must fit in a `usize`, such as an
+/// offset or length.
+fn usize_parameter(input: &str, index: usize) -> Option<usize> {
+    let value = uint_value_parameter(input, index)?;
+    if value > U256::from(u32::MAX) {
+        return None;
+    }
+    Some(value.as_usize())
+}
+
+/// Gets the word index of a dynamic ABI value from the offset at `index`,
+/// which is relative to the encoding starting at word `base`.
+fn dynamic_parameter(input: &str, base: usize, index: usize) -> Option<usize> {
+    let offset = usize_parameter(input, base + index)?;
+    if offset % (WORD_LENGTH / 2) != 0 {
+        return None;
+    }
+    Some(base + offset / (WORD_LENGTH / 2))
+}
+
+/// Decodes the `bytes` ABI value starting at word `start`.
+fn bytes_value(input: &str, start: usize) -> Option<Vec<u8>> {
+    let len = usize_parameter(input, start)?;
+    let data_start = METHOD_LENGTH + (start + 1) * WORD_LENGTH;
+    let data = input.get(data_start..data_start + 2 * len)?;
+    (0..len)
+        .map(|i| u8::from_str_radix(data.get(2 * i..2 * i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Decodes a `bytes` ABI parameter.
+fn bytes_parameter(input: &str, index: usize) -> Option<Vec<u8>> {
+    bytes_value(input, dynamic_parameter(input, 0, index)?)
+}
+
+/// Decodes the elements of a dynamic array ABI parameter with `element`,
+/// which is given the word index where the array content starts and the
+/// element index.
+fn array_parameter<T>(
+    input: &str,
+    index: usize,
+    element: impl Fn(usize, usize) -> Option<T>,
+) -> Option<Vec<T>> {
+    let start = dynamic_parameter(input, 0, index)?;
+    let len = usize_parameter(input, start)?;
+    (0..len).map(|i| element(start + 1, i)).collect()
+}
+
+/// Decodes a `uint256[]` ABI parameter as hex quantities.
+fn uint_array_parameter(input: &str, index: usize) -> Option<Vec<String>> {
+    array_parameter(input, index, |base, i| uint_parameter(input, base + i))
+}
+
+/// Gets the word indices of the elements of an array of dynamic ABI values,
+/// such as `bytes[]`.
+///
+/// The ABI encoder lays out elements in order, so offsets that are not
+/// strictly increasing are rejected. Aliased elements would otherwise decode
+/// the same nested calls repeatedly.
+fn dynamic_array_parameter(input: &str, index: usize) -> Option<Vec<usize>> {
+    let offsets = array_parameter(input, index, |base, i| dynamic_parameter(input, base, i))?;
+    if offsets.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return None;
+    }
+    Some(offsets)
+}
+
+/// Decodes a `bytes[]` ABI parameter.
+fn bytes_array_parameter(input: &str, index: usize) -> Option<Vec<Vec<u8>>> {
+    dynamic_array_parameter(input, index)?
+        .into_iter()
+        .map(|start| bytes_value(input, start))
+        .collect()
+}
+
+/// Decodes ERC-1155 `safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)`.
+fn safe_batch_transfer_from_transaction_info(input: &str) -> Option<TransactionInfo> {
+    let from = address_parameter(input, 0)?;
+    let to = address_parameter(input, 1)?;
+    let ids = uint_array_parameter(input, 2)?;
+    let amounts = uint_array_parameter(input, 3)?;
+    if ids.len() != amounts.len() {
+        return None;
+    }
+
+    let transfers = ids
+        .into_iter()
+        .zip(amounts)
+        .map(|(id, amount)| TokenTransferInfo {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+            token_id: Some(id),
+            token_info: None,
+            fiat_value: None,
+        })
+        .collect();
+    Some(TransactionInfo::TokenBatchTransfer { transfers })
+}
+
+/// Decodes a Safe `execTransaction` call on `safe`, classifying the executed
+/// call as if it was sent by the Safe within `budget`.
+fn safe_transaction_info(
+    safe: Option<Address>,
+    input: &str,
+    budget: CallBudget,
+) -> Option<TransactionInfo> {
+    let safe = safe?;
+    let to = address_parameter(input, 0)?;
+    let value = uint_value_parameter(input, 1)?;
+    let data = bytes_parameter(input, 2)?;
+    let operation = usize_parameter(input, 3)?;
+    if operation > 1 {
+        return None;
+    }
+
+    let request = Web3TransactionRequest {
+        from: safe,
+        to: Some(to.parse().ok()?),
+        value: Some(value),
+        data: Some(data.into()),
+        ..Default::default()
+    };
+
+    Some(TransactionInfo::SafeTransaction {
+        safe: format!("{:#x}", safe),
+        to,
+        value: format!("{:#x}", value),
+        operation: operation as u8,
+        info: Box::new(nested_transaction_info(&request, Some(safe), budget)),
+    })
+}
+
+/// Classifies a call of `data` on `to` sent by `from` within `budget`.
+fn call_transaction_info(
+    from: Option<Address>,
+    to: Address,
+    data: Vec<u8>,
+    budget: CallBudget,
+) -> TransactionInfo {
+    let request = Web3TransactionRequest {
+        from: from.unwrap_or_default(),
+        to: Some(to),
+        data: Some(data.into()),
+        ..Default::default()
+    };
+    nested_transaction_info(&request, from, budget)
+}
+
+/// Classifies a nested call, or reports it as unknown once `budget` is used
+/// up.
+fn nested_transaction_info(
+    request: &Web3TransactionRequest,
+    sender: Option<Address>,
+    budget: CallBudget,
+) -> TransactionInfo {
+    match budget.nested() {
+        Some(budget) => request_transaction_info(request, sender, budget),
+        None => TransactionInfo::Unknown {
+            value: request.value.map(|value| format!("{:#x}", value)),
+            function: None,
+        },
+    }
+}
+
+/// Decodes Multicall `aggregate`, `tryAggregate` and `aggregate3` calls,
+/// which are sent by `contract`, and router `multicall(bytes[])` calls, which
+/// are executed by `contract` itself on behalf of `sender`.
+fn multicall_transaction_info(
+    sender: &str,
+    contract: Option<Address>,
+    input: &str,
+    budget: CallBudget,
+) -> Option<TransactionInfo> {
+    let contract = contract?;
+
+    // Calls of `(address target, ..., bytes callData)` tuples, where `data`
+    // is the index of `callData`.
+    let aggregate_calls = |index: usize, data: usize| {
+        dynamic_array_parameter(input, index)?
+            .into_iter()
+            .map(|call| {
+                let target = address_parameter(input, call)?.parse().ok()?;
+                let data = bytes_value(input, dynamic_parameter(input, call, data)?)?;
+                Some(call_transaction_info(Some(contract), target, data, budget))
+            })
+            .collect::<Option<Vec<_>>>()
+    };
+    let router_calls = |index: usize| {
+        let sender = sender.parse().ok();
+        let calls = bytes_array_parameter(input, index)?;
+        Some(
+            calls
+                .into_iter()
+                .map(|data| call_transaction_info(sender, contract, data, budget))
+                .collect(),
+        )
+    };
+
+    let calls = match input.get(..METHOD_LENGTH)? {
+        MULTICALL_AGGREGATE => aggregate_calls(0, 1)?,
+        MULTICALL_TRY_AGGREGATE => aggregate_calls(1, 1)?,
+        MULTICALL_AGGREGATE3 => aggregate_calls(0, 2)?,
+        MULTICALL => router_calls(0)?,
+        MULTICALL_WITH_DEADLINE => router_calls(1)?,
+        _ => return None,
+    };
+    Some(TransactionInfo::Batch { calls })
+}
+
+/// Decodes the first and last token of an `address[]` Uniswap V2 path.
+fn v2_path_parameter(input: &str, index: usize) -> Option<(String, String)> {
+    let path = array_parameter(input, index, |base, i| address_parameter(input, base + i))?;
+    match path.as_slice() {
+        [token_in, .., token_out] => Some((token_in.clone(), token_out.clone())),
+        _ => None,
+    }
+}
+
+/// Decodes the first and last token of a packed Uniswap V3 path, which
+/// alternates token addresses and 3 byte pool fees.
+fn v3_path(path: &[u8]) -> Option<(String, String)> {
+    let hops = path.len().checked_sub(ADDRESS_LENGTH)?;
+    if hops == 0 || hops % V3_PATH_HOP_LENGTH != 0 {
+        return None;
+    }
+
+    let token_in = Address::from_slice(&path[..ADDRESS_LENGTH]);
+    let token_out = Address::from_slice(&path[path.len() - ADDRESS_LENGTH..]);
+    Some((format!("{:#x}", token_in), format!("{:#x}", token_out)))
+}
+
+/// Resolves the recipient placeholders of Uniswap routers, where address 1
+/// is the sender and address 2 is the router itself.
+fn swap_recipient(recipient: String, sender: &str, router: Option<Address>) -> String {
+    match recipient.parse::<Address>() {
+        Ok(address) if address == Address::from_low_u64_be(1) => sender.to_owned(),
+        Ok(address) if address == Address::from_low_u64_be(2) => router
+            .map(|router| format!("{:#x}", router))
+            .unwrap_or(recipient),
+        _ => recipient,
+    }
+}
+
+/// Decodes the first exact input swap command of a Universal Router
+/// `execute` call.
+fn universal_router_swap_info(
+    sender: &str,
+    router: Option<Address>,
+    input: &str,
+) -> Option<TransactionInfo> {
+    let commands = bytes_parameter(input, 0)?;
+    let inputs = bytes_array_parameter(input, 1)?;
+    if commands.len() != inputs.len() {
+        return None;
+    }
+
+    commands
+        .into_iter()
+        .zip(inputs)
+        .find_map(|(command, data)| {
+            // Command inputs are ABI encoded without a selector.
+            let input = format!(
+                "0x00000000{}",
+                data.iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect::<String>()
+            );
+            let (token_in, token_out) = match command & UNIVERSAL_ROUTER_COMMAND_MASK {
+                UNIVERSAL_ROUTER_V3_SWAP_EXACT_IN => v3_path(&bytes_parameter(&input, 3)?)?,
+                UNIVERSAL_ROUTER_V2_SWAP_EXACT_IN => v2_path_parameter(&input, 3)?,
+                _ => return None,
+            };
+
+            Some(TransactionInfo::TokenSwap {
+                token_in,
+                token_out,
+                amount_in: uint_parameter(&input, 1)?,
+                min_amount_out: uint_parameter(&input, 2)?,
+                recipient: swap_recipient(address_parameter(&input, 0)?, sender, router),
+            })
+        })
+}
+
+/// Decodes exact input swaps of the Uniswap V2 router, the V3 `SwapRouter`
+/// and `SwapRouter02`, and the Universal Router.
+///
+/// Ether swapped by the V2 router is the `value` of the transaction.
+fn swap_transaction_info(
+    sender: &str,
+    router: Option<Address>,
+    value: U256,
+    input: &str,
+) -> Option<TransactionInfo> {
+    let recipient = |index: usize| {
+        Some(swap_recipient(
+            address_parameter(input, index)?,
+            sender,
+            router,
+        ))
+    };
+    let exact_input_tuple = || dynamic_parameter(input, 0, 0);
+    let exact_input_path =
+        |tuple: usize| v3_path(&bytes_value(input, dynamic_parameter(input, tuple, 0)?)?);
+
+    let ((token_in, token_out), amount_in, min_amount_out, recipient) =
+        match input.get(..METHOD_LENGTH)? {
+            V2_SWAP_EXACT_TOKENS_FOR_TOKENS
+            | V2_SWAP_EXACT_TOKENS_FOR_TOKENS_FEE_ON_TRANSFER
+            | V2_SWAP_EXACT_TOKENS_FOR_ETH
+            | V2_SWAP_EXACT_TOKENS_FOR_ETH_FEE_ON_TRANSFER
+            | ROUTER02_SWAP_EXACT_TOKENS_FOR_TOKENS => (
+                v2_path_parameter(input, 2)?,
+                uint_parameter(input, 0)?,
+                uint_parameter(input, 1)?,
+                recipient(3)?,
+            ),
+            V2_SWAP_EXACT_ETH_FOR_TOKENS | V2_SWAP_EXACT_ETH_FOR_TOKENS_FEE_ON_TRANSFER => (
+                v2_path_parameter(input, 1)?,
+                format!("{:#x}", value),
+                uint_parameter(input, 0)?,
+                recipient(2)?,
+            ),
+            V3_EXACT_INPUT_SINGLE => (
+                (address_parameter(input, 0)?, address_parameter(input, 1)?),
+                uint_parameter(input, 5)?,
+                uint_parameter(input, 6)?,
+                recipient(3)?,
+            ),
+            ROUTER02_EXACT_INPUT_SINGLE => (
+                (address_parameter(input, 0)?, address_parameter(input, 1)?),
+                uint_parameter(input, 4)?,
+                uint_parameter(input, 5)?,
+                recipient(3)?,
+            ),
+            V3_EXACT_INPUT => {
+                let tuple = exact_input_tuple()?;
+                (
+                    exact_input_path(tuple)?,
+                    uint_parameter(input, tuple + 3)?,
+                    uint_parameter(input, tuple + 4)?,
+                    recipient(tuple + 1)?,
+                )
+            }
+            ROUTER02_EXACT_INPUT => {
+                let tuple = exact_input_tuple()?;
+                (
+                    exact_input_path(tuple)?,
+                    uint_parameter(input, tuple + 2)?,
+                    uint_parameter(input, tuple + 3)?,
+                    recipient(tuple + 1)?,
+                )
+            }
+            UNIVERSAL_ROUTER_EXECUTE | UNIVERSAL_ROUTER_EXECUTE_WITH_DEADLINE => {
+                return universal_router_swap_info(sender, router, input)
+            }
+            _ => return None,
+        };
+
+    Some(TransactionInfo::TokenSwap {
+        token_in,
+        token_out,
+        amount_in,
+        min_amount_out,
+        recipient,
+    })
+}
+
+/// Identifies deposits into and withdrawals from a canonical wrapped native
+/// token, including plain transfers that deposit through its fallback.
+///
+/// Transactions don't carry their chain ID, so the wrapped tokens of all
+/// known chains are recognized.
+fn wrap_transaction_info(to: Option<Address>, value: U256, input: &str) -> Option<TransactionInfo> {
+    let to = to?;
+    if !WRAPPED_NATIVE_TOKENS
+        .iter()
+        .any(|(_, token)| token.parse().ok() == Some(to))
+    {
+        return None;
+    }
+
+    let token = format!("{:#x}", to);
+    match input.get(..METHOD_LENGTH) {
+        None if input.trim_start_matches("0x").is_empty() && !value.is_zero() => {
+            Some(TransactionInfo::Wrap {
+                token,
+                amount: format!("{:#x}", value),
+            })
+        }
+        Some(WETH_DEPOSIT) => Some(TransactionInfo::Wrap {
+            token,
+            amount: format!("{:#x}", value),
+        }),
+        Some(WETH_WITHDRAW) => Some(TransactionInfo::Unwrap {
+            token,
+            amount: uint_parameter(input, 0)?,
+        }),
+        _ => None,
+    }
+}
+
+/// Decodes ERC-20 `approve` and `permit`, ERC-721/1155 `setApprovalForAll`
+/// and Permit2 `approve`.
+///
+/// Allowances of the maximum value of their type are unlimited.
+/// `setApprovalForAll` approves all tokens without an amount, and revokes
+/// with an amount of zero.
+fn approval_transaction_info(sender: &str, input: &str) -> Option<TransactionInfo> {
+    let (owner, spender, amount, token, max_amount) = match input.get(..METHOD_LENGTH)? {
+        ERC20_APPROVE => (
+            sender.to_owned(),
+            address_parameter(input, 0)?,
+            uint_value_parameter(input, 1)?,
+            None,
+            U256::MAX,
+        ),
+        ERC20_PERMIT => (
+            address_parameter(input, 0)?,
+            address_parameter(input, 1)?,
+            uint_value_parameter(input, 2)?,
+            None,
+            U256::MAX,
+        ),
+        PERMIT2_APPROVE => (
+            sender.to_owned(),
+            address_parameter(input, 1)?,
+            uint_value_parameter(input, 2)?,
+            Some(address_parameter(input, 0)?),
+            (U256::one() << 160) - 1,
+        ),
+        SET_APPROVAL_FOR_ALL => {
+            let approved = !uint_value_parameter(input, 1)?.is_zero();
+            return Some(TransactionInfo::Approval {
+                owner: sender.to_owned(),
+                spender: address_parameter(input, 0)?,
+                amount: if approved {
+                    None
+                } else {
+                    Some("0x0".to_owned())
+                },
+                token: None,
+                is_unlimited: approved,
+            });
+        }
+        _ => return None,
+    };
+
+    Some(TransactionInfo::Approval {
+        owner,
+        spender,
+        amount: Some(format!("{:#x}", amount)),
+        token,
+        is_unlimited: amount >= max_amount,
+    })
+}
+
+/// Identifies calls to known token methods from hex encoded `input`.
+///
+/// ERC-20 `transfer` is attributed to `sender`. Transfers with an explicit
+/// `from` or `to` argument of the zero address are reported as mints or burns.
+/// The implicit `sender` of `transfer` is never taken as a mint, since it is
+/// empty for requests without a known sender.
+///
+/// ERC-20 and ERC-721 share the `transferFrom(address,address,uint256)`
+/// selector and parameters, so it is reported as an ERC-20 transfer. Only the
+/// ERC-721 `safeTransferFrom` methods are reported with a `token_id`.
+fn calldata_transaction_info(
+    sender: &str,
+    contract: Option<Address>,
+    value: U256,
+    input: &str,
+    budget: CallBudget,
+) -> Option<TransactionInfo> {
+    let (from, to, amount, token_id) = match input.get(..METHOD_LENGTH)? {
+        SAFE_EXEC_TRANSACTION => return safe_transaction_info(contract, input, budget),
+        SAFE_TRANSFER_FROM if input.len() >= SAFE_TRANSFER_FROM_LENGTH => {
+            return Some(safe_transfer_from_transaction_info(input).into_supply_change())
+        }
+        ERC1155_SAFE_BATCH_TRANSFER_FROM => {
+            return safe_batch_transfer_from_transaction_info(input)
+                .map(TransactionInfo::into_supply_change)
+        }
+        ERC20_TRANSFER => {
+            let to = address_parameter(input, 0)?;
+            let amount = uint_parameter(input, 1)?;
+            return Some(if is_zero_address(&to) {
+                TransactionInfo::TokenBurn {
+                    from: sender.to_owned(),
+                    amount,
+                    token_id: None,
+                    token_info: None,
+                    fiat_value: None,
+                }
+            } else {
+                TransactionInfo::TokenTransfer {
+                    from: sender.to_owned(),
+                    to,
+                    amount,
+                    token_id: None,
+                    token_info: None,
+                    fiat_value: None,
+                }
+            });
+        }
+        ERC20_TRANSFER_FROM => (
+            address_parameter(input, 0)?,
+            address_parameter(input, 1)?,
+            uint_parameter(input, 2)?,
+            None,
+        ),
+        ERC721_SAFE_TRANSFER_FROM | ERC721_SAFE_TRANSFER_FROM_WITH_DATA => (
+            address_parameter(input, 0)?,
+            address_parameter(input, 1)?,
+            "0x1".to_owned(),
+            Some(uint_parameter(input, 2)?),
+        ),
+        MULTICALL_AGGREGATE
+        | MULTICALL_TRY_AGGREGATE
+        | MULTICALL_AGGREGATE3
+        | MULTICALL
+        | MULTICALL_WITH_DEADLINE => {
+            return multicall_transaction_info(sender, contract, input, budget)
+        }
+        _ => {
+            return approval_transaction_info(sender, input)
+                .or_else(|| swap_transaction_info(sender, contract, value, input))
+        }
+    };
+
+    Some(
+        TransactionInfo::TokenTransfer {
+            from,
+            to,
+            amount,
+            token_id,
+            token_info: None,
+            fiat_value: None,
+        }
+        .into_supply_change(),
+    )
+}
+
+/// Identifies a contract deployment, which has no recipient and the init
+/// code as data.
+fn contract_deployment_info(to: Option<Address>, data: &[u8]) -> Option<TransactionInfo> {
+    if to.is_some() || data.is_empty() {
+        return None;
+    }
+
+    Some(TransactionInfo::ContractDeployment {
+        bytecode_size: data.len(),
+        init_code_hash: format!("{:#x}", H256(keccak256(data))),
+    })
+}
 
 impl IdentifyableTransction for Web3Transaction {
     fn transaction_info(&self) -> TransactionInfo {
-        let value = Some(serde_json::json!(self.value).as_str().unwrap().to_owned());
         let input = serde_json::json!(self.input).as_str().unwrap().to_owned();
-        match input.split_at(10).0 {
-            SAFE_TRANSFER_FROM => safe_transfer_from_transaction_info(&input),
-            _ => TransactionInfo::Unknown { value },
-        }
+        web3_transaction_info(self, &input)
     }
 }
 
-/// RLP-encode an unsigned legacy transaction request.
+/// Identifies a transaction whose input is already hex encoded as `input`.
+pub(crate) fn web3_transaction_info(transaction: &Web3Transaction, input: &str) -> TransactionInfo {
+    if let Some(info) = contract_deployment_info(transaction.to, &transaction.input.0) {
+        return info;
+    }
+
+    let from = serde_json::json!(transaction.from)
+        .as_str()
+        .unwrap_or_default()
+        .to_owned();
+    if let Some(info) = wrap_transaction_info(transaction.to, transaction.value, input) {
+        return info;
+    }
+
+    let calls = Cell::default();
+    calldata_transaction_info(
+        &from,
+        transaction.to,
+        transaction.value,
+        input,
+        CallBudget::new(&calls),
+    )
+    .unwrap_or_else(|| TransactionInfo::Unknown {
+        value: Some(format!("{:#x}", transaction.value)),
+        function: None,
+    })
+}
+
+/// RLP-encode a legacy transaction request.
 ///
-/// The encoding is defined in [EIP-2712][eip-2718] as
-/// `rlp([nonce, gasprice, startgas, to, value, data, chainid, 0, 0])`.
+/// The encoding is defined in [EIP-155][eip-155] as
+/// `rlp([nonce, gasprice, startgas, to, value, data, chainid, 0, 0])` when
+/// unsigned, and with `[v, r, s]` instead of `[chainid, 0, 0]` when signed.
+/// Without a chain ID, the unsigned payload is just
+/// `rlp([nonce, gasprice, startgas, to, value, data])`.
 ///
-/// [eip-2718]: https://eips.ethereum.org/EIPS/eip-2718
+/// [eip-155]: https://eips.ethereum.org/EIPS/eip-155
 #[cfg(feature = "signing")]
-fn rlp_append_unsigned_legacy(
+fn rlp_append_legacy(
     request: &Web3TransactionRequest,
     rlp: &mut RlpStream,
-    chain_id: u64,
+    chain_id: Option<u64>,
+    signature: Option<&RawSignature>,
 ) -> Result<(), Error> {
-    rlp.begin_list(9);
-    rlp.append(&request.nonce);
-    rlp.append(&request.gas_price);
-    rlp.append(&request.gas);
-    if let Some(to) = request.to {
-        rlp.append(&to);
-    } else {
-        rlp.append(&"");
+    rlp.begin_list(match (signature, chain_id) {
+        (None, None) => 6,
+        _ => 9,
+    });
+    rlp.append(&request.nonce.unwrap_or_default());
+    rlp.append(&request.gas_price.unwrap_or_default());
+    rlp_append_call(request, rlp);
+    match (signature, chain_id) {
+        (Some(signature), _) => rlp_append_signature(rlp, signature),
+        (None, Some(chain_id)) => {
+            rlp.append(&chain_id);
+            rlp.append(&0u8);
+            rlp.append(&0u8);
+        }
+        (None, None) => {}
     }
-    rlp.append(&request.value);
-    rlp.append(&request.data.as_ref().map(|data| data.0.clone()));
-    rlp.append(&chain_id);
-    rlp.append(&0u8);
-    rlp.append(&0u8);
 
     Ok(())
 }
 
-/// RLP-encode an unsigned transaction request with optional access list.
+/// RLP-encode a transaction request with optional access list.
 ///
 /// The encoding is defined in [EIP-2930][eip-2930] as
 ///
@@ -113,40 +880,30 @@ fn rlp_append_unsigned_legacy(
 ///
 /// `[[accessed_addresses{20 bytes}, [accessed_storage_keys{32 bytes}...]]...]`
 ///
+/// followed by `[signatureYParity, signatureR, signatureS]` when signed.
+///
 /// [eip-2930]: https://eips.ethereum.org/EIPS/eip-2930
 #[cfg(feature = "signing")]
-fn rlp_append_unsigned_eip_2930(
+fn rlp_append_eip_2930(
     request: &Web3TransactionRequest,
     rlp: &mut RlpStream,
     chain_id: u64,
+    signature: Option<&RawSignature>,
 ) -> Result<(), Error> {
-    rlp.begin_list(8);
+    rlp.begin_list(if signature.is_some() { 11 } else { 8 });
     rlp.append(&chain_id);
-    rlp.append(&request.nonce);
-    rlp.append(&request.gas_price);
-    rlp.append(&request.gas);
-    if let Some(to) = request.to {
-        rlp.append(&to);
-    } else {
-        rlp.append(&"");
-    }
-    rlp.append(&request.value);
-    rlp.append(&request.data.as_ref().map(|data| data.0.clone()));
-    if let Some(access_list) = &request.access_list {
-        for item in access_list.iter() {
-            rlp.begin_list(2);
-            rlp.append(&item.address);
-            rlp.begin_list(item.storage_keys.len());
-            for key in item.storage_keys.iter() {
-                rlp.append(key);
-            }
-        }
+    rlp.append(&request.nonce.unwrap_or_default());
+    rlp.append(&request.gas_price.unwrap_or_default());
+    rlp_append_call(request, rlp);
+    rlp_append_access_list(request, rlp);
+    if let Some(signature) = signature {
+        rlp_append_signature(rlp, signature);
     }
 
     Ok(())
 }
 
-/// RLP-encode an unsigned transaction request for EIP-1559.
+/// RLP-encode a transaction request for EIP-1559.
 ///
 /// The encoding is defined in [EIP-1559][eip-1559] as
 ///
@@ -156,147 +913,672 @@ fn rlp_append_unsigned_eip_2930(
 ///
 /// `[[accessed_addresses{20 bytes}, [accessed_storage_keys{32 bytes}...]]...]`
 ///
+/// followed by `[signature_y_parity, signature_r, signature_s]` when signed.
+///
 /// [eip-1559]: https://eips.ethereum.org/EIPS/eip-1559
 #[cfg(feature = "signing")]
-fn rlp_append_unsigned_eip_1559(
+fn rlp_append_eip_1559(
+    request: &Web3TransactionRequest,
+    rlp: &mut RlpStream,
+    chain_id: u64,
+    signature: Option<&RawSignature>,
+) -> Result<(), Error> {
+    rlp.begin_list(if signature.is_some() { 12 } else { 9 });
+    rlp.append(&chain_id);
+    rlp.append(&request.nonce.unwrap_or_default());
+    rlp.append(&request.max_priority_fee_per_gas.unwrap_or_default());
+    rlp.append(&request.max_fee_per_gas.unwrap_or_default());
+    rlp_append_call(request, rlp);
+    rlp_append_access_list(request, rlp);
+    if let Some(signature) = signature {
+        rlp_append_signature(rlp, signature);
+    }
+
+    Ok(())
+}
+
+/// RLP-encode a blob transaction request for EIP-4844.
+///
+/// The encoding is defined in [EIP-4844][eip-4844] as
+///
+/// `rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, to, value, data, access_list, max_fee_per_blob_gas, blob_versioned_hashes])`
+///
+/// followed by `[y_parity, r, s]` when signed. Blob transactions can't
+/// create contracts, so `to` is required.
+///
+/// [eip-4844]: https://eips.ethereum.org/EIPS/eip-4844
+#[cfg(feature = "signing")]
+fn rlp_append_eip_4844(
     request: &Web3TransactionRequest,
     rlp: &mut RlpStream,
     chain_id: u64,
+    signature: Option<&RawSignature>,
+    (max_fee_per_blob_gas, blob_versioned_hashes): (U256, &[H256]),
 ) -> Result<(), Error> {
-    rlp.begin_list(9);
+    if request.to.is_none() {
+        return Err(Error::InvalidData);
+    }
+
+    rlp.begin_list(if signature.is_some() { 14 } else { 11 });
     rlp.append(&chain_id);
-    rlp.append(&request.nonce);
-    rlp.append(&request.max_priority_fee_per_gas);
-    rlp.append(&request.max_fee_per_gas);
-    rlp.append(&request.gas);
+    rlp.append(&request.nonce.unwrap_or_default());
+    rlp.append(&request.max_priority_fee_per_gas.unwrap_or_default());
+    rlp.append(&request.max_fee_per_gas.unwrap_or_default());
+    rlp_append_call(request, rlp);
+    rlp_append_access_list(request, rlp);
+    rlp.append(&max_fee_per_blob_gas);
+    rlp.append_list(blob_versioned_hashes);
+    if let Some(signature) = signature {
+        rlp_append_signature(rlp, signature);
+    }
+
+    Ok(())
+}
+
+/// RLP-encode a set code transaction request for EIP-7702.
+///
+/// The encoding is defined in [EIP-7702][eip-7702] as
+///
+/// `rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, destination, value, data, access_list, authorization_list])`
+///
+/// where `authorization_list` is
+///
+/// `[[chain_id, address, nonce, y_parity, r, s]...]`
+///
+/// followed by `[signature_y_parity, signature_r, signature_s]` when signed.
+/// Set code transactions can't create contracts, so `destination` is
+/// required.
+///
+/// [eip-7702]: https://eips.ethereum.org/EIPS/eip-7702
+#[cfg(feature = "signing")]
+fn rlp_append_eip_7702(
+    request: &Web3TransactionRequest,
+    rlp: &mut RlpStream,
+    chain_id: u64,
+    signature: Option<&RawSignature>,
+    authorization_list: &[Authorization],
+) -> Result<(), Error> {
+    if request.to.is_none() || authorization_list.is_empty() {
+        return Err(Error::InvalidData);
+    }
+
+    rlp.begin_list(if signature.is_some() { 13 } else { 10 });
+    rlp.append(&chain_id);
+    rlp.append(&request.nonce.unwrap_or_default());
+    rlp.append(&request.max_priority_fee_per_gas.unwrap_or_default());
+    rlp.append(&request.max_fee_per_gas.unwrap_or_default());
+    rlp_append_call(request, rlp);
+    rlp_append_access_list(request, rlp);
+    rlp.append_list(authorization_list);
+    if let Some(signature) = signature {
+        rlp_append_signature(rlp, signature);
+    }
+
+    Ok(())
+}
+
+/// RLP-encode the `[gas, to, value, data]` fields of all transaction types.
+#[cfg(feature = "signing")]
+fn rlp_append_call(request: &Web3TransactionRequest, rlp: &mut RlpStream) {
+    rlp.append(&request.gas.unwrap_or_default());
     if let Some(to) = request.to {
         rlp.append(&to);
     } else {
         rlp.append(&"");
     }
-    rlp.append(&request.value);
-    rlp.append(&request.data.as_ref().map(|data| data.0.clone()));
-    if let Some(access_list) = &request.access_list {
-        for item in access_list.iter() {
-            rlp.begin_list(2);
-            rlp.append(&item.address);
-            rlp.begin_list(item.storage_keys.len());
-            for key in item.storage_keys.iter() {
-                rlp.append(key);
-            }
+    rlp.append(&request.value.unwrap_or_default());
+    rlp.append(
+        &request
+            .data
+            .as_ref()
+            .map(|data| data.0.as_slice())
+            .unwrap_or_default(),
+    );
+}
+
+#[cfg(feature = "signing")]
+fn rlp_append_access_list(request: &Web3TransactionRequest, rlp: &mut RlpStream) {
+    let access_list = request.access_list.as_deref().unwrap_or_default();
+    rlp.begin_list(access_list.len());
+    for item in access_list {
+        rlp.begin_list(2);
+        rlp.append(&item.address);
+        rlp.append_list(&item.storage_keys);
+    }
+}
+
+#[cfg(feature = "signing")]
+fn rlp_append_signature(rlp: &mut RlpStream, signature: &RawSignature) {
+    rlp.append(&signature.v);
+    rlp.append(&signature.r);
+    rlp.append(&signature.s);
+}
+
+/// The ECDSA signature of a raw transaction.
+pub(crate) struct RawSignature {
+    /// The `v` as encoded, which is the y parity of typed transactions.
+    pub(crate) v: u64,
+    pub(crate) r: U256,
+    pub(crate) s: U256,
+}
+
+/// A decoded raw transaction.
+pub(crate) struct RawTransaction {
+    /// The transaction, sent by the recovered signer if signed or the zero
+    /// address otherwise.
+    pub(crate) request: Web3TransactionRequest,
+    pub(crate) signature: Option<RawSignature>,
+    /// The chain ID of typed and EIP-155 transactions.
+    pub(crate) chain_id: Option<u64>,
+    /// The max fee per blob gas and blob versioned hashes of an EIP-4844
+    /// transaction.
+    pub(crate) blob: Option<(U256, Vec<H256>)>,
+    /// The authorization list of an EIP-7702 transaction.
+    pub(crate) authorization_list: Option<Vec<Authorization>>,
+}
+
+/// Decodes a raw transaction, which is either an RLP list of a legacy
+/// transaction or an [EIP-2718] typed transaction envelope of an EIP-2930,
+/// EIP-1559, EIP-4844 or EIP-7702 transaction.
+///
+/// Both signed transactions and the unsigned payloads signed by the sender
+/// are accepted. Blob transactions must not be wrapped with their blobs.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub(crate) fn decode_raw_transaction(bytes: &[u8]) -> Result<RawTransaction, Error> {
+    let (transaction_type, payload) = match bytes.split_first() {
+        Some((first, _)) if *first >= 0xc0 => (None, bytes),
+        Some((transaction_type, payload)) if *transaction_type <= 0x7f => {
+            (Some(u64::from(*transaction_type)), payload)
         }
+        _ => return Err(Error::InvalidData),
+    };
+    let rlp = Rlp::new(payload);
+    let payload_info = rlp.payload_info()?;
+    if !rlp.is_list() || payload_info.header_len + payload_info.value_len != payload.len() {
+        return Err(Error::InvalidData);
     }
 
-    Ok(())
+    // The number of unsigned fields and the index of the nonce, which
+    // follows the chain ID of typed transactions.
+    let (unsigned_len, nonce) = match transaction_type {
+        None => (6, 0),
+        Some(EIP_2930_TRANSACTION_TYPE) => (8, 1),
+        Some(EIP_1559_TRANSACTION_TYPE) => (9, 1),
+        Some(EIP_4844_TRANSACTION_TYPE) => (11, 1),
+        Some(EIP_7702_TRANSACTION_TYPE) => (10, 1),
+        Some(_) => return Err(Error::InvalidData),
+    };
+    let signature = match rlp.item_count()? {
+        len if len == unsigned_len => None,
+        len if len == unsigned_len + 3 => Some(RawSignature {
+            v: rlp.val_at(unsigned_len)?,
+            r: rlp.val_at(unsigned_len + 1)?,
+            s: rlp.val_at(unsigned_len + 2)?,
+        }),
+        _ => return Err(Error::InvalidData),
+    };
+
+    // Unsigned EIP-155 payloads end with the chain ID and two zeros.
+    let (signature, chain_id, recovery_id) = match (transaction_type, signature) {
+        (None, Some(signature)) if signature.r.is_zero() && signature.s.is_zero() => {
+            (None, Some(signature.v), 0)
+        }
+        (None, Some(signature)) if signature.v >= EIP_155_V_OFFSET => {
+            let v = signature.v - EIP_155_V_OFFSET;
+            (Some(signature), Some(v / 2), v % 2)
+        }
+        (None, Some(signature)) => {
+            let recovery_id = signature
+                .v
+                .checked_sub(LEGACY_V_OFFSET)
+                .ok_or(Error::InvalidData)?;
+            (Some(signature), None, recovery_id)
+        }
+        (None, None) => (None, None, 0),
+        (Some(_), signature) => {
+            let recovery_id = signature.as_ref().map(|signature| signature.v).unwrap_or(0);
+            (signature, Some(rlp.val_at(0)?), recovery_id)
+        }
+    };
+
+    let (gas_price, max_priority_fee_per_gas, max_fee_per_gas, gas) = if matches!(
+        transaction_type,
+        Some(EIP_1559_TRANSACTION_TYPE)
+            | Some(EIP_4844_TRANSACTION_TYPE)
+            | Some(EIP_7702_TRANSACTION_TYPE)
+    ) {
+        (
+            None,
+            Some(rlp.val_at(nonce + 1)?),
+            Some(rlp.val_at(nonce + 2)?),
+            nonce + 3,
+        )
+    } else {
+        (Some(rlp.val_at(nonce + 1)?), None, None, nonce + 2)
+    };
+    let to = rlp.at(gas + 1)?;
+    let access_list = match transaction_type {
+        Some(_) => Some(decode_access_list(&rlp.at(gas + 4)?)?),
+        None => None,
+    };
+    let blob = match transaction_type {
+        Some(EIP_4844_TRANSACTION_TYPE) if to.is_empty() => return Err(Error::InvalidData),
+        Some(EIP_4844_TRANSACTION_TYPE) => Some((rlp.val_at(gas + 5)?, rlp.list_at(gas + 6)?)),
+        _ => None,
+    };
+    let authorization_list = match transaction_type {
+        Some(EIP_7702_TRANSACTION_TYPE) if to.is_empty() => return Err(Error::InvalidData),
+        Some(EIP_7702_TRANSACTION_TYPE) => Some(rlp.list_at(gas + 5)?),
+        _ => None,
+    };
+    let mut request = Web3TransactionRequest {
+        nonce: Some(rlp.val_at(nonce)?),
+        gas_price,
+        gas: Some(rlp.val_at(gas)?),
+        to: if to.is_empty() {
+            None
+        } else {
+            Some(to.as_val()?)
+        },
+        value: Some(rlp.val_at(gas + 2)?),
+        data: Some(Bytes(rlp.val_at(gas + 3)?)),
+        transaction_type: transaction_type.map(U64::from),
+        access_list,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        ..Default::default()
+    };
+
+    if let Some(signature) = &signature {
+        let mut stream = RlpStream::new();
+        match (transaction_type, chain_id) {
+            (None, Some(_)) => stream.begin_list(unsigned_len + 3),
+            _ => stream.begin_list(unsigned_len),
+        };
+        for index in 0..unsigned_len {
+            stream.append_raw(rlp.at(index)?.as_raw(), 1);
+        }
+        if let (None, Some(chain_id)) = (transaction_type, chain_id) {
+            stream.append(&chain_id);
+            stream.append(&0u8);
+            stream.append(&0u8);
+        }
+
+        let mut message = transaction_type
+            .map(|transaction_type| vec![transaction_type as u8])
+            .unwrap_or_default();
+        message.extend_from_slice(stream.as_raw());
+        request.from = recover_signer(&keccak256(&message), recovery_id, signature)?;
+    }
+
+    Ok(RawTransaction {
+        request,
+        signature,
+        chain_id,
+        blob,
+        authorization_list,
+    })
 }
 
-impl TransactionRequest for Web3TransactionRequest {
-    fn from_json(json: Value) -> Result<Self, Error> {
-        let request = serde_json::from_value(json)?;
-        Ok(request)
+/// Decodes an access list of `[address, [storage_keys...]]` items.
+fn decode_access_list(rlp: &Rlp) -> Result<AccessList, Error> {
+    rlp.iter()
+        .map(|item| {
+            Ok(AccessListItem {
+                address: item.val_at(0)?,
+                storage_keys: item.list_at(1)?,
+            })
+        })
+        .collect()
+}
+
+/// Recovers the sender of a signed raw transaction of any supported type,
+/// without an RPC call.
+///
+/// Unsigned payloads are rejected, since they have no sender.
+pub fn recover_sender(raw_transaction: &[u8]) -> Result<Address, Error> {
+    let raw = decode_raw_transaction(raw_transaction)?;
+    raw.signature.ok_or(Error::InvalidData)?;
+    Ok(raw.request.from)
+}
+
+/// Recovers the address that signed `message_hash`.
+pub(crate) fn recover_signer(
+    message_hash: &[u8],
+    recovery_id: u64,
+    signature: &RawSignature,
+) -> Result<Address, Error> {
+    if recovery_id > 1 {
+        return Err(Error::InvalidData);
     }
 
-    fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
-        let request = serde_json::from_slice(bytes)?;
-        Ok(request)
+    let mut compact = [0u8; 64];
+    signature.r.to_big_endian(&mut compact[..32]);
+    signature.s.to_big_endian(&mut compact[32..]);
+    let recovery_id = RecoveryId::from_i32(recovery_id as i32).map_err(Error::Key)?;
+    let signature =
+        RecoverableSignature::from_compact(&compact, recovery_id).map_err(Error::Key)?;
+    let message = Message::from_slice(message_hash).map_err(Error::Key)?;
+    let public_key = Secp256k1::verification_only()
+        .recover_ecdsa(&message, &signature)
+        .map_err(Error::Key)?;
+    Address::from_public_key(&public_key.serialize_uncompressed())
+}
+
+/// Formats an amount as `0x` prefixed hex.
+pub(crate) fn hex_amount(amount: U256) -> String {
+    format!("{:#x}", amount)
+}
+
+/// Computes `value + gas * (maxFeePerGas or gasPrice)`.
+pub(crate) fn max_total_fee(request: &Web3TransactionRequest) -> Option<U256> {
+    request
+        .gas
+        .zip(request.max_fee_per_gas.or(request.gas_price))
+        .and_then(|(gas, price)| gas.checked_mul(price))
+        .and_then(|fee| fee.checked_add(request.value.unwrap_or_default()))
+}
+
+/// Gets the gas parameters of a request with its maximum total fee.
+pub(crate) fn request_fee_info(request: &Web3TransactionRequest) -> FeeInfo {
+    FeeInfo {
+        gas_limit: request.gas.map(hex_amount),
+        gas_price: request.gas_price.map(hex_amount),
+        max_fee_per_gas: request.max_fee_per_gas.map(hex_amount),
+        max_priority_fee_per_gas: request.max_priority_fee_per_gas.map(hex_amount),
+        max_fee_per_blob_gas: None,
+        max_total_fee: max_total_fee(request).map(hex_amount),
     }
+}
 
-    fn transaction_info(&self) -> TransactionInfo {
-        if self.value.unwrap_or_default() > U256::zero()
-            && self.to.is_some()
-            && self.data.clone().unwrap_or_default().0.is_empty()
-        {
-            return TransactionInfo::TokenTransfer {
-                from: serde_json::json!(self.from)
-                    .as_str()
-                    .unwrap_or_default()
-                    .to_owned(),
-                to: serde_json::json!(self.to)
-                    .as_str()
-                    .unwrap_or_default()
-                    .to_owned(),
-                amount: serde_json::json!(self.value)
-                    .as_str()
-                    .unwrap_or_default()
-                    .to_owned(),
-                token_id: None,
-                token_info: None,
-            };
-        }
+/// Identifies a transaction request sent by `sender`, decoding nested calls
+/// within `budget`.
+///
+/// The sender of the returned info is empty if `sender` is unknown.
+fn request_transaction_info(
+    request: &Web3TransactionRequest,
+    sender: Option<Address>,
+    budget: CallBudget,
+) -> TransactionInfo {
+    let from = sender
+        .map(|sender| format!("{:#x}", sender))
+        .unwrap_or_default();
+    let data = request
+        .data
+        .as_ref()
+        .map(|data| data.0.as_slice())
+        .unwrap_or_default();
+    if let Some(info) = contract_deployment_info(request.to, data) {
+        return info;
+    }
 
-        let value = Some(
-            serde_json::json!(self.value)
+    let input = serde_json::json!(request.data)
+        .as_str()
+        .unwrap_or_default()
+        .to_owned();
+    if let Some(info) = wrap_transaction_info(request.to, request.value.unwrap_or_default(), &input)
+    {
+        return info;
+    }
+
+    if request.value.unwrap_or_default() > U256::zero()
+        && request.to.is_some()
+        && request.data.clone().unwrap_or_default().0.is_empty()
+    {
+        return TransactionInfo::TokenTransfer {
+            from,
+            to: serde_json::json!(request.to)
                 .as_str()
                 .unwrap_or_default()
                 .to_owned(),
-        );
-        let input = serde_json::json!(self.data)
+            amount: serde_json::json!(request.value)
+                .as_str()
+                .unwrap_or_default()
+                .to_owned(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        };
+    }
+
+    let value = Some(
+        serde_json::json!(request.value)
             .as_str()
             .unwrap_or_default()
-            .to_owned();
-        let method = if input.len() > METHOD_LENGTH {
-            input[0..METHOD_LENGTH].to_string()
-        } else {
-            String::new()
-        };
+            .to_owned(),
+    );
+    calldata_transaction_info(
+        &from,
+        request.to,
+        request.value.unwrap_or_default(),
+        &input,
+        budget,
+    )
+    .unwrap_or(TransactionInfo::Unknown {
+        value,
+        function: None,
+    })
+}
 
-        match method.as_str() {
-            SAFE_TRANSFER_FROM => safe_transfer_from_transaction_info(&input),
-            _ => TransactionInfo::Unknown { value },
-        }
+impl TransactionRequest for Web3TransactionRequest {
+    fn from_json(json: Value) -> Result<Self, Error> {
+        let request = from_json_value(json)?;
+        Ok(request)
+    }
+
+    /// Decodes a signed raw transaction or the unsigned payload signed by
+    /// its sender.
+    ///
+    /// The sender of signed transactions is recovered, and unsigned ones
+    /// are sent by the zero address.
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(decode_raw_transaction(bytes)?.request)
+    }
+
+    fn transaction_info(&self) -> TransactionInfo {
+        let calls = Cell::default();
+        request_transaction_info(self, Some(self.from), CallBudget::new(&calls))
+    }
+
+    fn fee_info(&self) -> Option<FeeInfo> {
+        Some(request_fee_info(self))
     }
 }
 
+/// The fields of typed transactions that aren't part of
+/// `Web3TransactionRequest`.
 #[cfg(feature = "signing")]
-impl SignableTransactionRequest for Web3TransactionRequest {
-    fn message_hash(&self, chain_id: u64) -> Result<Vec<u8>, Error> {
-        use web3::signing::keccak256;
-        let mut rlp = RlpStream::new();
-
-        match self.transaction_type.map(|t| t.as_u64()) {
-            Some(EIP_1559_TRANSACTION_TYPE) => {
-                // EIP-1559 transaction (Fee market change for ETH 1.0 chain)
-                if self.gas_price.is_some() {
-                    return Err(Error::InvalidData);
-                }
-                rlp_append_unsigned_eip_1559(self, &mut rlp, chain_id)?;
+pub(crate) enum TypedTransactionFields<'a> {
+    /// The max fee per blob gas and blob versioned hashes of an EIP-4844
+    /// transaction.
+    Blob(U256, &'a [H256]),
+    /// The authorization list of an EIP-7702 transaction.
+    SetCode(&'a [Authorization]),
+}
+
+/// Encodes a transaction request as signed with `signature`, or as the
+/// unsigned payload hashed for signing without one.
+///
+/// Typed transactions are prefixed with their type as defined in
+/// [EIP-2718][eip-2718]. EIP-4844 and EIP-7702 transactions are encoded with
+/// their `fields`. Only legacy transactions can be encoded without a chain ID.
+///
+/// [eip-2718]: https://eips.ethereum.org/EIPS/eip-2718
+#[cfg(feature = "signing")]
+pub(crate) fn encode_transaction_request(
+    request: &Web3TransactionRequest,
+    mode: SigningMode,
+    signature: Option<&RawSignature>,
+    fields: Option<TypedTransactionFields>,
+) -> Result<Vec<u8>, Error> {
+    let mut rlp = RlpStream::new();
+    let typed_chain_id = || mode.chain_id().ok_or(Error::InvalidData);
+
+    let transaction_type = match (request.transaction_type.map(|t| t.as_u64()), fields) {
+        (
+            Some(EIP_4844_TRANSACTION_TYPE),
+            Some(TypedTransactionFields::Blob(max_fee_per_blob_gas, blob_versioned_hashes)),
+        ) => {
+            // EIP-4844 transaction (Shard blob transactions)
+            if request.gas_price.is_some() {
+                return Err(Error::InvalidData);
             }
-            Some(EIP_2930_TRANSACTION_TYPE) => {
-                // EIP-2930 transaction (Optional access lists)
-                if self.max_fee_per_gas.is_some() || self.max_priority_fee_per_gas.is_some() {
-                    return Err(Error::InvalidData);
-                }
-                rlp_append_unsigned_eip_2930(self, &mut rlp, chain_id)?;
+            let blob = (max_fee_per_blob_gas, blob_versioned_hashes);
+            rlp_append_eip_4844(request, &mut rlp, typed_chain_id()?, signature, blob)?;
+            Some(EIP_4844_TRANSACTION_TYPE)
+        }
+        (
+            Some(EIP_7702_TRANSACTION_TYPE),
+            Some(TypedTransactionFields::SetCode(authorization_list)),
+        ) => {
+            // EIP-7702 transaction (Set code for EOAs)
+            if request.gas_price.is_some() {
+                return Err(Error::InvalidData);
             }
-            Some(transaction_type)
-                if transaction_type <= 0x7fu64 || transaction_type == 0xffu64 =>
-            {
+            rlp_append_eip_7702(
+                request,
+                &mut rlp,
+                typed_chain_id()?,
+                signature,
+                authorization_list,
+            )?;
+            Some(EIP_7702_TRANSACTION_TYPE)
+        }
+        (_, Some(_)) => return Err(Error::InvalidData),
+        (Some(EIP_1559_TRANSACTION_TYPE), None) => {
+            // EIP-1559 transaction (Fee market change for ETH 1.0 chain)
+            if request.gas_price.is_some() {
                 return Err(Error::InvalidData);
             }
-            _ => {
-                // Legacy transaction
-                if self.access_list.is_some()
-                    || self.max_fee_per_gas.is_some()
-                    || self.max_priority_fee_per_gas.is_some()
-                {
-                    return Err(Error::InvalidData);
-                }
-                rlp_append_unsigned_legacy(self, &mut rlp, chain_id)?;
+            rlp_append_eip_1559(request, &mut rlp, typed_chain_id()?, signature)?;
+            Some(EIP_1559_TRANSACTION_TYPE)
+        }
+        (Some(EIP_2930_TRANSACTION_TYPE), None) => {
+            // EIP-2930 transaction (Optional access lists)
+            if request.max_fee_per_gas.is_some() || request.max_priority_fee_per_gas.is_some() {
+                return Err(Error::InvalidData);
+            }
+            rlp_append_eip_2930(request, &mut rlp, typed_chain_id()?, signature)?;
+            Some(EIP_2930_TRANSACTION_TYPE)
+        }
+        (Some(transaction_type), None)
+            if transaction_type <= 0x7fu64 || transaction_type == 0xffu64 =>
+        {
+            return Err(Error::InvalidData);
+        }
+        (_, None) => {
+            // Legacy transaction
+            if request.access_list.is_some()
+                || request.max_fee_per_gas.is_some()
+                || request.max_priority_fee_per_gas.is_some()
+            {
+                return Err(Error::InvalidData);
             }
+            rlp_append_legacy(request, &mut rlp, mode.chain_id(), signature)?;
+            None
         }
+    };
+
+    let mut bytes = transaction_type
+        .map(|transaction_type| vec![transaction_type as u8])
+        .unwrap_or_default();
+    bytes.extend_from_slice(rlp.as_raw());
+    Ok(bytes)
+}
+
+#[cfg(feature = "signing")]
+impl SignableTransactionRequest for Web3TransactionRequest {
+    fn message_hash(&self, chain_id: u64) -> Result<Vec<u8>, Error> {
+        message_hash_with_mode(self, SigningMode::Eip155(chain_id))
+    }
+
+    fn encode_signed(
+        &self,
+        chain_id: u64,
+        signature: &[u8],
+        recovery_id: u64,
+    ) -> Result<Vec<u8>, Error> {
+        encode_signed_with_mode(self, SigningMode::Eip155(chain_id), signature, recovery_id)
+    }
+
+    fn transaction_hash(&self, _chain_id: u64, raw: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(keccak256(raw).to_vec())
+    }
+}
+
+/// Hashes a transaction request for signing with `mode`.
+///
+/// `message_hash` always signs with EIP-155, while
+/// [`SigningMode::PreEip155`] signs legacy transactions without a chain ID.
+#[cfg(feature = "signing")]
+pub fn message_hash_with_mode(
+    request: &Web3TransactionRequest,
+    mode: SigningMode,
+) -> Result<Vec<u8>, Error> {
+    let message = encode_transaction_request(request, mode, None, None)?;
+    Ok(keccak256(&message).to_vec())
+}
+
+/// Encodes a transaction request signed with `mode`, ready to be broadcast.
+#[cfg(feature = "signing")]
+pub fn encode_signed_with_mode(
+    request: &Web3TransactionRequest,
+    mode: SigningMode,
+    signature: &[u8],
+    recovery_id: u64,
+) -> Result<Vec<u8>, Error> {
+    let signature = raw_signature(request, mode, signature, recovery_id)?;
+    encode_transaction_request(request, mode, Some(&signature), None)
+}
 
-        let hash = keccak256(rlp.as_raw());
-        Ok(Vec::from(hash))
+/// Converts a 64 byte signature and recovery ID to the signature of a raw
+/// transaction, whose legacy `v` is defined in [EIP-155], or is 27 or 28
+/// without a chain ID.
+///
+/// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+#[cfg(feature = "signing")]
+pub(crate) fn raw_signature(
+    request: &Web3TransactionRequest,
+    mode: SigningMode,
+    signature: &[u8],
+    recovery_id: u64,
+) -> Result<RawSignature, Error> {
+    if signature.len() != 64 || recovery_id > 1 {
+        return Err(Error::InvalidData);
     }
+
+    let v = match request.transaction_type.map(|t| t.as_u64()) {
+        Some(EIP_1559_TRANSACTION_TYPE)
+        | Some(EIP_2930_TRANSACTION_TYPE)
+        | Some(EIP_4844_TRANSACTION_TYPE)
+        | Some(EIP_7702_TRANSACTION_TYPE) => recovery_id,
+        _ => match mode {
+            SigningMode::Eip155(chain_id) => chain_id * 2 + EIP_155_V_OFFSET + recovery_id,
+            SigningMode::PreEip155 => LEGACY_V_OFFSET + recovery_id,
+        },
+    };
+    Ok(RawSignature {
+        v,
+        r: U256::from_big_endian(&signature[..32]),
+        s: U256::from_big_endian(&signature[32..]),
+    })
 }
 
 fn parameters_from_request(
     request: &Web3TransactionRequest,
     chain_id: Option<u64>,
 ) -> Result<Web3TransactionParameters, Error> {
-    let gas = request.gas.ok_or(Error::InvalidData)?;
-    let value = request.value.ok_or(Error::InvalidData)?;
-    let data = request.data.clone().ok_or(Error::InvalidData)?;
+    let gas = request
+        .gas
+        .ok_or_else(|| Error::invalid_field("gas", "a quantity"))?;
+    let value = request
+        .value
+        .ok_or_else(|| Error::invalid_field("value", "a quantity"))?;
+    let data = request
+        .data
+        .clone()
+        .ok_or_else(|| Error::invalid_field("data", "hex bytes"))?;
     Ok(Web3TransactionParameters {
         nonce: request.nonce,
         gas_price: request.gas_price,
@@ -312,28 +1594,61 @@ fn parameters_from_request(
     })
 }
 
+/// Converts transaction parameters to a request from the zero address, since
+/// parameters don't have a sender.
+fn request_from_parameters(parameters: &Web3TransactionParameters) -> Web3TransactionRequest {
+    Web3TransactionRequest {
+        from: Address::zero(),
+        to: parameters.to,
+        gas: Some(parameters.gas),
+        gas_price: parameters.gas_price,
+        value: Some(parameters.value),
+        data: Some(parameters.data.clone()),
+        nonce: parameters.nonce,
+        condition: None,
+        transaction_type: parameters.transaction_type,
+        access_list: parameters.access_list.clone(),
+        max_fee_per_gas: parameters.max_fee_per_gas,
+        max_priority_fee_per_gas: parameters.max_priority_fee_per_gas,
+    }
+}
+
 impl TransactionRequest for Web3TransactionParameters {
     fn from_json(json: Value) -> Result<Self, Error> {
         let chain_id = json["chainId"].as_u64();
-        let request: Web3TransactionRequest = serde_json::from_value(json)?;
+        let request: Web3TransactionRequest = from_json_value(json)?;
         let parameters = parameters_from_request(&request, chain_id)?;
         Ok(parameters)
     }
 
+    /// Decodes a signed raw transaction or the unsigned payload signed by
+    /// its sender, keeping its chain ID.
+    ///
+    /// Blob and set code transactions aren't supported by parameters.
     fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
-        let request: Web3TransactionRequest = serde_json::from_slice(bytes)?;
-        let parameters = parameters_from_request(&request, None)?;
+        let raw = decode_raw_transaction(bytes)?;
+        if raw.blob.is_some() || raw.authorization_list.is_some() {
+            return Err(Error::InvalidData);
+        }
+        let parameters = parameters_from_request(&raw.request, raw.chain_id)?;
         Ok(parameters)
     }
 
+    /// Identifies the transaction with an empty sender, since parameters
+    /// don't have a sender.
     fn transaction_info(&self) -> TransactionInfo {
-        todo!()
+        let calls = Cell::default();
+        request_transaction_info(
+            &request_from_parameters(self),
+            None,
+            CallBudget::new(&calls),
+        )
     }
 }
 
 #[cfg(feature = "signing")]
 impl SignableTransactionRequest for Web3TransactionParameters {
-    fn message_hash(&self, _chain_id: u64) -> Result<Vec<u8>, Error> {
-        todo!()
+    fn message_hash(&self, chain_id: u64) -> Result<Vec<u8>, Error> {
+        request_from_parameters(self).message_hash(chain_id)
     }
 }
@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use web3::types::{Address, H256, U256};
+
+use crate::models::error::{from_json_slice, from_json_value, Error};
+use crate::models::ethereum_message::decode_prefixed_hex;
+use crate::models::keccak::keccak256;
+use crate::models::message::{MessageInfo, SignableMessage};
+
+/// The struct type of the domain separator.
+pub const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// A member of an EIP-712 struct type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TypedDataField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// [EIP-712] typed data, as sent to `eth_signTypedData_v4`.
+///
+/// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedDataField>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+/// Checks if a JSON value has the shape of EIP-712 typed data.
+pub fn is_typed_data(json: &Value) -> bool {
+    ["types", "primaryType", "domain", "message"]
+        .iter()
+        .all(|field| json.get(field).is_some())
+}
+
+impl TypedData {
+    /// Hashes the domain with `hashStruct(EIP712Domain, domain)`.
+    pub fn domain_separator(&self) -> Result<H256, Error> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain).map(H256)
+    }
+
+    /// Hashes the typed data with
+    /// `keccak256("\x19\x01" || domainSeparator || hashStruct(message))`.
+    ///
+    /// The message hash is omitted if the primary type is the domain.
+    pub fn signing_hash(&self) -> Result<H256, Error> {
+        let mut bytes = vec![0x19, 0x01];
+        bytes.extend_from_slice(self.domain_separator()?.as_bytes());
+        if self.primary_type != DOMAIN_TYPE {
+            bytes.extend_from_slice(&self.hash_struct(&self.primary_type, &self.message)?);
+        }
+        Ok(H256(keccak256(&bytes)))
+    }
+
+    /// Encodes a struct type and the struct types it references, such as
+    /// `Mail(Person from,Person to,string contents)Person(string name,address wallet)`.
+    pub fn encode_type(&self, name: &str) -> Result<String, Error> {
+        let mut dependencies = BTreeSet::new();
+        self.collect_dependencies(name, &mut dependencies)?;
+        dependencies.remove(name);
+
+        std::iter::once(name)
+            .chain(dependencies.iter().map(String::as_str))
+            .map(|name| {
+                let fields = self.fields(name)?;
+                let members: Vec<_> = fields
+                    .iter()
+                    .map(|field| format!("{} {}", field.kind, field.name))
+                    .collect();
+                Ok(format!("{}({})", name, members.join(",")))
+            })
+            .collect()
+    }
+
+    /// Hashes a struct with `keccak256(typeHash || encodeData(value))`.
+    pub fn hash_struct(&self, name: &str, value: &Value) -> Result<[u8; 32], Error> {
+        let mut bytes = keccak256(self.encode_type(name)?.as_bytes()).to_vec();
+        for field in self.fields(name)? {
+            let member = value.get(&field.name).unwrap_or(&Value::Null);
+            let encoded = self
+                .encode_value(&field.kind, member)
+                .map_err(|error| match error {
+                    Error::InvalidData => Error::invalid_field(&field.name, &field.kind),
+                    error => error,
+                })?;
+            bytes.extend_from_slice(&encoded);
+        }
+        Ok(keccak256(&bytes))
+    }
+
+    fn fields(&self, name: &str) -> Result<&[TypedDataField], Error> {
+        self.types
+            .get(name)
+            .map(Vec::as_slice)
+            .ok_or(Error::InvalidData)
+    }
+
+    fn collect_dependencies(
+        &self,
+        name: &str,
+        dependencies: &mut BTreeSet<String>,
+    ) -> Result<(), Error> {
+        if !dependencies.insert(name.to_owned()) {
+            return Ok(());
+        }
+        for field in self.fields(name)? {
+            let kind = base_type(&field.kind);
+            if self.types.contains_key(kind) {
+                self.collect_dependencies(kind, dependencies)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encodes a member value as a 32 byte word.
+    fn encode_value(&self, kind: &str, value: &Value) -> Result<[u8; 32], Error> {
+        if let Some(item_kind) = array_item_type(kind) {
+            let mut bytes = Vec::new();
+            for item in value.as_array().ok_or(Error::InvalidData)? {
+                bytes.extend_from_slice(&self.encode_value(item_kind, item)?);
+            }
+            return Ok(keccak256(&bytes));
+        }
+        if self.types.contains_key(kind) {
+            return self.hash_struct(kind, value);
+        }
+
+        match kind {
+            "string" => Ok(keccak256(
+                value.as_str().ok_or(Error::InvalidData)?.as_bytes(),
+            )),
+            "bytes" => Ok(keccak256(&json_bytes(value)?)),
+            "bool" => {
+                let value = value.as_bool().ok_or(Error::InvalidData)?;
+                Ok(word(U256::from(value as u8)))
+            }
+            "address" => {
+                let address: Address = value
+                    .as_str()
+                    .and_then(|address| address.parse().ok())
+                    .ok_or(Error::InvalidData)?;
+                Ok(H256::from(address).0)
+            }
+            _ if kind.starts_with("bytes") => {
+                let bytes = json_bytes(value)?;
+                if bytes.len() > 32 {
+                    return Err(Error::InvalidData);
+                }
+                let mut word = [0; 32];
+                word[..bytes.len()].copy_from_slice(&bytes);
+                Ok(word)
+            }
+            _ if kind.starts_with("uint") => Ok(word(json_integer(value, false)?)),
+            _ if kind.starts_with("int") => Ok(word(json_integer(value, true)?)),
+            _ => Err(Error::InvalidData),
+        }
+    }
+}
+
+/// Gets the item type of an array type such as `Person[]` or `uint256[2]`.
+fn array_item_type(kind: &str) -> Option<&str> {
+    kind.strip_suffix(']')
+        .and_then(|kind| kind.rfind('[').map(|index| &kind[..index]))
+}
+
+/// Gets the type of a member without any array suffixes.
+fn base_type(kind: &str) -> &str {
+    kind.split('[').next().unwrap_or(kind)
+}
+
+fn word(value: U256) -> [u8; 32] {
+    let mut word = [0; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+fn json_bytes(value: &Value) -> Result<Vec<u8>, Error> {
+    value
+        .as_str()
+        .and_then(decode_prefixed_hex)
+        .ok_or(Error::InvalidData)
+}
+
+/// Parses an integer member, which may be a JSON number or a decimal or `0x`
+/// prefixed hex string. Negative signed integers are two's complement.
+fn json_integer(value: &Value, signed: bool) -> Result<U256, Error> {
+    let (negative, magnitude) = match value {
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(number), _) => (false, U256::from(number)),
+            (None, Some(number)) => (true, U256::from(number.unsigned_abs())),
+            _ => return Err(Error::InvalidData),
+        },
+        Value::String(string) => {
+            let (negative, digits) = match string.strip_prefix('-') {
+                Some(digits) => (true, digits),
+                None => (false, string.as_str()),
+            };
+            let magnitude = match digits.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16).ok(),
+                None => U256::from_dec_str(digits).ok(),
+            }
+            .ok_or(Error::InvalidData)?;
+            (negative, magnitude)
+        }
+        _ => return Err(Error::InvalidData),
+    };
+
+    match (negative, signed) {
+        (false, _) => Ok(magnitude),
+        (true, true) => Ok((!magnitude).overflowing_add(U256::one()).0),
+        (true, false) => Err(Error::InvalidData),
+    }
+}
+
+impl crate::models::message::Message for TypedData {
+    /// Parses typed data either as a JSON object or encoded in a string.
+    fn from_json(json: Value) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        match json {
+            Value::String(string) => from_json_slice(string.as_bytes()),
+            json => from_json_value(json),
+        }
+    }
+
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        from_json_slice(bytes)
+    }
+
+    fn message_info(&self) -> MessageInfo {
+        MessageInfo::Json(serde_json::to_value(self).unwrap_or_default())
+    }
+}
+
+impl SignableMessage for TypedData {
+    fn message_hash(&self, _chain_id: u64) -> Result<Vec<u8>, Error> {
+        Ok(self.signing_hash()?.as_bytes().to_vec())
+    }
+}
@@ -0,0 +1,413 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use web3::ethabi::{self, ParamType, Token};
+use web3::types::{Address, Bytes, TransactionRequest as Web3TransactionRequest, H256, U256};
+
+use crate::models::error::{from_json_slice, from_json_value, Error};
+#[cfg(feature = "signing")]
+use crate::models::ethereum_message::hash_personal_message;
+use crate::models::keccak::keccak256;
+#[cfg(feature = "signing")]
+use crate::models::transaction::SignableTransactionRequest;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::TransactionInfo;
+
+/// The address of the v0.6 entry point.
+pub const ENTRY_POINT_V06: &str = "0x5ff137d4b0fdcd49dca30c7cf57e578a026d2789";
+
+/// The address of the v0.7 entry point.
+pub const ENTRY_POINT_V07: &str = "0x0000000071727de22e5e68d2d6f1a9c0af4a48e7";
+
+/// `execute(address,uint256,bytes)` of `SimpleAccount` and most accounts.
+const EXECUTE: [u8; 4] = [0xb6, 0x1d, 0x27, 0xf6];
+/// `execute(address,uint256,bytes,uint8)` of Kernel accounts.
+const EXECUTE_WITH_OPERATION: [u8; 4] = [0x51, 0x94, 0x54, 0x47];
+/// `executeUserOp(address,uint256,bytes,uint8)` of the Safe 4337 module.
+const SAFE_EXECUTE_USER_OP: [u8; 4] = [0x7b, 0xb3, 0x74, 0x28];
+/// `executeBatch(address[],bytes[])` of the v0.6 `SimpleAccount`.
+const EXECUTE_BATCH: [u8; 4] = [0x18, 0xdf, 0xb3, 0xc7];
+/// `executeBatch(address[],uint256[],bytes[])` of the v0.7 `SimpleAccount`.
+const EXECUTE_BATCH_WITH_VALUES: [u8; 4] = [0x47, 0xe1, 0xda, 0x2a];
+/// `executeBatch((address,uint256,bytes)[])` of Coinbase Smart Wallet.
+const EXECUTE_BATCH_OF_CALLS: [u8; 4] = [0x34, 0xfc, 0xd5, 0xbe];
+
+/// An [ERC-4337] user operation of the v0.6 entry point.
+///
+/// [ERC-4337]: https://eips.ethereum.org/EIPS/eip-4337
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationV06 {
+    pub sender: Address,
+    pub nonce: U256,
+    pub init_code: Bytes,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub paymaster_and_data: Bytes,
+    pub signature: Bytes,
+}
+
+/// An [ERC-4337] user operation of the v0.7 entry point, as sent to bundlers
+/// before it is packed.
+///
+/// [ERC-4337]: https://eips.ethereum.org/EIPS/eip-4337
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationV07 {
+    pub sender: Address,
+    pub nonce: U256,
+    /// The factory deploying the sender, if it isn't deployed yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory_data: Option<Bytes>,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    pub signature: Bytes,
+}
+
+impl UserOperationV07 {
+    /// Gets the init code, which is the factory followed by its data.
+    pub fn init_code(&self) -> Vec<u8> {
+        match self.factory {
+            Some(factory) => {
+                let mut init_code = factory.as_bytes().to_vec();
+                init_code.extend_from_slice(&self.factory_data.clone().unwrap_or_default().0);
+                init_code
+            }
+            None => vec![],
+        }
+    }
+
+    /// Gets the paymaster and data, which is the paymaster followed by its
+    /// verification and post-op gas limits, as 16 bytes each, and its data.
+    pub fn paymaster_and_data(&self) -> Vec<u8> {
+        match self.paymaster {
+            Some(paymaster) => {
+                let mut paymaster_and_data = paymaster.as_bytes().to_vec();
+                paymaster_and_data.extend_from_slice(&pack_uint128(
+                    self.paymaster_verification_gas_limit.unwrap_or_default(),
+                    self.paymaster_post_op_gas_limit.unwrap_or_default(),
+                ));
+                paymaster_and_data
+                    .extend_from_slice(&self.paymaster_data.clone().unwrap_or_default().0);
+                paymaster_and_data
+            }
+            None => vec![],
+        }
+    }
+}
+
+/// Packs two 128 bit values into 32 bytes, with `high` first.
+fn pack_uint128(high: U256, low: U256) -> [u8; 32] {
+    let mut packed = [0; 32];
+    (high << 128 | low & (U256::MAX >> 128)).to_big_endian(&mut packed);
+    packed
+}
+
+/// An [ERC-4337] user operation, in the layout of its entry point version.
+///
+/// [ERC-4337]: https://eips.ethereum.org/EIPS/eip-4337
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum UserOperation {
+    V06(UserOperationV06),
+    V07(UserOperationV07),
+}
+
+impl UserOperation {
+    /// Parses a user operation, as v0.6 when it has an `initCode` or
+    /// `paymasterAndData` field and as v0.7 otherwise.
+    pub fn from_json(json: Value) -> Result<Self, Error> {
+        if json.get("initCode").is_some() || json.get("paymasterAndData").is_some() {
+            Ok(Self::V06(from_json_value(json)?))
+        } else {
+            Ok(Self::V07(from_json_value(json)?))
+        }
+    }
+
+    pub fn sender(&self) -> Address {
+        match self {
+            Self::V06(operation) => operation.sender,
+            Self::V07(operation) => operation.sender,
+        }
+    }
+
+    pub fn call_data(&self) -> &Bytes {
+        match self {
+            Self::V06(operation) => &operation.call_data,
+            Self::V07(operation) => &operation.call_data,
+        }
+    }
+
+    /// Decodes the calls the sender executes from its `execute` or
+    /// `executeBatch` call data, as transaction requests sent by the sender.
+    ///
+    /// Returns `None` for call data of other methods, and for delegate calls.
+    pub fn calls(&self) -> Option<Vec<Web3TransactionRequest>> {
+        let call_data = &self.call_data().0;
+        let selector = call_data.get(..4)?;
+        let input = &call_data[4..];
+        let call = |to: &Token, value: &Token, data: &Token| {
+            Some(Web3TransactionRequest {
+                from: self.sender(),
+                to: Some(to.clone().into_address()?),
+                value: Some(value.clone().into_uint()?),
+                data: Some(data.clone().into_bytes()?.into()),
+                ..Default::default()
+            })
+        };
+        let array = |kind: ParamType| ParamType::Array(Box::new(kind));
+
+        if selector == EXECUTE {
+            let tokens = ethabi::decode(
+                &[ParamType::Address, ParamType::Uint(256), ParamType::Bytes],
+                input,
+            )
+            .ok()?;
+            Some(vec![call(&tokens[0], &tokens[1], &tokens[2])?])
+        } else if selector == EXECUTE_WITH_OPERATION || selector == SAFE_EXECUTE_USER_OP {
+            let tokens = ethabi::decode(
+                &[
+                    ParamType::Address,
+                    ParamType::Uint(256),
+                    ParamType::Bytes,
+                    ParamType::Uint(8),
+                ],
+                input,
+            )
+            .ok()?;
+            if !tokens[3].clone().into_uint()?.is_zero() {
+                return None;
+            }
+            Some(vec![call(&tokens[0], &tokens[1], &tokens[2])?])
+        } else if selector == EXECUTE_BATCH {
+            let tokens =
+                ethabi::decode(&[array(ParamType::Address), array(ParamType::Bytes)], input)
+                    .ok()?;
+            let (to, data) = (
+                tokens[0].clone().into_array()?,
+                tokens[1].clone().into_array()?,
+            );
+            // An empty array of data calls each address without data.
+            let data = if data.is_empty() {
+                vec![Token::Bytes(vec![]); to.len()]
+            } else {
+                data
+            };
+            if to.len() != data.len() {
+                return None;
+            }
+            let zero = Token::Uint(U256::zero());
+            to.iter()
+                .zip(&data)
+                .map(|(to, data)| call(to, &zero, data))
+                .collect()
+        } else if selector == EXECUTE_BATCH_WITH_VALUES {
+            let tokens = ethabi::decode(
+                &[
+                    array(ParamType::Address),
+                    array(ParamType::Uint(256)),
+                    array(ParamType::Bytes),
+                ],
+                input,
+            )
+            .ok()?;
+            let to = tokens[0].clone().into_array()?;
+            let values = tokens[1].clone().into_array()?;
+            let data = tokens[2].clone().into_array()?;
+            // An empty array of values sends no ether with the calls.
+            let values = if values.is_empty() {
+                vec![Token::Uint(U256::zero()); to.len()]
+            } else {
+                values
+            };
+            if to.len() != values.len() || to.len() != data.len() {
+                return None;
+            }
+            (0..to.len())
+                .map(|i| call(&to[i], &values[i], &data[i]))
+                .collect()
+        } else if selector == EXECUTE_BATCH_OF_CALLS {
+            let tuple = ParamType::Tuple(vec![
+                ParamType::Address,
+                ParamType::Uint(256),
+                ParamType::Bytes,
+            ]);
+            let tokens = ethabi::decode(&[array(tuple)], input).ok()?;
+            tokens[0]
+                .clone()
+                .into_array()?
+                .into_iter()
+                .map(|tuple| match tuple {
+                    Token::Tuple(tuple) => match tuple.as_slice() {
+                        [to, value, data] => call(to, value, data),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect()
+        } else {
+            None
+        }
+    }
+
+    /// Computes the `userOpHash` of the operation for an entry point on a
+    /// chain, which is the hash of the operation without its signature,
+    /// `entry_point` and `chain_id`.
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> H256 {
+        let hash = |bytes: &[u8]| Token::FixedBytes(keccak256(bytes).to_vec());
+        let fields = match self {
+            Self::V06(operation) => vec![
+                Token::Address(operation.sender),
+                Token::Uint(operation.nonce),
+                hash(&operation.init_code.0),
+                hash(&operation.call_data.0),
+                Token::Uint(operation.call_gas_limit),
+                Token::Uint(operation.verification_gas_limit),
+                Token::Uint(operation.pre_verification_gas),
+                Token::Uint(operation.max_fee_per_gas),
+                Token::Uint(operation.max_priority_fee_per_gas),
+                hash(&operation.paymaster_and_data.0),
+            ],
+            Self::V07(operation) => vec![
+                Token::Address(operation.sender),
+                Token::Uint(operation.nonce),
+                hash(&operation.init_code()),
+                hash(&operation.call_data.0),
+                Token::FixedBytes(
+                    pack_uint128(operation.verification_gas_limit, operation.call_gas_limit)
+                        .to_vec(),
+                ),
+                Token::Uint(operation.pre_verification_gas),
+                Token::FixedBytes(
+                    pack_uint128(
+                        operation.max_priority_fee_per_gas,
+                        operation.max_fee_per_gas,
+                    )
+                    .to_vec(),
+                ),
+                hash(&operation.paymaster_and_data()),
+            ],
+        };
+        H256(keccak256(&ethabi::encode(&[
+            hash(&ethabi::encode(&fields)),
+            Token::Address(entry_point),
+            Token::Uint(chain_id.into()),
+        ])))
+    }
+
+    /// Sets the signature of the operation.
+    pub fn set_signature(&mut self, signature: Bytes) {
+        match self {
+            Self::V06(operation) => operation.signature = signature,
+            Self::V07(operation) => operation.signature = signature,
+        }
+    }
+}
+
+/// A user operation to be sent to a bundler for an entry point, as the
+/// parameters of `eth_sendUserOperation`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserOperationRequest {
+    pub user_operation: UserOperation,
+    pub entry_point: Address,
+}
+
+impl UserOperationRequest {
+    /// Computes the `userOpHash` of the operation on a chain.
+    pub fn user_op_hash(&self, chain_id: u64) -> H256 {
+        self.user_operation.hash(self.entry_point, chain_id)
+    }
+}
+
+impl TransactionRequest for UserOperationRequest {
+    /// Parses the `[userOperation, entryPoint]` parameters of
+    /// `eth_sendUserOperation`.
+    fn from_json(json: Value) -> Result<Self, Error> {
+        let (user_operation, entry_point): (Value, Address) = from_json_value(json)?;
+        Ok(Self {
+            user_operation: UserOperation::from_json(user_operation)?,
+            entry_point,
+        })
+    }
+
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_json(from_json_slice(bytes)?)
+    }
+
+    /// Classifies the calls executed by the sender, as a batch when there
+    /// are several, or else the call data as a call of the sender by the
+    /// entry point.
+    fn transaction_info(&self) -> TransactionInfo {
+        if let Some(mut calls) = self.user_operation.calls() {
+            if calls.len() == 1 {
+                return calls.remove(0).transaction_info();
+            }
+            return TransactionInfo::Batch {
+                calls: calls.iter().map(|call| call.transaction_info()).collect(),
+            };
+        }
+
+        Web3TransactionRequest {
+            from: self.entry_point,
+            to: Some(self.user_operation.sender()),
+            data: Some(self.user_operation.call_data().clone()),
+            ..Default::default()
+        }
+        .transaction_info()
+    }
+}
+
+#[cfg(feature = "signing")]
+impl SignableTransactionRequest for UserOperationRequest {
+    /// Returns the `userOpHash` as a personal message, which is what
+    /// ECDSA-validating accounts such as `SimpleAccount` recover the owner
+    /// from.
+    fn message_hash(&self, chain_id: u64) -> Result<Vec<u8>, Error> {
+        Ok(
+            hash_personal_message(self.user_op_hash(chain_id).as_bytes())
+                .as_bytes()
+                .to_vec(),
+        )
+    }
+
+    /// Encodes the user operation with its 65 byte signature as JSON, ready to
+    /// be sent with `eth_sendUserOperation`.
+    fn encode_signed(
+        &self,
+        _chain_id: u64,
+        signature: &[u8],
+        recovery_id: u64,
+    ) -> Result<Vec<u8>, Error> {
+        if signature.len() != 64 || recovery_id > 1 {
+            return Err(Error::InvalidData);
+        }
+        let mut signature = signature.to_vec();
+        signature.push(27 + recovery_id as u8);
+
+        let mut user_operation = self.user_operation.clone();
+        user_operation.set_signature(signature.into());
+        Ok(serde_json::to_vec(&user_operation)?)
+    }
+
+    /// Returns the `userOpHash`, which bundlers identify the operation by.
+    fn transaction_hash(&self, chain_id: u64, _raw: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(self.user_op_hash(chain_id).as_bytes().to_vec())
+    }
+}
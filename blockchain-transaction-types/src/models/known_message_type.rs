@@ -1,14 +1,21 @@
 use crate::models::coin_type::CoinType;
 use crate::models::error::Error;
+use crate::models::message::Message;
 
 pub enum KnownMessageType {
     Ethereum(crate::models::ethereum_message::Message),
+    EthereumTypedData(crate::models::ethereum_typed_data::TypedData),
+    EthereumSignIn(Box<crate::models::ethereum_siwe_message::SiweMessage>),
+    Solana(solana_sdk::offchain_message::OffchainMessage),
 }
 
 impl KnownMessageType {
     pub fn message(&self) -> &dyn crate::models::message::Message {
         match self {
             KnownMessageType::Ethereum(message) => message,
+            KnownMessageType::EthereumTypedData(typed_data) => typed_data,
+            KnownMessageType::EthereumSignIn(message) => message.as_ref(),
+            KnownMessageType::Solana(message) => message,
         }
     }
 
@@ -16,6 +23,9 @@ impl KnownMessageType {
     pub fn signable_message(&self) -> Box<dyn crate::models::message::SignableMessage> {
         match self {
             KnownMessageType::Ethereum(message) => Box::new(message.clone()),
+            KnownMessageType::EthereumTypedData(typed_data) => Box::new(typed_data.clone()),
+            KnownMessageType::EthereumSignIn(message) => Box::new(message.as_ref().clone()),
+            KnownMessageType::Solana(message) => Box::new(message.clone()),
         }
     }
 }
@@ -27,12 +37,45 @@ impl KnownMessageType {
         _chain_id: Option<u64>,
     ) -> Result<KnownMessageType, Error> {
         match coin_type {
+            CoinType::Ethereum if is_ethereum_typed_data(&value) => {
+                let typed_data = crate::models::ethereum_typed_data::TypedData::from_json(value)?;
+                Ok(KnownMessageType::EthereumTypedData(typed_data))
+            }
             CoinType::Ethereum => {
-                let transaction: crate::models::ethereum_message::Message =
-                    serde_json::from_value(value)?;
-                Ok(KnownMessageType::Ethereum(transaction))
+                let message = crate::models::ethereum_message::Message::from_json(value)?;
+                match ethereum_sign_in_message(&message) {
+                    Some(message) => Ok(KnownMessageType::EthereumSignIn(Box::new(message))),
+                    None => Ok(KnownMessageType::Ethereum(message)),
+                }
+            }
+            CoinType::Solana => {
+                let message = solana_sdk::offchain_message::OffchainMessage::from_json(value)?;
+                Ok(KnownMessageType::Solana(message))
             }
             _ => Err(Error::InvalidCoinType),
         }
     }
 }
+
+/// Detects EIP-712 typed data, either as a JSON object or encoded in a string
+/// as sent to `eth_signTypedData_v4`.
+fn is_ethereum_typed_data(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::String(string) => serde_json::from_str(string)
+            .map(|json| crate::models::ethereum_typed_data::is_typed_data(&json))
+            .unwrap_or_default(),
+        json => crate::models::ethereum_typed_data::is_typed_data(json),
+    }
+}
+
+/// Parses a `personal_sign` message as a Sign-In with Ethereum message, which
+/// may be hex encoded.
+fn ethereum_sign_in_message(
+    message: &crate::models::ethereum_message::Message,
+) -> Option<crate::models::ethereum_siwe_message::SiweMessage> {
+    let crate::models::ethereum_message::Message::String(string) = message;
+    match crate::models::ethereum_message::decode_prefixed_hex(string) {
+        Some(bytes) => crate::models::ethereum_siwe_message::SiweMessage::from_raw(&bytes).ok(),
+        None => string.parse().ok(),
+    }
+}
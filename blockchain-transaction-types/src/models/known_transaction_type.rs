@@ -1,54 +1,214 @@
+use crate::models::account::AccountId;
 use crate::models::coin_type::CoinType;
-use crate::models::error::Error;
+use crate::models::error::{from_json_value, Error};
+use crate::models::ethereum_transaction::{EIP_4844_TRANSACTION_TYPE, EIP_7702_TRANSACTION_TYPE};
 #[cfg(feature = "signing")]
 use crate::models::transaction::SignableTransactionRequest;
 use crate::models::transaction::TransactionRequest;
 
 pub enum KnownTransactionRequestType {
+    Bitcoin(crate::models::bitcoin_transaction::Psbt),
+    Cosmos(crate::models::cosmos_transaction::CosmosTransactionRequest),
     Ethereum(web3::types::TransactionRequest),
+    EthereumBatch(crate::models::ethereum_send_calls::SendCallsRequest),
+    EthereumBlob(crate::models::ethereum_blob_transaction::BlobTransactionRequest),
+    EthereumSetCode(crate::models::ethereum_set_code_transaction::SetCodeTransactionRequest),
     Solana(solana_sdk::transaction::Transaction),
+    SolanaVersioned(solana_sdk::transaction::VersionedTransaction),
 }
 
 impl KnownTransactionRequestType {
     pub fn transaction_request(&self) -> &dyn TransactionRequest {
         match self {
+            Self::Bitcoin(tx) => tx,
+            Self::Cosmos(tx) => tx,
             Self::Ethereum(tx) => tx,
-            Self::Solana(_tx) => unimplemented!("Cant handle Solana yet"),
+            Self::EthereumBatch(tx) => tx,
+            Self::EthereumBlob(tx) => tx,
+            Self::EthereumSetCode(tx) => tx,
+            Self::Solana(tx) => tx,
+            Self::SolanaVersioned(tx) => tx,
         }
     }
 
-    // pub fn sender(&self) -> &dyn Account {
-    //     match self {
-    //         Self::Ethereum(tx) => &tx.from,
-    //         Self::Solana(_tx) => unimplemented!("Cant handle Solana yet")
-    //     }
-    // }
+    /// Gets the account initiating the transaction.
+    ///
+    /// For Bitcoin, this is the output script spent by the first input, and
+    /// `None` if that output isn't part of the PSBT. For Cosmos, this is the
+    /// sender of the first `MsgSend`. For EIP-5792 batches, this is `None`
+    /// when the wallet chooses the account. For Solana, this is the first signer,
+    /// which is also the fee payer.
+    pub fn sender(&self) -> Option<AccountId> {
+        match self {
+            Self::Bitcoin(tx) => tx
+                .spent_output(0)
+                .ok()
+                .map(|output| AccountId::Bitcoin(output.script_pubkey)),
+            Self::Cosmos(tx) => tx
+                .msg_sends()
+                .into_iter()
+                .next()
+                .map(|msg_send| AccountId::Cosmos(msg_send.from_address)),
+            Self::Ethereum(tx) => Some(AccountId::Ethereum(tx.from)),
+            Self::EthereumBatch(tx) => tx.from.map(AccountId::Ethereum),
+            Self::EthereumBlob(tx) => Some(AccountId::Ethereum(tx.request.from)),
+            Self::EthereumSetCode(tx) => Some(AccountId::Ethereum(tx.request.from)),
+            Self::Solana(tx) => tx
+                .message
+                .account_keys
+                .first()
+                .copied()
+                .map(AccountId::Solana),
+            Self::SolanaVersioned(tx) => tx
+                .message
+                .static_account_keys()
+                .first()
+                .copied()
+                .map(AccountId::Solana),
+        }
+    }
+
+    /// Gets the account paying the fees of the transaction.
+    ///
+    /// Bitcoin fees are paid from the spent inputs, so the fee payer is the
+    /// sender, as is the case for Ethereum. Cosmos fees are paid by the
+    /// payer of the fee when set.
+    pub fn fee_payer(&self) -> Option<AccountId> {
+        match self {
+            Self::Cosmos(tx) => match &tx.auth_info.fee {
+                Some(fee) if !fee.payer.is_empty() => Some(AccountId::Cosmos(fee.payer.clone())),
+                _ => self.sender(),
+            },
+            Self::Bitcoin(_)
+            | Self::Ethereum(_)
+            | Self::EthereumBatch(_)
+            | Self::EthereumBlob(_)
+            | Self::EthereumSetCode(_)
+            | Self::Solana(_)
+            | Self::SolanaVersioned(_) => self.sender(),
+        }
+    }
 
+    /// Gets the request as a single transaction to sign.
+    ///
+    /// Fails with [`Error::InvalidData`] for EIP-5792 batches, whose calls
+    /// are signed as transactions chosen by the wallet.
     #[cfg(feature = "signing")]
-    pub fn signable_transaction_request(self) -> Box<dyn SignableTransactionRequest> {
+    pub fn signable_transaction_request(
+        self,
+    ) -> Result<Box<dyn SignableTransactionRequest>, Error> {
         match self {
-            Self::Ethereum(tx) => Box::new(tx),
-            Self::Solana(_tx) => unimplemented!("Can't sign Solana yet"),
+            Self::Bitcoin(tx) => Ok(Box::new(tx)),
+            Self::Cosmos(tx) => Ok(Box::new(tx)),
+            Self::Ethereum(tx) => Ok(Box::new(tx)),
+            Self::EthereumBatch(_) => Err(Error::InvalidData),
+            Self::EthereumBlob(tx) => Ok(Box::new(tx)),
+            Self::EthereumSetCode(tx) => Ok(Box::new(tx)),
+            Self::Solana(tx) => Ok(Box::new(tx)),
+            Self::SolanaVersioned(tx) => Ok(Box::new(tx)),
         }
     }
 }
 
 impl KnownTransactionRequestType {
+    /// Parses a transaction request of `coin_type`, to be signed for
+    /// `chain_id`.
+    ///
+    /// Ethereum requests with `calls` are parsed as the batch of an EIP-5792
+    /// `wallet_sendCalls` request.
+    ///
+    /// Fails with [`Error::ChainIdMismatch`] when an Ethereum request has a
+    /// `chainId` other than `chain_id`. Use
+    /// [`KnownTransactionRequestType::from_json_ignoring_chain_id`] to sign it
+    /// for another chain anyway.
     pub fn from_json(
         value: serde_json::Value,
         coin_type: CoinType,
-        _chain_id: Option<u64>,
+        chain_id: Option<u64>,
+    ) -> Result<KnownTransactionRequestType, Error> {
+        if let (CoinType::Ethereum, Some(expected)) = (coin_type, chain_id) {
+            match ethereum_chain_id(&value)? {
+                Some(payload) if payload != expected => {
+                    return Err(Error::ChainIdMismatch { payload, expected })
+                }
+                _ => {}
+            }
+        }
+        Self::from_json_ignoring_chain_id(value, coin_type)
+    }
+
+    /// Parses a transaction request of `coin_type`, without checking its
+    /// chain ID.
+    pub fn from_json_ignoring_chain_id(
+        value: serde_json::Value,
+        coin_type: CoinType,
     ) -> Result<KnownTransactionRequestType, Error> {
         match coin_type {
-            CoinType::Ethereum => {
-                let transaction = serde_json::from_value(value)?;
-                Ok(KnownTransactionRequestType::Ethereum(transaction))
+            CoinType::Bitcoin => {
+                let transaction = crate::models::bitcoin_transaction::Psbt::from_json(value)?;
+                Ok(KnownTransactionRequestType::Bitcoin(transaction))
+            }
+            CoinType::Cosmos => {
+                let transaction =
+                    crate::models::cosmos_transaction::CosmosTransactionRequest::from_json(value)?;
+                Ok(KnownTransactionRequestType::Cosmos(transaction))
             }
-            CoinType::Solana => {
-                let transaction = serde_json::from_value(value)?;
-                Ok(KnownTransactionRequestType::Solana(transaction))
+            CoinType::Ethereum if value["calls"].is_array() => {
+                let request =
+                    crate::models::ethereum_send_calls::SendCallsRequest::from_json(value)?;
+                Ok(KnownTransactionRequestType::EthereumBatch(request))
             }
+            CoinType::Ethereum => match ethereum_transaction_type(&value) {
+                Some(EIP_4844_TRANSACTION_TYPE) => {
+                    let transaction =
+                        crate::models::ethereum_blob_transaction::BlobTransactionRequest::from_json(
+                            value,
+                        )?;
+                    Ok(KnownTransactionRequestType::EthereumBlob(transaction))
+                }
+                Some(EIP_7702_TRANSACTION_TYPE) => {
+                    let transaction = crate::models::ethereum_set_code_transaction::SetCodeTransactionRequest::from_json(value)?;
+                    Ok(KnownTransactionRequestType::EthereumSetCode(transaction))
+                }
+                _ => {
+                    let transaction = from_json_value(value)?;
+                    Ok(KnownTransactionRequestType::Ethereum(transaction))
+                }
+            },
+            CoinType::Solana => match from_json_value(value.clone()) {
+                Ok(transaction) => Ok(KnownTransactionRequestType::Solana(transaction)),
+                Err(_) => {
+                    let transaction = from_json_value(value)?;
+                    Ok(KnownTransactionRequestType::SolanaVersioned(transaction))
+                }
+            },
             _ => Err(Error::InvalidCoinType),
         }
     }
 }
+
+/// Gets the chain ID of an Ethereum transaction request JSON, which may be a
+/// number or a hex or decimal string.
+///
+/// Fails if the chain ID is set but malformed or larger than a `u64`, so
+/// that the chain ID check can't be skipped.
+fn ethereum_chain_id(value: &serde_json::Value) -> Result<Option<u64>, Error> {
+    let chain_id = match &value["chainId"] {
+        serde_json::Value::Null => return Ok(None),
+        serde_json::Value::String(chain_id) => match chain_id.strip_prefix("0x") {
+            Some(chain_id) => u64::from_str_radix(chain_id, 16).ok(),
+            None => chain_id.parse().ok(),
+        },
+        chain_id => chain_id.as_u64(),
+    };
+    chain_id
+        .map(Some)
+        .ok_or_else(|| Error::invalid_field("chainId", "a number or a hex or decimal string"))
+}
+
+/// Gets the type of an Ethereum transaction request JSON.
+fn ethereum_transaction_type(value: &serde_json::Value) -> Option<u64> {
+    value["type"].as_str().and_then(|transaction_type| {
+        u64::from_str_radix(transaction_type.trim_start_matches("0x"), 16).ok()
+    })
+}
@@ -1,4 +1,6 @@
 use crate::models::error::Error;
+pub use crate::models::signer::SignError;
+use crate::models::signer::{sign_hash, Signer};
 use std::future::Future;
 
 pub trait Message {
@@ -13,8 +15,16 @@ pub trait Message {
     fn message_info(&self) -> MessageInfo;
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum MessageInfo {
+    /// A message signed as its UTF-8 text.
     String(String),
+    /// A `0x` prefixed hex message signed as its decoded bytes, with their
+    /// text if they are valid UTF-8.
+    Bytes {
+        hex: String,
+        text: Option<String>,
+    },
     Json(serde_json::Value),
 }
 
@@ -22,14 +32,6 @@ pub trait SignableMessage: Message {
     fn message_hash(&self, chain_id: u64) -> Result<Vec<u8>, Error>;
 }
 
-use thiserror::Error as ThisError;
-
-#[derive(Debug, ThisError)]
-pub enum SignError<E> {
-    Hash(Error),
-    Sign(E),
-}
-
 impl dyn SignableMessage {
     pub async fn sign_message<
         E,
@@ -48,4 +50,13 @@ impl dyn SignableMessage {
             .map_err(|error| SignError::<E>::Sign(error))?;
         Ok((signature, recovery))
     }
+
+    /// Signs the message hash with `signer`.
+    pub async fn sign_with<S: Signer>(
+        &self,
+        chain_id: u64,
+        signer: &S,
+    ) -> Result<(Vec<u8>, u64), SignError<S::Error>> {
+        sign_hash(self.message_hash(chain_id), signer).await
+    }
 }
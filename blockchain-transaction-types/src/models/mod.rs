@@ -1,16 +1,68 @@
+#[cfg(feature = "ethereum")]
+pub mod abi_registry;
 pub mod account;
+#[cfg(feature = "bitcoin")]
+pub mod bitcoin_transaction;
+pub mod chain_info;
 pub mod coin_type;
+#[cfg(feature = "cosmos")]
+pub mod cosmos_transaction;
+#[cfg(feature = "ens-resolver")]
+pub mod ens_resolver;
 pub mod error;
 #[cfg(feature = "ethereum")]
+pub mod ethereum_access_list;
+#[cfg(feature = "ethereum")]
 pub mod ethereum_account;
 #[cfg(feature = "ethereum")]
+pub mod ethereum_blob_transaction;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_block;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_calldata;
+#[cfg(feature = "fee-estimator")]
+pub mod ethereum_fee_estimator;
+#[cfg(feature = "ethereum")]
 pub mod ethereum_message;
 #[cfg(feature = "ethereum")]
+pub mod ethereum_receipt;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_replacement;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_send_calls;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_set_code_transaction;
+#[cfg(feature = "simulator")]
+pub mod ethereum_simulator;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_siwe_message;
+#[cfg(feature = "ethereum")]
 pub mod ethereum_transaction;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_typed_data;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_user_operation;
+#[cfg(feature = "ethereum")]
+pub mod keccak;
 #[cfg(feature = "all-chains")]
 pub mod known_message_type;
 #[cfg(feature = "all-chains")]
 pub mod known_transaction_type;
 pub mod message;
+#[cfg(feature = "price-provider")]
+pub mod price_provider;
+#[cfg(feature = "ethereum")]
+pub mod risk_assessment;
+#[cfg(feature = "selector-resolver")]
+pub mod selector_resolver;
+pub mod signer;
+#[cfg(feature = "solana")]
+pub mod solana_message;
+#[cfg(feature = "solana")]
+pub mod solana_transaction;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+#[cfg(feature = "token-info-provider")]
+pub mod token_info_provider;
 pub mod transaction;
 pub mod transaction_info;
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use web3::types::{Address, U256};
+
+use crate::models::chain_info::chain_info;
+use crate::models::error::Error;
+use crate::models::transaction_info::{FiatValue, TokenInfo, TransactionInfo};
+
+/// Estimates the fiat value of token amounts.
+#[async_trait]
+pub trait PriceProvider {
+    /// Estimates the value of `amount`, in the smallest unit, of the token
+    /// contract `token`, or of the native currency of the chain when `token`
+    /// is `None`.
+    ///
+    /// Returns `None` for tokens without a known price.
+    async fn fiat_value(
+        &self,
+        chain_id: u64,
+        token: Option<Address>,
+        token_info: Option<&TokenInfo>,
+        amount: U256,
+    ) -> Result<Option<FiatValue>, Error>;
+}
+
+/// Fills in the `fiat_value` of the fungible token transfers, mints and
+/// burns of `info`, where `token` is the called token contract, or `None` for
+/// transfers of the native currency.
+///
+/// Transfers executed by a Safe are priced in the token the Safe calls, or in
+/// the native currency when the Safe sends a value. Pricing errors are
+/// ignored, leaving `fiat_value` as `None`.
+pub async fn attach_fiat_values(
+    info: &mut TransactionInfo,
+    chain_id: u64,
+    token: Option<Address>,
+    provider: &(dyn PriceProvider + Sync),
+) {
+    match info {
+        TransactionInfo::TokenTransfer {
+            amount,
+            token_id: None,
+            token_info,
+            fiat_value,
+            ..
+        }
+        | TransactionInfo::TokenMint {
+            amount,
+            token_id: None,
+            token_info,
+            fiat_value,
+            ..
+        }
+        | TransactionInfo::TokenBurn {
+            amount,
+            token_id: None,
+            token_info,
+            fiat_value,
+            ..
+        } => {
+            *fiat_value = price(provider, chain_id, token, token_info.as_ref(), amount).await;
+        }
+        TransactionInfo::TokenBatchTransfer { transfers }
+        | TransactionInfo::TokenBatchMint { transfers }
+        | TransactionInfo::TokenBatchBurn { transfers } => {
+            for transfer in transfers
+                .iter_mut()
+                .filter(|transfer| transfer.token_id.is_none())
+            {
+                transfer.fiat_value = price(
+                    provider,
+                    chain_id,
+                    token,
+                    transfer.token_info.as_ref(),
+                    &transfer.amount,
+                )
+                .await;
+            }
+        }
+        TransactionInfo::SafeTransaction {
+            to, value, info, ..
+        } => {
+            let token = match parse_amount(value) {
+                Some(value) if !value.is_zero() => None,
+                _ => Address::from_str(to).ok(),
+            };
+            attach_fiat_values_boxed(info, chain_id, token, provider).await;
+        }
+        _ => {}
+    }
+}
+
+/// Boxes the recursive future of a Safe transaction.
+fn attach_fiat_values_boxed<'a>(
+    info: &'a mut TransactionInfo,
+    chain_id: u64,
+    token: Option<Address>,
+    provider: &'a (dyn PriceProvider + Sync),
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send + 'a>> {
+    Box::pin(attach_fiat_values(info, chain_id, token, provider))
+}
+
+async fn price(
+    provider: &(dyn PriceProvider + Sync),
+    chain_id: u64,
+    token: Option<Address>,
+    token_info: Option<&TokenInfo>,
+    amount: &str,
+) -> Option<FiatValue> {
+    let amount = parse_amount(amount)?;
+    provider
+        .fiat_value(chain_id, token, token_info, amount)
+        .await
+        .ok()
+        .flatten()
+}
+
+fn parse_amount(amount: &str) -> Option<U256> {
+    U256::from_str_radix(amount.trim_start_matches("0x"), 16).ok()
+}
+
+/// Prices tokens at fixed unit prices, such as stablecoins at their peg or
+/// native currencies at a periodically refreshed rate.
+///
+/// Tokens are converted from their smallest unit with the decimals of their
+/// token info, and native currencies with the decimals of their chain.
+#[derive(Clone, Debug)]
+pub struct UnitPriceProvider {
+    currency: String,
+    native_prices: HashMap<u64, f64>,
+    token_prices: HashMap<(u64, Address), f64>,
+}
+
+impl UnitPriceProvider {
+    /// Creates a provider without any prices, in the ISO 4217 `currency`.
+    pub fn new(currency: &str) -> Self {
+        Self {
+            currency: currency.to_owned(),
+            native_prices: HashMap::new(),
+            token_prices: HashMap::new(),
+        }
+    }
+
+    /// Sets the price of one unit of the native currency of `chain_id`.
+    pub fn set_native_price(&mut self, chain_id: u64, price: f64) {
+        self.native_prices.insert(chain_id, price);
+    }
+
+    /// Sets the price of one whole token of the contract `token`.
+    pub fn set_token_price(&mut self, chain_id: u64, token: Address, price: f64) {
+        self.token_prices.insert((chain_id, token), price);
+    }
+}
+
+#[async_trait]
+impl PriceProvider for UnitPriceProvider {
+    async fn fiat_value(
+        &self,
+        chain_id: u64,
+        token: Option<Address>,
+        token_info: Option<&TokenInfo>,
+        amount: U256,
+    ) -> Result<Option<FiatValue>, Error> {
+        let (price, decimals) = match token {
+            Some(token) => match (self.token_prices.get(&(chain_id, token)), token_info) {
+                (Some(price), Some(token_info)) => (*price, token_info.decimals),
+                _ => return Ok(None),
+            },
+            None => match self.native_prices.get(&chain_id) {
+                Some(price) => {
+                    let chain = chain_info(chain_id).ok_or(Error::UnknownChain(chain_id))?;
+                    (*price, chain.native_decimals)
+                }
+                None => return Ok(None),
+            },
+        };
+
+        let units: f64 = amount.to_string().parse().unwrap_or_default();
+        Ok(Some(FiatValue {
+            currency: self.currency.clone(),
+            amount: units / 10f64.powi(decimals.into()) * price,
+        }))
+    }
+}
@@ -0,0 +1,168 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use web3::types::{Address, U256};
+
+use crate::models::ethereum_typed_data::TypedData;
+use crate::models::transaction_info::TransactionInfo;
+
+/// The dead address commonly used to burn tokens.
+const DEAD_ADDRESS: &str = "0x000000000000000000000000000000000000dead";
+
+/// A heuristic warning about a transaction or message.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RiskFlag {
+    /// An approval of all tokens, or of the maximum allowance.
+    UnlimitedApproval { spender: String },
+    /// An approval to an address without code, which can't be a contract
+    /// such as a marketplace or router.
+    ApprovalToExternallyOwnedAccount { spender: String },
+    /// A transfer to the zero address or the dead address, which burns the
+    /// tokens.
+    TransferToBurnAddress { to: String },
+    /// A contract call that couldn't be decoded with a known ABI.
+    UnknownContractCall { function: Option<String> },
+    /// Typed data whose domain is for another chain than the one signing.
+    #[serde(rename_all = "camelCase")]
+    DomainChainMismatch { chain_id: u64, domain_chain_id: u64 },
+    /// Typed data whose domain is for another contract than expected.
+    #[serde(rename_all = "camelCase")]
+    DomainContractMismatch {
+        expected: String,
+        verifying_contract: String,
+    },
+}
+
+/// The risk flags of a transaction or message, for wallets to warn about
+/// before signing.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct RiskAssessment {
+    pub flags: Vec<RiskFlag>,
+}
+
+impl RiskAssessment {
+    /// Checks if no risks were found.
+    pub fn is_empty(&self) -> bool {
+        self.flags.is_empty()
+    }
+}
+
+/// Flags the risks of a classified transaction, including the calls of Safe
+/// transactions and batches.
+///
+/// `is_contract` tells whether an address has code, or `None` when unknown,
+/// in which case approvals to it aren't flagged.
+pub fn assess_transaction(
+    info: &TransactionInfo,
+    is_contract: &dyn Fn(&Address) -> Option<bool>,
+) -> RiskAssessment {
+    let mut assessment = RiskAssessment::default();
+    collect_transaction_flags(info, is_contract, &mut assessment.flags);
+    assessment
+}
+
+fn collect_transaction_flags(
+    info: &TransactionInfo,
+    is_contract: &dyn Fn(&Address) -> Option<bool>,
+    flags: &mut Vec<RiskFlag>,
+) {
+    match info {
+        TransactionInfo::TokenTransfer { to, .. } if is_burn_address(to) => {
+            flags.push(RiskFlag::TransferToBurnAddress { to: to.clone() });
+        }
+        TransactionInfo::TokenBurn { .. } | TransactionInfo::TokenBatchBurn { .. } => {
+            flags.push(RiskFlag::TransferToBurnAddress {
+                to: format!("{:#x}", Address::zero()),
+            });
+        }
+        TransactionInfo::TokenBatchTransfer { transfers } => {
+            if let Some(transfer) = transfers
+                .iter()
+                .find(|transfer| is_burn_address(&transfer.to))
+            {
+                flags.push(RiskFlag::TransferToBurnAddress {
+                    to: transfer.to.clone(),
+                });
+            }
+        }
+        TransactionInfo::Approval {
+            spender,
+            is_unlimited,
+            ..
+        } => {
+            if *is_unlimited {
+                flags.push(RiskFlag::UnlimitedApproval {
+                    spender: spender.clone(),
+                });
+            }
+            let externally_owned = Address::from_str(spender)
+                .ok()
+                .and_then(|spender| is_contract(&spender))
+                == Some(false);
+            if externally_owned {
+                flags.push(RiskFlag::ApprovalToExternallyOwnedAccount {
+                    spender: spender.clone(),
+                });
+            }
+        }
+        TransactionInfo::Unknown { function, .. } => {
+            flags.push(RiskFlag::UnknownContractCall {
+                function: function.clone(),
+            });
+        }
+        TransactionInfo::SafeTransaction { info, .. } => {
+            collect_transaction_flags(info, is_contract, flags);
+        }
+        TransactionInfo::Batch { calls } => {
+            for call in calls {
+                collect_transaction_flags(call, is_contract, flags);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_burn_address(address: &str) -> bool {
+    Address::from_str(address).ok() == Some(Address::zero())
+        || address.eq_ignore_ascii_case(DEAD_ADDRESS)
+}
+
+/// Flags typed data signed on `chain_id` whose domain is for another chain,
+/// or for another contract than `expected_contract` when it is known.
+pub fn assess_typed_data(
+    typed_data: &TypedData,
+    chain_id: u64,
+    expected_contract: Option<&Address>,
+) -> RiskAssessment {
+    let mut flags = Vec::new();
+
+    let domain_chain_id = match &typed_data.domain["chainId"] {
+        serde_json::Value::Number(number) => number.as_u64(),
+        serde_json::Value::String(string) => match string.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).ok(),
+            None => U256::from_dec_str(string).ok(),
+        }
+        .filter(|chain_id| *chain_id <= U256::from(u64::MAX))
+        .map(|chain_id| chain_id.as_u64()),
+        _ => None,
+    };
+    if let Some(domain_chain_id) = domain_chain_id.filter(|domain| *domain != chain_id) {
+        flags.push(RiskFlag::DomainChainMismatch {
+            chain_id,
+            domain_chain_id,
+        });
+    }
+
+    let verifying_contract = typed_data.domain["verifyingContract"].as_str();
+    if let (Some(expected), Some(verifying_contract)) = (expected_contract, verifying_contract) {
+        if Address::from_str(verifying_contract).ok().as_ref() != Some(expected) {
+            flags.push(RiskFlag::DomainContractMismatch {
+                expected: format!("{:#x}", expected),
+                verifying_contract: verifying_contract.to_owned(),
+            });
+        }
+    }
+
+    RiskAssessment { flags }
+}
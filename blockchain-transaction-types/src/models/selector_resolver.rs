@@ -0,0 +1,178 @@
+use async_trait::async_trait;
+#[cfg(feature = "reqwest-selector-resolver")]
+use serde_json::Value;
+use web3::types::TransactionRequest as Web3TransactionRequest;
+
+use crate::models::error::Error;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::TransactionInfo;
+
+/// The OpenChain signature database lookup endpoint.
+#[cfg(feature = "reqwest-selector-resolver")]
+pub const OPEN_CHAIN_URL: &str = "https://api.openchain.xyz/signature-database/v1/lookup";
+
+/// The 4byte.directory function signatures endpoint.
+#[cfg(feature = "reqwest-selector-resolver")]
+pub const FOUR_BYTE_URL: &str = "https://www.4byte.directory/api/v1/signatures/";
+
+/// Resolves 4 byte function selectors to function signatures.
+#[async_trait]
+pub trait SelectorResolver {
+    /// Gets the best guess of the signature of a selector, such as
+    /// `transfer(address,uint256)`.
+    async fn resolve(&self, selector: [u8; 4]) -> Result<Option<String>, Error>;
+}
+
+/// Identifies a transaction request, resolving the function name of unknown
+/// calls with `resolver`.
+///
+/// Resolution errors are ignored, since the function name is a best guess.
+pub async fn resolve_transaction_info(
+    request: &Web3TransactionRequest,
+    resolver: &(dyn SelectorResolver + Sync),
+) -> TransactionInfo {
+    let info = request.transaction_info();
+    let selector = request
+        .data
+        .as_ref()
+        .and_then(|data| data.0.get(..4))
+        .and_then(|selector| selector.try_into().ok());
+
+    match (info, selector) {
+        (
+            TransactionInfo::Unknown {
+                value,
+                function: None,
+            },
+            Some(selector),
+        ) => TransactionInfo::Unknown {
+            value,
+            function: resolver.resolve(selector).await.ok().flatten(),
+        },
+        (info, _) => info,
+    }
+}
+
+/// Formats a selector as `0x` prefixed hex.
+#[cfg(feature = "reqwest-selector-resolver")]
+fn hex_selector(selector: [u8; 4]) -> String {
+    format!("0x{:08x}", u32::from_be_bytes(selector))
+}
+
+/// Gets the best signature of an OpenChain lookup response, which is the
+/// first one not filtered as spam.
+#[cfg(feature = "reqwest-selector-resolver")]
+pub(crate) fn open_chain_signature(response: &Value, selector: [u8; 4]) -> Option<String> {
+    response["result"]["function"][hex_selector(selector)]
+        .as_array()?
+        .iter()
+        .find(|signature| !signature["filtered"].as_bool().unwrap_or_default())
+        .and_then(|signature| signature["name"].as_str())
+        .map(str::to_owned)
+}
+
+/// Gets the best signature of a 4byte.directory response, which is the
+/// earliest registered one since later ones are more likely collisions.
+#[cfg(feature = "reqwest-selector-resolver")]
+pub(crate) fn four_byte_signature(response: &Value) -> Option<String> {
+    response["results"]
+        .as_array()?
+        .iter()
+        .filter_map(|signature| {
+            Some((
+                signature["id"].as_u64()?,
+                signature["text_signature"].as_str()?,
+            ))
+        })
+        .min_by_key(|(id, _)| *id)
+        .map(|(_, signature)| signature.to_owned())
+}
+
+/// Resolves selectors with the [OpenChain] signature database.
+///
+/// [OpenChain]: https://openchain.xyz/signatures
+#[cfg(feature = "reqwest-selector-resolver")]
+#[derive(Clone, Debug)]
+pub struct OpenChainResolver {
+    client: reqwest::Client,
+    url: String,
+}
+
+#[cfg(feature = "reqwest-selector-resolver")]
+impl OpenChainResolver {
+    /// Creates a resolver using [`OPEN_CHAIN_URL`].
+    pub fn new(client: reqwest::Client) -> Self {
+        Self::with_url(client, OPEN_CHAIN_URL)
+    }
+
+    /// Creates a resolver using a custom lookup endpoint.
+    pub fn with_url<U: Into<String>>(client: reqwest::Client, url: U) -> Self {
+        Self {
+            client,
+            url: url.into(),
+        }
+    }
+}
+
+#[cfg(feature = "reqwest-selector-resolver")]
+#[async_trait]
+impl SelectorResolver for OpenChainResolver {
+    async fn resolve(&self, selector: [u8; 4]) -> Result<Option<String>, Error> {
+        let response: Value = self
+            .client
+            .get(&self.url)
+            .query(&[
+                ("function", hex_selector(selector).as_str()),
+                ("filter", "true"),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(open_chain_signature(&response, selector))
+    }
+}
+
+/// Resolves selectors with [4byte.directory].
+///
+/// [4byte.directory]: https://www.4byte.directory
+#[cfg(feature = "reqwest-selector-resolver")]
+#[derive(Clone, Debug)]
+pub struct FourByteResolver {
+    client: reqwest::Client,
+    url: String,
+}
+
+#[cfg(feature = "reqwest-selector-resolver")]
+impl FourByteResolver {
+    /// Creates a resolver using [`FOUR_BYTE_URL`].
+    pub fn new(client: reqwest::Client) -> Self {
+        Self::with_url(client, FOUR_BYTE_URL)
+    }
+
+    /// Creates a resolver using a custom signatures endpoint.
+    pub fn with_url<U: Into<String>>(client: reqwest::Client, url: U) -> Self {
+        Self {
+            client,
+            url: url.into(),
+        }
+    }
+}
+
+#[cfg(feature = "reqwest-selector-resolver")]
+#[async_trait]
+impl SelectorResolver for FourByteResolver {
+    async fn resolve(&self, selector: [u8; 4]) -> Result<Option<String>, Error> {
+        let response: Value = self
+            .client
+            .get(&self.url)
+            .query(&[("hex_signature", hex_selector(selector))])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(four_byte_signature(&response))
+    }
+}
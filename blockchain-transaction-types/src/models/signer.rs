@@ -0,0 +1,47 @@
+use std::future::{Future, Ready};
+
+use thiserror::Error as ThisError;
+
+use crate::models::error::Error;
+
+/// Signs the hashes of transactions and messages, such as with a key store.
+///
+/// Signatures are returned as 64 bytes with their recovery ID, which is `0`
+/// for signature schemes without one.
+pub trait Signer {
+    type Error;
+    type Future: Future<Output = Result<(Vec<u8>, u64), Self::Error>>;
+
+    fn sign(&self, hash: Vec<u8>) -> Self::Future;
+}
+
+/// A [`Signer`] signing synchronously with a function.
+#[derive(Clone, Debug)]
+pub struct BlockingSigner<F>(pub F);
+
+impl<F, E> Signer for BlockingSigner<F>
+where
+    F: Fn(&[u8]) -> Result<(Vec<u8>, u64), E>,
+{
+    type Error = E;
+    type Future = Ready<Result<(Vec<u8>, u64), E>>;
+
+    fn sign(&self, hash: Vec<u8>) -> Self::Future {
+        std::future::ready((self.0)(&hash))
+    }
+}
+
+#[derive(Debug, ThisError)]
+pub enum SignError<E> {
+    Hash(Error),
+    Sign(E),
+}
+
+/// Signs the hash of a transaction or message.
+pub(crate) async fn sign_hash<S: Signer>(
+    hash: Result<Vec<u8>, Error>,
+    signer: &S,
+) -> Result<(Vec<u8>, u64), SignError<S::Error>> {
+    let hash = hash.map_err(SignError::Hash)?;
+    signer.sign(hash).await.map_err(SignError::Sign)
+}
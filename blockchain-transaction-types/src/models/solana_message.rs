@@ -0,0 +1,39 @@
+use serde_json::Value;
+use solana_sdk::offchain_message::OffchainMessage;
+
+use crate::models::error::Error;
+use crate::models::message::{Message, MessageInfo, SignableMessage};
+
+/// The off-chain message version used for messages created from text.
+const OFFCHAIN_MESSAGE_VERSION: u8 = 0;
+
+impl Message for OffchainMessage {
+    /// Creates a version 0 off-chain message from a string.
+    fn from_json(json: Value) -> Result<Self, Error> {
+        let message = json.as_str().ok_or(Error::InvalidData)?;
+        OffchainMessage::new(OFFCHAIN_MESSAGE_VERSION, message.as_bytes())
+            .map_err(|_| Error::InvalidData)
+    }
+
+    /// Deserializes an off-chain message with its signing domain header, or
+    /// creates a version 0 message from other bytes.
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.starts_with(OffchainMessage::SIGNING_DOMAIN) {
+            OffchainMessage::deserialize(bytes)
+        } else {
+            OffchainMessage::new(OFFCHAIN_MESSAGE_VERSION, bytes)
+        }
+        .map_err(|_| Error::InvalidData)
+    }
+
+    fn message_info(&self) -> MessageInfo {
+        MessageInfo::String(String::from_utf8_lossy(self.get_message()).into_owned())
+    }
+}
+
+impl SignableMessage for OffchainMessage {
+    /// Gets the serialized message with its header, which Ed25519 signs as is.
+    fn message_hash(&self, _chain_id: u64) -> Result<Vec<u8>, Error> {
+        self.serialize().map_err(|_| Error::InvalidData)
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::CompiledInstruction;
+use solana_sdk::message::v0::MessageAddressTableLookup;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::program_utils::limited_deserialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use solana_sdk::transaction::{
+    Transaction as SolanaTransaction, VersionedTransaction as SolanaVersionedTransaction,
+};
+
+use crate::models::error::{from_json_value, Error};
+#[cfg(feature = "signing")]
+use crate::models::transaction::SignableTransactionRequest;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::TransactionInfo;
+
+/// The addresses stored in address lookup tables, by table address.
+pub type AddressLookupTables = HashMap<Pubkey, Vec<Pubkey>>;
+
+/// SPL Token instruction tag for `Transfer { amount: u64 }`.
+const SPL_TOKEN_TRANSFER: u8 = 3;
+/// SPL Token instruction tag for `TransferChecked { amount: u64, decimals: u8 }`.
+const SPL_TOKEN_TRANSFER_CHECKED: u8 = 12;
+/// Token-2022 instruction tag for the instructions of the transfer fee
+/// extension.
+const TOKEN_2022_TRANSFER_FEE_EXTENSION: u8 = 26;
+/// Transfer fee extension instruction tag for
+/// `TransferCheckedWithFee { amount: u64, decimals: u8, fee: u64 }`.
+const TOKEN_2022_TRANSFER_CHECKED_WITH_FEE: u8 = 1;
+/// Associated Token Account instruction tag for `Create`, which may also be
+/// encoded without any data.
+const ATA_CREATE: u8 = 0;
+/// Associated Token Account instruction tag for `CreateIdempotent`.
+const ATA_CREATE_IDEMPOTENT: u8 = 1;
+
+mod spl_token {
+    solana_sdk::declare_id!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+}
+
+mod spl_token_2022 {
+    solana_sdk::declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+}
+
+mod spl_associated_token_account {
+    solana_sdk::declare_id!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+}
+
+impl TransactionRequest for SolanaTransaction {
+    fn from_json(json: Value) -> Result<Self, Error> {
+        let transaction = from_json_value(json)?;
+        Ok(transaction)
+    }
+
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
+        limited_deserialize(bytes).map_err(|_| Error::InvalidData)
+    }
+
+    /// Identifies the first System Program, SPL Token or Token-2022 transfer
+    /// in the transaction, and the associated token accounts it creates.
+    fn transaction_info(&self) -> TransactionInfo {
+        instructions_transaction_info(&self.message.account_keys, &self.message.instructions)
+    }
+}
+
+#[cfg(feature = "signing")]
+impl SignableTransactionRequest for SolanaTransaction {
+    /// Returns the serialized message, which is signed as is with Ed25519.
+    ///
+    /// Fails with [`Error::InvalidData`] for durable nonce transactions
+    /// without the nonce as their recent blockhash.
+    fn message_hash(&self, _chain_id: u64) -> Result<Vec<u8>, Error> {
+        validate_nonce(self)?;
+        Ok(self.message_data())
+    }
+}
+
+impl TransactionRequest for SolanaVersionedTransaction {
+    fn from_json(json: Value) -> Result<Self, Error> {
+        let transaction = from_json_value(json)?;
+        Ok(transaction)
+    }
+
+    fn from_raw(bytes: &[u8]) -> Result<Self, Error> {
+        limited_deserialize(bytes).map_err(|_| Error::InvalidData)
+    }
+
+    /// Identifies the first System Program, SPL Token or Token-2022 transfer
+    /// in the transaction, and the associated token accounts it creates.
+    ///
+    /// Accounts loaded from address lookup tables are not resolved, see
+    /// [`versioned_transaction_info`] to resolve them.
+    fn transaction_info(&self) -> TransactionInfo {
+        versioned_transaction_info(self, &AddressLookupTables::new()).unwrap_or(
+            TransactionInfo::Unknown {
+                value: None,
+                function: None,
+            },
+        )
+    }
+}
+
+#[cfg(feature = "signing")]
+impl SignableTransactionRequest for SolanaVersionedTransaction {
+    /// Returns the serialized message, which is signed as is with Ed25519.
+    ///
+    /// Fails with [`Error::InvalidData`] for durable nonce transactions
+    /// without the nonce as their recent blockhash.
+    fn message_hash(&self, _chain_id: u64) -> Result<Vec<u8>, Error> {
+        validate_nonce(self)?;
+        Ok(self.message.serialize())
+    }
+}
+
+/// A Solana transaction, which either expires with its recent blockhash or
+/// uses a durable nonce.
+///
+/// Durable nonce transactions start with a System Program
+/// `AdvanceNonceAccount` instruction, and have the nonce stored in the nonce
+/// account as their recent blockhash.
+pub trait DurableNonce {
+    fn account_keys(&self) -> &[Pubkey];
+
+    fn instructions(&self) -> &[CompiledInstruction];
+
+    fn recent_blockhash(&self) -> &Hash;
+
+    /// Sets the recent blockhash, or the nonce of a durable nonce
+    /// transaction.
+    fn set_blockhash(&mut self, blockhash: Hash);
+
+    /// Whether the transaction starts with an `AdvanceNonceAccount`
+    /// instruction.
+    fn uses_durable_nonce(&self) -> bool {
+        advance_nonce_instruction(self).is_some()
+    }
+
+    /// Gets the nonce account advanced by the transaction.
+    fn nonce_account(&self) -> Option<Pubkey> {
+        let instruction = advance_nonce_instruction(self)?;
+        instruction_account(self.account_keys(), instruction, 0)
+    }
+
+    /// Gets the authority of the nonce account, which must sign the
+    /// transaction.
+    fn nonce_authority(&self) -> Option<Pubkey> {
+        let instruction = advance_nonce_instruction(self)?;
+        instruction_account(self.account_keys(), instruction, 2)
+    }
+
+    /// Replaces the recent blockhash with a newer one before signing, unless
+    /// the transaction uses a durable nonce, whose nonce is kept.
+    ///
+    /// Returns whether the blockhash was replaced.
+    fn refresh_blockhash(&mut self, blockhash: Hash) -> bool {
+        if self.uses_durable_nonce() {
+            return false;
+        }
+        self.set_blockhash(blockhash);
+        true
+    }
+}
+
+impl DurableNonce for SolanaTransaction {
+    fn account_keys(&self) -> &[Pubkey] {
+        &self.message.account_keys
+    }
+
+    fn instructions(&self) -> &[CompiledInstruction] {
+        &self.message.instructions
+    }
+
+    fn recent_blockhash(&self) -> &Hash {
+        &self.message.recent_blockhash
+    }
+
+    fn set_blockhash(&mut self, blockhash: Hash) {
+        self.message.recent_blockhash = blockhash;
+    }
+}
+
+/// Accounts loaded from address lookup tables are not resolved, so the nonce
+/// account and authority are only known when they are static accounts.
+impl DurableNonce for SolanaVersionedTransaction {
+    fn account_keys(&self) -> &[Pubkey] {
+        match &self.message {
+            VersionedMessage::Legacy(message) => &message.account_keys,
+            VersionedMessage::V0(message) => &message.account_keys,
+        }
+    }
+
+    fn instructions(&self) -> &[CompiledInstruction] {
+        match &self.message {
+            VersionedMessage::Legacy(message) => &message.instructions,
+            VersionedMessage::V0(message) => &message.instructions,
+        }
+    }
+
+    fn recent_blockhash(&self) -> &Hash {
+        match &self.message {
+            VersionedMessage::Legacy(message) => &message.recent_blockhash,
+            VersionedMessage::V0(message) => &message.recent_blockhash,
+        }
+    }
+
+    fn set_blockhash(&mut self, blockhash: Hash) {
+        match &mut self.message {
+            VersionedMessage::Legacy(message) => message.recent_blockhash = blockhash,
+            VersionedMessage::V0(message) => message.recent_blockhash = blockhash,
+        }
+    }
+}
+
+/// Gets the leading `AdvanceNonceAccount` instruction of a transaction.
+fn advance_nonce_instruction<T: DurableNonce + ?Sized>(
+    transaction: &T,
+) -> Option<&CompiledInstruction> {
+    let instruction = transaction.instructions().first()?;
+    let program_id = account_key(transaction.account_keys(), instruction.program_id_index)?;
+    match limited_deserialize(&instruction.data).ok()? {
+        SystemInstruction::AdvanceNonceAccount if *program_id == system_program::id() => {
+            Some(instruction)
+        }
+        _ => None,
+    }
+}
+
+/// Checks that a durable nonce transaction has its nonce set, rather than
+/// the default blockhash.
+#[cfg(feature = "signing")]
+fn validate_nonce<T: DurableNonce>(transaction: &T) -> Result<(), Error> {
+    if transaction.uses_durable_nonce() && *transaction.recent_blockhash() == Hash::default() {
+        return Err(Error::InvalidData);
+    }
+    Ok(())
+}
+
+/// Identifies the first System Program, SPL Token or Token-2022 transfer in a
+/// versioned transaction, and the associated token accounts it creates,
+/// resolving accounts loaded from address lookup tables.
+///
+/// Returns [`Error::InvalidData`] if the transaction uses a lookup table
+/// missing from `tables` or an index out of bounds of its table.
+pub fn versioned_transaction_info(
+    transaction: &SolanaVersionedTransaction,
+    tables: &AddressLookupTables,
+) -> Result<TransactionInfo, Error> {
+    let (static_keys, instructions, lookups): (_, _, &[MessageAddressTableLookup]) =
+        match &transaction.message {
+            VersionedMessage::Legacy(message) => {
+                (&message.account_keys, &message.instructions, &[])
+            }
+            VersionedMessage::V0(message) => (
+                &message.account_keys,
+                &message.instructions,
+                &message.address_table_lookups,
+            ),
+        };
+
+    let account_keys = resolve_account_keys(static_keys, lookups, tables)?;
+    Ok(instructions_transaction_info(&account_keys, instructions))
+}
+
+/// Resolves the account keys of a v0 message.
+///
+/// The keys are ordered as the static keys, followed by the writable keys of
+/// each lookup, followed by the readonly keys of each lookup.
+fn resolve_account_keys(
+    static_keys: &[Pubkey],
+    lookups: &[MessageAddressTableLookup],
+    tables: &AddressLookupTables,
+) -> Result<Vec<Pubkey>, Error> {
+    let lookup_keys = |indexes: fn(&MessageAddressTableLookup) -> &[u8]| {
+        lookups.iter().flat_map(move |lookup| {
+            let table = tables.get(&lookup.account_key);
+            indexes(lookup).iter().map(move |index| {
+                table
+                    .and_then(|table| table.get(usize::from(*index)))
+                    .copied()
+                    .ok_or(Error::InvalidData)
+            })
+        })
+    };
+
+    static_keys
+        .iter()
+        .copied()
+        .map(Ok)
+        .chain(lookup_keys(|lookup| &lookup.writable_indexes))
+        .chain(lookup_keys(|lookup| &lookup.readonly_indexes))
+        .collect()
+}
+
+/// Identifies the first System Program, SPL Token or Token-2022 transfer in
+/// a list of instructions.
+///
+/// Associated token accounts created by the instructions are listed after
+/// the transfer in a batch, since their creation pays rent.
+fn instructions_transaction_info(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+) -> TransactionInfo {
+    let transfer = instructions.iter().find_map(|instruction| {
+        let program_id = account_key(account_keys, instruction.program_id_index)?;
+        if *program_id == system_program::id() {
+            system_transfer_info(account_keys, instruction)
+        } else if *program_id == spl_token::id() || *program_id == spl_token_2022::id() {
+            spl_token_transfer_info(account_keys, instruction)
+        } else {
+            None
+        }
+    });
+    let mut calls: Vec<TransactionInfo> = transfer.into_iter().collect();
+    calls.extend(instructions.iter().filter_map(|instruction| {
+        let program_id = account_key(account_keys, instruction.program_id_index)?;
+        if *program_id == spl_associated_token_account::id() {
+            token_account_creation_info(account_keys, instruction)
+        } else {
+            None
+        }
+    }));
+
+    match calls.len() {
+        0 => TransactionInfo::Unknown {
+            value: None,
+            function: None,
+        },
+        1 => calls.remove(0),
+        _ => TransactionInfo::Batch { calls },
+    }
+}
+
+/// Gets the account key at an index of the message account keys.
+fn account_key(account_keys: &[Pubkey], index: u8) -> Option<&Pubkey> {
+    account_keys.get(usize::from(index))
+}
+
+/// Gets the account key of the nth account of an instruction.
+fn instruction_account(
+    account_keys: &[Pubkey],
+    instruction: &CompiledInstruction,
+    position: usize,
+) -> Option<Pubkey> {
+    let index = *instruction.accounts.get(position)?;
+    account_key(account_keys, index).copied()
+}
+
+/// Decodes a System Program `Transfer` instruction.
+///
+/// The accounts are `[from, to]`.
+fn system_transfer_info(
+    account_keys: &[Pubkey],
+    instruction: &CompiledInstruction,
+) -> Option<TransactionInfo> {
+    match limited_deserialize(&instruction.data).ok()? {
+        SystemInstruction::Transfer { lamports } => Some(TransactionInfo::TokenTransfer {
+            from: instruction_account(account_keys, instruction, 0)?.to_string(),
+            to: instruction_account(account_keys, instruction, 1)?.to_string(),
+            amount: format!("{:#x}", lamports),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }),
+        _ => None,
+    }
+}
+
+/// Decodes an SPL Token or Token-2022 `Transfer` or `TransferChecked`
+/// instruction, or a Token-2022 `TransferCheckedWithFee` instruction.
+///
+/// The accounts are `[source, destination, authority]` for `Transfer` and
+/// `[source, mint, destination, authority]` for the others. The mint is
+/// returned as the token id when known. The amount of `TransferCheckedWithFee`
+/// is the amount sent, of which the fee is withheld from the destination.
+fn spl_token_transfer_info(
+    account_keys: &[Pubkey],
+    instruction: &CompiledInstruction,
+) -> Option<TransactionInfo> {
+    let (mut tag, mut data) = instruction.data.split_first()?;
+    if *tag == TOKEN_2022_TRANSFER_FEE_EXTENSION {
+        match data.split_first()? {
+            (&TOKEN_2022_TRANSFER_CHECKED_WITH_FEE, fee_data) => {
+                tag = &SPL_TOKEN_TRANSFER_CHECKED;
+                data = fee_data;
+            }
+            _ => return None,
+        }
+    }
+    let amount = u64::from_le_bytes(data.get(..8)?.try_into().ok()?);
+
+    let (source, mint, destination) = match *tag {
+        SPL_TOKEN_TRANSFER => (
+            instruction_account(account_keys, instruction, 0)?,
+            None,
+            instruction_account(account_keys, instruction, 1)?,
+        ),
+        SPL_TOKEN_TRANSFER_CHECKED => (
+            instruction_account(account_keys, instruction, 0)?,
+            Some(instruction_account(account_keys, instruction, 1)?),
+            instruction_account(account_keys, instruction, 2)?,
+        ),
+        _ => return None,
+    };
+
+    Some(TransactionInfo::TokenTransfer {
+        from: source.to_string(),
+        to: destination.to_string(),
+        amount: format!("{:#x}", amount),
+        token_id: mint.map(|mint| mint.to_string()),
+        token_info: None,
+        fiat_value: None,
+    })
+}
+
+/// Decodes an Associated Token Account `Create` or `CreateIdempotent`
+/// instruction.
+///
+/// The accounts are `[payer, account, owner, mint, system program, token
+/// program]`.
+fn token_account_creation_info(
+    account_keys: &[Pubkey],
+    instruction: &CompiledInstruction,
+) -> Option<TransactionInfo> {
+    match instruction.data.first() {
+        None | Some(&ATA_CREATE) | Some(&ATA_CREATE_IDEMPOTENT) => {}
+        Some(_) => return None,
+    }
+
+    Some(TransactionInfo::TokenAccountCreation {
+        payer: instruction_account(account_keys, instruction, 0)?.to_string(),
+        account: instruction_account(account_keys, instruction, 1)?.to_string(),
+        owner: instruction_account(account_keys, instruction, 2)?.to_string(),
+        mint: instruction_account(account_keys, instruction, 3)?.to_string(),
+    })
+}
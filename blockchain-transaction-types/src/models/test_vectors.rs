@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use web3::types::{Bytes, H256};
+
+use crate::models::coin_type::CoinType;
+use crate::models::error::{from_json_slice, Error};
+use crate::models::transaction_info::TransactionInfo;
+
+/// The fixtures shipped with the crate, ordered by file name.
+const FIXTURES: [&str; 4] = [
+    include_str!("../../test-vectors/eip155_transfer.json"),
+    include_str!("../../test-vectors/eip1559_transfer.json"),
+    include_str!("../../test-vectors/eip712_mail.json"),
+    include_str!("../../test-vectors/erc1155_transfer.json"),
+];
+
+/// Whether the request of a [`TestVector`] is a transaction or a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TestVectorKind {
+    Transaction,
+    Message,
+}
+
+/// A known-good request with the results expected from parsing, hashing and
+/// signing it.
+///
+/// Expectations that don't apply to a vector, such as the raw transaction of
+/// a message, are `None`. Signatures are deterministic, so signing the message
+/// hash with the private key gives the same signature and recovery ID.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestVector {
+    pub name: String,
+    pub description: String,
+    pub coin_type: CoinType,
+    pub chain_id: u64,
+    pub kind: TestVectorKind,
+    /// The JSON of the request, as accepted by
+    /// [`known_transaction_request_type_from_json`] or
+    /// [`known_message_type_from_json`].
+    ///
+    /// [`known_transaction_request_type_from_json`]: crate::known_transaction_request_type_from_json
+    /// [`known_message_type_from_json`]: crate::known_message_type_from_json
+    pub request: Value,
+    pub private_key: Option<H256>,
+    pub message_hash: Option<H256>,
+    /// The 64-byte signature of the message hash, without the recovery ID.
+    pub signature: Option<Bytes>,
+    pub recovery_id: Option<u64>,
+    /// The signed transaction, ready to be broadcast.
+    pub raw: Option<Bytes>,
+    pub transaction_hash: Option<H256>,
+    pub transaction_info: Option<TransactionInfo>,
+}
+
+/// Loads the test vectors shipped with the crate, ordered by file name.
+///
+/// Integrations can check their own parsing and signing against the same
+/// fixtures as the crate's tests.
+pub fn test_vectors() -> Result<Vec<TestVector>, Error> {
+    FIXTURES
+        .iter()
+        .map(|fixture| from_json_slice(fixture.as_bytes()))
+        .collect()
+}
+
+/// Loads the test vector named `name`, such as `eip712-mail`.
+pub fn test_vector(name: &str) -> Result<Option<TestVector>, Error> {
+    Ok(test_vectors()?
+        .into_iter()
+        .find(|vector| vector.name == name))
+}
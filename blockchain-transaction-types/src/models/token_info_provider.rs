@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use web3::ethabi::{self, ParamType, Token};
+use web3::types::{Address, Bytes, CallRequest, U256};
+use web3::{Transport, Web3};
+
+use crate::models::error::Error;
+use crate::models::keccak::keccak256;
+use crate::models::transaction_info::{TokenInfo, TransactionInfo};
+
+/// Looks up the metadata of tokens.
+#[async_trait]
+pub trait TokenInfoProvider {
+    /// Gets the metadata of the token contract `contract`, including the
+    /// metadata URI of `token_id` for non-fungible tokens.
+    async fn token_info(
+        &self,
+        chain_id: u64,
+        contract: Address,
+        token_id: Option<U256>,
+    ) -> Result<TokenInfo, Error>;
+}
+
+/// Fills in the `token_info` of the token transfers of `info`, where
+/// `contract` is the called token contract.
+///
+/// Transfers executed by a Safe are looked up on the contract the Safe calls.
+/// Lookup errors are ignored, leaving `token_info` as `None`.
+pub async fn enrich(
+    info: &mut TransactionInfo,
+    chain_id: u64,
+    contract: Address,
+    provider: &(dyn TokenInfoProvider + Sync),
+) {
+    match info {
+        TransactionInfo::TokenTransfer {
+            token_id,
+            token_info,
+            ..
+        }
+        | TransactionInfo::TokenMint {
+            token_id,
+            token_info,
+            ..
+        }
+        | TransactionInfo::TokenBurn {
+            token_id,
+            token_info,
+            ..
+        } => {
+            *token_info = lookup(provider, chain_id, contract, token_id.as_deref()).await;
+        }
+        TransactionInfo::TokenBatchTransfer { transfers }
+        | TransactionInfo::TokenBatchMint { transfers }
+        | TransactionInfo::TokenBatchBurn { transfers } => {
+            for transfer in transfers {
+                transfer.token_info =
+                    lookup(provider, chain_id, contract, transfer.token_id.as_deref()).await;
+            }
+        }
+        TransactionInfo::SafeTransaction { to, info, .. } => {
+            if let Ok(to) = Address::from_str(to) {
+                enrich_boxed(info, chain_id, to, provider).await;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Boxes the recursive future of a Safe transaction.
+fn enrich_boxed<'a>(
+    info: &'a mut TransactionInfo,
+    chain_id: u64,
+    contract: Address,
+    provider: &'a (dyn TokenInfoProvider + Sync),
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send + 'a>> {
+    Box::pin(enrich(info, chain_id, contract, provider))
+}
+
+async fn lookup(
+    provider: &(dyn TokenInfoProvider + Sync),
+    chain_id: u64,
+    contract: Address,
+    token_id: Option<&str>,
+) -> Option<TokenInfo> {
+    let token_id = match token_id {
+        Some(token_id) => Some(U256::from_str(token_id).ok()?),
+        None => None,
+    };
+    provider.token_info(chain_id, contract, token_id).await.ok()
+}
+
+/// Reads token metadata with `eth_call`s of `name()`, `symbol()`,
+/// `decimals()` and `tokenURI(uint256)`.
+///
+/// Only `name()` is required, since NFTs have no decimals and not every
+/// token has a symbol.
+#[derive(Clone, Debug)]
+pub struct Web3TokenInfoProvider<T: Transport> {
+    chains: HashMap<u64, Web3<T>>,
+}
+
+impl<T: Transport> Default for Web3TokenInfoProvider<T> {
+    fn default() -> Self {
+        Self {
+            chains: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Transport> Web3TokenInfoProvider<T> {
+    /// Creates a provider without any chains.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the transport used for calls on `chain_id`.
+    pub fn register_chain(&mut self, chain_id: u64, transport: T) {
+        self.chains.insert(chain_id, Web3::new(transport));
+    }
+
+    async fn call(
+        &self,
+        web3: &Web3<T>,
+        contract: Address,
+        signature: &str,
+        parameters: &[Token],
+    ) -> Result<Vec<u8>, Error> {
+        let mut data = keccak256(signature.as_bytes())[..4].to_vec();
+        data.extend(ethabi::encode(parameters));
+        let request = CallRequest {
+            to: Some(contract),
+            data: Some(Bytes(data)),
+            ..Default::default()
+        };
+        Ok(web3.eth().call(request, None).await?.0)
+    }
+}
+
+#[async_trait]
+impl<T> TokenInfoProvider for Web3TokenInfoProvider<T>
+where
+    T: Transport + Send + Sync,
+    T::Out: Send,
+{
+    async fn token_info(
+        &self,
+        chain_id: u64,
+        contract: Address,
+        token_id: Option<U256>,
+    ) -> Result<TokenInfo, Error> {
+        let web3 = self
+            .chains
+            .get(&chain_id)
+            .ok_or(Error::UnknownChain(chain_id))?;
+
+        let name = decode_string(&self.call(web3, contract, "name()", &[]).await?)
+            .ok_or(Error::InvalidData)?;
+        let symbol = self
+            .call(web3, contract, "symbol()", &[])
+            .await
+            .ok()
+            .and_then(|output| decode_string(&output));
+        let decimals = self
+            .call(web3, contract, "decimals()", &[])
+            .await
+            .ok()
+            .and_then(|output| output.get(..32).map(U256::from_big_endian))
+            .filter(|decimals| *decimals <= U256::from(u8::MAX))
+            .map_or(0, |decimals| decimals.as_u32() as u8);
+        let uri = match token_id {
+            Some(token_id) => self
+                .call(
+                    web3,
+                    contract,
+                    "tokenURI(uint256)",
+                    &[Token::Uint(token_id)],
+                )
+                .await
+                .ok()
+                .and_then(|output| decode_string(&output)),
+            None => None,
+        };
+
+        Ok(TokenInfo {
+            name,
+            symbol,
+            decimals,
+            uri,
+            ..Default::default()
+        })
+    }
+}
+
+/// Decodes an ABI encoded `string`, or a `bytes32` string as returned by
+/// some older tokens.
+fn decode_string(output: &[u8]) -> Option<String> {
+    match ethabi::decode(&[ParamType::String], output) {
+        Ok(mut tokens) => tokens.pop()?.into_string(),
+        Err(_) if output.len() == 32 => {
+            let end = output.iter().position(|byte| *byte == 0).unwrap_or(32);
+            String::from_utf8(output[..end].to_vec()).ok()
+        }
+        Err(_) => None,
+    }
+}
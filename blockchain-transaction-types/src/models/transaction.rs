@@ -1,7 +1,12 @@
 use crate::models::account::Account;
 use crate::models::error::Error;
-use crate::models::transaction_info::TransactionInfo;
+use crate::models::signer::{sign_hash, SignError, Signer};
+use crate::models::transaction_info::{FeeInfo, TransactionInfo};
+#[cfg(feature = "ethereum")]
+use serde::{Deserialize, Serialize};
 use std::future::Future;
+#[cfg(feature = "ethereum")]
+use web3::types::{Bytes, H256};
 
 pub trait Transaction {
     type Account: Account;
@@ -31,6 +36,11 @@ pub trait TransactionRequest {
         Self: Sized;
 
     fn transaction_info(&self) -> TransactionInfo;
+
+    /// Gets the fees of the request, for chains with fees set by the sender.
+    fn fee_info(&self) -> Option<FeeInfo> {
+        None
+    }
 }
 
 pub trait GasPricedTransactionRequest: TransactionRequest {
@@ -39,6 +49,38 @@ pub trait GasPricedTransactionRequest: TransactionRequest {
 
 pub trait SignableTransactionRequest: TransactionRequest {
     fn message_hash(&self, chain_id: u64) -> Result<Vec<u8>, Error>;
+
+    /// Encodes the transaction signed with the 64 byte `signature` and
+    /// `recovery_id` returned by `sign_transaction`, ready to be broadcast.
+    fn encode_signed(
+        &self,
+        _chain_id: u64,
+        _signature: &[u8],
+        _recovery_id: u64,
+    ) -> Result<Vec<u8>, Error> {
+        Err(Error::InvalidData)
+    }
+
+    /// Computes the hash identifying the transaction encoded by
+    /// `encode_signed`, such as the transaction hash of Ethereum
+    /// transactions.
+    fn transaction_hash(&self, _chain_id: u64, _raw: &[u8]) -> Result<Vec<u8>, Error> {
+        Err(Error::InvalidData)
+    }
+}
+
+/// A signed transaction, encoded by [`SignableTransactionRequest::encode_signed`].
+#[cfg(feature = "ethereum")]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedTransaction {
+    /// The signed transaction, ready to be broadcast.
+    pub raw: Bytes,
+    pub hash: H256,
+    /// The 64 byte signature.
+    pub signature: Bytes,
+    pub recovery_id: u64,
+    pub chain_id: u64,
 }
 
 impl dyn SignableTransactionRequest {
@@ -55,4 +97,41 @@ impl dyn SignableTransactionRequest {
         let (signature, recovery) = provider(hash).await.or(Err(Error::InvalidData))?;
         Ok((signature, recovery))
     }
+
+    /// Signs the transaction hash with `signer`.
+    pub async fn sign_with<S: Signer>(
+        &self,
+        chain_id: u64,
+        signer: &S,
+    ) -> Result<(Vec<u8>, u64), SignError<S::Error>> {
+        sign_hash(self.message_hash(chain_id), signer).await
+    }
+
+    /// Signs the transaction with `provider` like `sign_transaction`, and
+    /// encodes it with its hash.
+    #[cfg(feature = "ethereum")]
+    pub async fn sign_and_encode<
+        E,
+        O: Future<Output = Result<(Vec<u8>, u64), E>>,
+        F: FnOnce(Vec<u8>) -> O,
+    >(
+        &self,
+        chain_id: u64,
+        provider: F,
+    ) -> Result<SignedTransaction, Error> {
+        let (signature, recovery_id) = self.sign_transaction(chain_id, provider).await?;
+        let raw = self.encode_signed(chain_id, &signature, recovery_id)?;
+        let hash = self.transaction_hash(chain_id, &raw)?;
+        if hash.len() != 32 {
+            return Err(Error::InvalidData);
+        }
+
+        Ok(SignedTransaction {
+            raw: raw.into(),
+            hash: H256::from_slice(&hash),
+            signature: signature.into(),
+            recovery_id,
+            chain_id,
+        })
+    }
 }
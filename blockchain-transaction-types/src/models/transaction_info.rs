@@ -1,22 +1,267 @@
-#[derive(Clone, PartialEq, Default, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TokenInfo {
     pub name: String,
     pub symbol: Option<String>,
     pub decimals: u8,
     pub total_supply: Option<u64>,
     pub image: Option<String>,
+    /// The metadata URI of a non-fungible token.
+    #[serde(default)]
+    pub uri: Option<String>,
+}
+
+/// An estimate of the value of an amount in a fiat currency.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FiatValue {
+    /// The ISO 4217 code of the currency, such as `USD`.
+    pub currency: String,
+    pub amount: f64,
+}
+
+/// An output of a UTXO based transaction.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionOutput {
+    /// The hex encoded locking script.
+    pub script_pubkey: String,
+    pub amount: String,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+/// A single token transfer of a batch.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransferInfo {
+    pub from: String,
+    pub to: String,
+    pub amount: String,
+    pub token_id: Option<String>,
+    pub token_info: Option<TokenInfo>,
+    #[serde(default)]
+    pub fiat_value: Option<FiatValue>,
+}
+
+/// A decoded parameter of a contract call.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallParameter {
+    pub name: String,
+    /// The ABI type, such as `address` or `uint256`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub value: serde_json::Value,
+}
+
+/// The gas parameters of a transaction request and its maximum cost, as
+/// hex encoded amounts of the smallest unit of the native token.
+#[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInfo {
+    pub gas_limit: Option<String>,
+    /// The gas price of legacy and EIP-2930 transactions.
+    pub gas_price: Option<String>,
+    pub max_fee_per_gas: Option<String>,
+    pub max_priority_fee_per_gas: Option<String>,
+    pub max_fee_per_blob_gas: Option<String>,
+    /// The value plus the maximum gas fee, or `None` when the gas limit or
+    /// price isn't set.
+    pub max_total_fee: Option<String>,
+}
+
+/// A classification of a transaction request.
+///
+/// Serialized with a `type` field naming the variant in camelCase, such as
+/// `{"type": "tokenTransfer", "from": "0x…", …}`.
+///
+/// Senders, such as the `from` of transfers and the `owner` of approvals, are
+/// empty when the request has no known sender.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
 pub enum TransactionInfo {
+    #[serde(rename_all = "camelCase")]
     TokenTransfer {
         from: String,
         to: String,
         amount: String,
         token_id: Option<String>,
         token_info: Option<TokenInfo>,
+        /// The estimated value of the amount, when priced.
+        #[serde(default)]
+        fiat_value: Option<FiatValue>,
+    },
+    #[serde(rename_all = "camelCase")]
+    TokenBatchTransfer { transfers: Vec<TokenTransferInfo> },
+    /// Tokens created by a transfer from the zero address.
+    #[serde(rename_all = "camelCase")]
+    TokenMint {
+        to: String,
+        amount: String,
+        token_id: Option<String>,
+        token_info: Option<TokenInfo>,
+        #[serde(default)]
+        fiat_value: Option<FiatValue>,
     },
+    /// Tokens destroyed by a transfer to the zero address.
+    #[serde(rename_all = "camelCase")]
+    TokenBurn {
+        from: String,
+        amount: String,
+        token_id: Option<String>,
+        token_info: Option<TokenInfo>,
+        #[serde(default)]
+        fiat_value: Option<FiatValue>,
+    },
+    /// Tokens created by a batch transfer from the zero address.
+    #[serde(rename_all = "camelCase")]
+    TokenBatchMint { transfers: Vec<TokenTransferInfo> },
+    /// Tokens destroyed by a batch transfer to the zero address.
+    #[serde(rename_all = "camelCase")]
+    TokenBatchBurn { transfers: Vec<TokenTransferInfo> },
+    #[serde(rename_all = "camelCase")]
+    Approval {
+        owner: String,
+        spender: String,
+        /// The allowance, or `None` when all tokens are approved.
+        amount: Option<String>,
+        /// The approved token contract, when it isn't the transaction
+        /// recipient.
+        token: Option<String>,
+        is_unlimited: bool,
+    },
+    /// A swap of an exact amount of `token_in` for at least
+    /// `min_amount_out` of `token_out`.
+    ///
+    /// Swaps of ether report the wrapped ether token of the swap path.
+    #[serde(rename_all = "camelCase")]
+    TokenSwap {
+        token_in: String,
+        token_out: String,
+        amount_in: String,
+        min_amount_out: String,
+        recipient: String,
+    },
+    /// Ether, or the native token of the chain, deposited into its wrapped
+    /// token contract.
+    #[serde(rename_all = "camelCase")]
+    Wrap { token: String, amount: String },
+    /// Wrapped native tokens withdrawn from their contract.
+    #[serde(rename_all = "camelCase")]
+    Unwrap { token: String, amount: String },
+    /// A Solana associated token account created for `owner`, with its rent
+    /// paid by `payer`.
+    ///
+    /// Idempotent creations of accounts that already exist pay no rent.
+    #[serde(rename_all = "camelCase")]
+    TokenAccountCreation {
+        payer: String,
+        account: String,
+        owner: String,
+        mint: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    ContractDeployment {
+        /// The size of the init code in bytes.
+        bytecode_size: usize,
+        /// The hex encoded Keccak-256 hash of the init code.
+        init_code_hash: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    ContractCall {
+        /// The function signature, such as `mint(uint256)`.
+        function: String,
+        parameters: Vec<CallParameter>,
+    },
+    #[serde(rename_all = "camelCase")]
+    SafeTransaction {
+        /// The Safe executing the transaction.
+        safe: String,
+        to: String,
+        value: String,
+        /// `0` for a call and `1` for a delegate call.
+        operation: u8,
+        /// The classification of the executed call, as sent by the Safe.
+        info: Box<TransactionInfo>,
+    },
+    /// Calls executed by a multicall, each classified on its own.
+    #[serde(rename_all = "camelCase")]
+    Batch { calls: Vec<TransactionInfo> },
+    #[serde(rename_all = "camelCase")]
+    UtxoTransfer {
+        outputs: Vec<TransactionOutput>,
+        fee: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
     Unknown {
         value: Option<String>,
+        /// The best guess of the called function signature, such as
+        /// `transfer(address,uint256)`, when resolved from its selector.
+        function: Option<String>,
     },
 }
+
+impl TransactionInfo {
+    /// Classifies a token transfer from the zero address as a mint, and one to
+    /// the zero address as a burn.
+    ///
+    /// Batch transfers are classified the same way when all of their
+    /// transfers are from or to the zero address. Other classifications are
+    /// kept.
+    pub fn into_supply_change(self) -> Self {
+        match self {
+            TransactionInfo::TokenTransfer {
+                from,
+                to,
+                amount,
+                token_id,
+                token_info,
+                fiat_value,
+            } if is_zero_address(&from) => TransactionInfo::TokenMint {
+                to,
+                amount,
+                token_id,
+                token_info,
+                fiat_value,
+            },
+            TransactionInfo::TokenTransfer {
+                from,
+                to,
+                amount,
+                token_id,
+                token_info,
+                fiat_value,
+            } if is_zero_address(&to) => TransactionInfo::TokenBurn {
+                from,
+                amount,
+                token_id,
+                token_info,
+                fiat_value,
+            },
+            TransactionInfo::TokenBatchTransfer { transfers }
+                if !transfers.is_empty()
+                    && transfers
+                        .iter()
+                        .all(|transfer| is_zero_address(&transfer.from)) =>
+            {
+                TransactionInfo::TokenBatchMint { transfers }
+            }
+            TransactionInfo::TokenBatchTransfer { transfers }
+                if !transfers.is_empty()
+                    && transfers
+                        .iter()
+                        .all(|transfer| is_zero_address(&transfer.to)) =>
+            {
+                TransactionInfo::TokenBatchBurn { transfers }
+            }
+            info => info,
+        }
+    }
+}
+
+/// Whether `address` is the hex encoded zero address.
+pub(crate) fn is_zero_address(address: &str) -> bool {
+    let digits = address.trim_start_matches("0x");
+    !digits.is_empty() && digits.bytes().all(|digit| digit == b'0')
+}
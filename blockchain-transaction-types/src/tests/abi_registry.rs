@@ -0,0 +1,110 @@
+use web3::types::{Address, TransactionRequest};
+
+use crate::models::abi_registry::AbiRegistry;
+use crate::models::keccak::keccak256;
+use crate::models::transaction_info::{CallParameter, TransactionInfo};
+
+const ABI: &[u8] = br#"[{
+    "type": "function",
+    "name": "mint",
+    "inputs": [
+        { "name": "to", "type": "address" },
+        { "name": "ids", "type": "uint256[]" }
+    ],
+    "outputs": [],
+    "stateMutability": "nonpayable"
+}]"#;
+
+const CONTRACT_ABI: &[u8] = br#"[{
+    "type": "function",
+    "name": "mint",
+    "inputs": [
+        { "name": "recipient", "type": "address" },
+        { "name": "tokenIds", "type": "uint256[]" }
+    ],
+    "outputs": [],
+    "stateMutability": "nonpayable"
+}]"#;
+
+/// Builds a `mint(address,uint256[])` call of ids 7 and 8.
+fn mint_request(contract: Address, recipient: Address) -> TransactionRequest {
+    let mut data = keccak256(b"mint(address,uint256[])")[..4].to_vec();
+    data.extend_from_slice(&[0; 12]);
+    data.extend_from_slice(recipient.as_bytes());
+    for word in [0x40u8, 2, 7, 8] {
+        let mut bytes = [0; 32];
+        bytes[31] = word;
+        data.extend_from_slice(&bytes);
+    }
+
+    TransactionRequest {
+        to: Some(contract),
+        data: Some(data.into()),
+        ..Default::default()
+    }
+}
+
+fn mint_info(recipient: Address, names: [&str; 2]) -> TransactionInfo {
+    TransactionInfo::ContractCall {
+        function: "mint(address,uint256[])".to_owned(),
+        parameters: vec![
+            CallParameter {
+                name: names[0].to_owned(),
+                kind: "address".to_owned(),
+                value: serde_json::json!(format!("{:#x}", recipient)),
+            },
+            CallParameter {
+                name: names[1].to_owned(),
+                kind: "uint256[]".to_owned(),
+                value: serde_json::json!(["0x7", "0x8"]),
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_abi_registry_decodes_by_selector() {
+    let mut registry = AbiRegistry::new();
+    registry.register_abi(ABI).unwrap();
+    let recipient = Address::random();
+
+    assert_eq!(
+        registry.transaction_info(&mint_request(Address::random(), recipient)),
+        mint_info(recipient, ["to", "ids"])
+    );
+}
+
+#[test]
+fn test_abi_registry_prefers_contract_abi() {
+    let contract = Address::random();
+    let mut registry = AbiRegistry::new();
+    registry.register_abi(ABI).unwrap();
+    registry.register_contract(contract, CONTRACT_ABI).unwrap();
+    let recipient = Address::random();
+
+    assert_eq!(
+        registry.transaction_info(&mint_request(contract, recipient)),
+        mint_info(recipient, ["recipient", "tokenIds"])
+    );
+    assert_eq!(
+        registry.transaction_info(&mint_request(Address::random(), recipient)),
+        mint_info(recipient, ["to", "ids"])
+    );
+}
+
+#[test]
+fn test_abi_registry_unknown_call() {
+    let registry = AbiRegistry::new();
+
+    assert!(matches!(
+        registry.transaction_info(&mint_request(Address::random(), Address::random())),
+        TransactionInfo::Unknown { .. }
+    ));
+}
+
+#[test]
+fn test_abi_registry_invalid_abi() {
+    let mut registry = AbiRegistry::new();
+
+    assert!(registry.register_abi(b"{").is_err());
+}
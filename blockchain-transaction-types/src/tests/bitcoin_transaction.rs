@@ -0,0 +1,240 @@
+use crate::models::account::AccountId;
+use crate::models::bitcoin_transaction::{BitcoinTransaction, OutPoint, Psbt, TxIn, TxOut};
+use crate::models::coin_type::CoinType;
+use crate::models::error::Error;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::{TransactionInfo, TransactionOutput};
+
+/// The native P2WPKH example of BIP-143.
+const UNSIGNED_TX: &str = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000";
+const P2PK_SCRIPT: &str = "2103c9f4836b9a4f77fc0d81f7bcb01b7f1b35916864b9476c241ce9fc198bd25432ac";
+const P2WPKH_SCRIPT: &str = "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1";
+const P2WPKH_SIGHASH: &str = "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670";
+
+fn push_var_bytes(psbt: &mut Vec<u8>, bytes: &[u8]) {
+    if bytes.len() < 0xfd {
+        psbt.push(bytes.len() as u8);
+    } else {
+        psbt.push(0xfd);
+        psbt.extend_from_slice(&(bytes.len() as u16).to_le_bytes());
+    }
+    psbt.extend_from_slice(bytes);
+}
+
+fn push_key_value(psbt: &mut Vec<u8>, key: &[u8], value: &[u8]) {
+    push_var_bytes(psbt, key);
+    push_var_bytes(psbt, value);
+}
+
+fn witness_utxo(value: u64, script_pubkey: &str) -> Vec<u8> {
+    let script_pubkey = hex::decode(script_pubkey).unwrap();
+    let mut bytes = value.to_le_bytes().to_vec();
+    bytes.push(script_pubkey.len() as u8);
+    bytes.extend_from_slice(&script_pubkey);
+    bytes
+}
+
+/// Builds a binary PSBT from an unsigned transaction and the key-value pairs
+/// of each input map, with empty output maps.
+fn psbt(unsigned_tx: &[u8], inputs: &[Vec<(Vec<u8>, Vec<u8>)>], outputs: usize) -> Vec<u8> {
+    let mut psbt = b"psbt\xff".to_vec();
+    push_key_value(&mut psbt, &[0x00], unsigned_tx);
+    psbt.push(0x00);
+    for input in inputs {
+        for (key, value) in input {
+            push_key_value(&mut psbt, key, value);
+        }
+        psbt.push(0x00);
+    }
+    psbt.resize(psbt.len() + outputs, 0x00);
+    psbt
+}
+
+fn bip_143_psbt() -> Vec<u8> {
+    psbt(
+        &hex::decode(UNSIGNED_TX).unwrap(),
+        &[
+            vec![(vec![0x01], witness_utxo(625_000_000, P2PK_SCRIPT))],
+            vec![(vec![0x01], witness_utxo(600_000_000, P2WPKH_SCRIPT))],
+        ],
+        2,
+    )
+}
+
+#[test]
+fn test_psbt_from_raw() {
+    let psbt = Psbt::from_raw(&bip_143_psbt()).unwrap();
+
+    assert_eq!(psbt.unsigned_tx.version, 1);
+    assert_eq!(psbt.unsigned_tx.inputs.len(), 2);
+    assert_eq!(psbt.unsigned_tx.outputs.len(), 2);
+    assert_eq!(psbt.unsigned_tx.lock_time, 17);
+    assert_eq!(
+        psbt.unsigned_tx.serialize(),
+        hex::decode(UNSIGNED_TX).unwrap()
+    );
+    assert_eq!(
+        psbt.inputs[1].witness_utxo.as_ref().unwrap().value,
+        600_000_000
+    );
+}
+
+#[test]
+fn test_psbt_from_base64() {
+    let bytes = bip_143_psbt();
+    let encoded = base64::encode(&bytes);
+
+    assert_eq!(
+        Psbt::from_raw(encoded.as_bytes()).unwrap(),
+        Psbt::from_raw(&bytes).unwrap()
+    );
+    assert_eq!(
+        Psbt::from_json(serde_json::json!(encoded)).unwrap(),
+        Psbt::from_raw(&bytes).unwrap()
+    );
+}
+
+#[test]
+fn test_psbt_invalid_data() {
+    let mut bytes = bip_143_psbt();
+    assert!(matches!(
+        Psbt::from_raw(&bytes[1..]),
+        Err(Error::InvalidData)
+    ));
+    assert!(matches!(
+        Psbt::from_raw(&bytes[..bytes.len() - 1]),
+        Err(Error::InvalidData)
+    ));
+
+    bytes.push(0x00);
+    assert!(matches!(Psbt::from_raw(&bytes), Err(Error::InvalidData)));
+    assert!(matches!(
+        Psbt::from_json(serde_json::json!(42)),
+        Err(Error::InvalidData)
+    ));
+}
+
+#[test]
+fn test_psbt_transaction_info() {
+    let psbt = Psbt::from_raw(&bip_143_psbt()).unwrap();
+
+    assert_eq!(
+        psbt.transaction_info(),
+        TransactionInfo::UtxoTransfer {
+            outputs: vec![
+                TransactionOutput {
+                    script_pubkey: "76a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac".into(),
+                    amount: "0x6b22c20".into(),
+                },
+                TransactionOutput {
+                    script_pubkey: "76a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac".into(),
+                    amount: "0xd519390".into(),
+                },
+            ],
+            fee: Some("0x35004490".into()),
+        }
+    );
+}
+
+#[test]
+fn test_psbt_p2wpkh_sighash() {
+    let psbt = Psbt::from_raw(&bip_143_psbt()).unwrap();
+
+    assert_eq!(hex::encode(psbt.sighash(1).unwrap()), P2WPKH_SIGHASH);
+    assert!(matches!(psbt.sighash(2), Err(Error::InvalidData)));
+}
+
+#[test]
+fn test_psbt_unsupported_sighash_type() {
+    let mut psbt = Psbt::from_raw(&bip_143_psbt()).unwrap();
+    psbt.inputs[1].sighash_type = Some(0x81);
+
+    assert!(matches!(psbt.sighash(1), Err(Error::InvalidData)));
+}
+
+#[test]
+fn test_psbt_non_witness_utxo() {
+    let previous = BitcoinTransaction {
+        version: 2,
+        inputs: vec![TxIn {
+            previous_output: OutPoint {
+                txid: [1; 32],
+                vout: 0,
+            },
+            script_sig: vec![],
+            sequence: 0xffffffff,
+        }],
+        outputs: vec![TxOut {
+            value: 10_000,
+            script_pubkey: hex::decode(P2WPKH_SCRIPT).unwrap(),
+        }],
+        lock_time: 0,
+    };
+    let unsigned_tx = BitcoinTransaction {
+        version: 2,
+        inputs: vec![TxIn {
+            previous_output: OutPoint {
+                txid: previous.txid(),
+                vout: 0,
+            },
+            script_sig: vec![],
+            sequence: 0xffffffff,
+        }],
+        outputs: vec![TxOut {
+            value: 9_000,
+            script_pubkey: hex::decode(P2WPKH_SCRIPT).unwrap(),
+        }],
+        lock_time: 0,
+    };
+
+    let mut psbt = Psbt::from_raw(&psbt(
+        &unsigned_tx.serialize(),
+        &[vec![(vec![0x00], previous.serialize())]],
+        1,
+    ))
+    .unwrap();
+    assert_eq!(psbt.inputs[0].non_witness_utxo.as_ref(), Some(&previous));
+    assert_eq!(psbt.fee(), Some(1_000));
+    assert!(psbt.sighash(0).is_ok());
+
+    psbt.unsigned_tx.inputs[0].previous_output.txid = [2; 32];
+    assert_eq!(psbt.fee(), None);
+    assert!(matches!(psbt.sighash(0), Err(Error::InvalidData)));
+}
+
+#[test]
+fn test_psbt_from_json() {
+    let known = crate::known_transaction_request_type_from_json(
+        serde_json::json!(base64::encode(bip_143_psbt())),
+        CoinType::Bitcoin,
+        None,
+    )
+    .expect("Could not identify transaction");
+
+    assert!(matches!(
+        known.transaction_request().transaction_info(),
+        TransactionInfo::UtxoTransfer { .. }
+    ));
+
+    let sender = AccountId::Bitcoin(hex::decode(P2PK_SCRIPT).unwrap());
+    assert_eq!(known.sender(), Some(sender.clone()));
+    assert_eq!(known.fee_payer(), Some(sender));
+}
+
+#[cfg(feature = "signing")]
+#[test]
+fn test_psbt_message_hash() {
+    use crate::models::transaction::SignableTransactionRequest;
+
+    let mut psbt = Psbt::from_raw(&bip_143_psbt()).unwrap();
+    assert!(matches!(psbt.message_hash(0), Err(Error::InvalidData)));
+
+    let sighashes = psbt.sighashes().unwrap();
+    assert_eq!(sighashes.len(), 2);
+    assert_eq!(hex::encode(sighashes[1]), P2WPKH_SIGHASH);
+
+    psbt.unsigned_tx.inputs.truncate(1);
+    psbt.inputs.truncate(1);
+    let message = psbt.message_hash(0).unwrap();
+    assert_eq!(message, psbt.sighash(0).unwrap());
+}
@@ -0,0 +1,37 @@
+use crate::models::chain_info::{chain_info, validate_chain, CHAINS};
+use crate::models::coin_type::CoinType;
+use crate::models::error::Error;
+
+#[test]
+fn test_chain_info() {
+    let chain = chain_info(137).unwrap();
+
+    assert_eq!(chain.name, "Polygon");
+    assert_eq!(chain.native_symbol, "MATIC");
+    assert_eq!(chain.native_decimals, 18);
+    assert!(!chain.is_testnet);
+    assert!(chain_info(11155111).unwrap().is_testnet);
+    assert_eq!(chain_info(0), None);
+}
+
+#[test]
+fn test_chain_ids_are_unique() {
+    for (index, chain) in CHAINS.iter().enumerate() {
+        assert!(CHAINS[..index]
+            .iter()
+            .all(|other| other.chain_id != chain.chain_id));
+    }
+}
+
+#[test]
+fn test_validate_chain() {
+    assert_eq!(validate_chain(CoinType::Ethereum, 1).unwrap().chain_id, 1);
+    assert!(matches!(
+        validate_chain(CoinType::Solana, 1),
+        Err(Error::InvalidCoinType)
+    ));
+    assert!(matches!(
+        validate_chain(CoinType::Ethereum, 31337),
+        Err(Error::UnknownChain(31337))
+    ));
+}
@@ -0,0 +1,55 @@
+use crate::models::coin_type::CoinType;
+
+#[test]
+fn test_coin_type_from_number() {
+    assert_eq!(CoinType::from(60), CoinType::Ethereum);
+    assert_eq!(CoinType::from(0x8000_01f5), CoinType::Solana);
+    assert_eq!(CoinType::from(1234), CoinType::Other(1234));
+    assert_eq!(CoinType::Other(1234).slip44(), Some(1234));
+    assert_eq!(CoinType::Bitcoin.slip44(), Some(0));
+    assert_eq!(CoinType::Unknown.slip44(), None);
+}
+
+#[test]
+fn test_coin_type_from_str() {
+    assert_eq!("60".parse::<CoinType>().unwrap(), CoinType::Ethereum);
+    assert_eq!("501'".parse::<CoinType>().unwrap(), CoinType::Solana);
+    assert_eq!("0h".parse::<CoinType>().unwrap(), CoinType::Bitcoin);
+    assert_eq!("eth".parse::<CoinType>().unwrap(), CoinType::Ethereum);
+    assert_eq!(
+        "Bitcoin Cash".parse::<CoinType>().unwrap(),
+        CoinType::BitcoinCash
+    );
+    assert_eq!("4242".parse::<CoinType>().unwrap(), CoinType::Other(4242));
+    assert!("dogecoin2".parse::<CoinType>().is_err());
+    assert!("2147483648".parse::<CoinType>().is_err());
+}
+
+#[test]
+fn test_coin_type_names() {
+    assert_eq!(CoinType::Ethereum.symbol(), Some("ETH"));
+    assert_eq!(CoinType::Solana.name(), Some("Solana"));
+    assert_eq!(CoinType::Other(4242).name(), None);
+}
+
+#[test]
+fn test_coin_type_json() {
+    assert_eq!(
+        serde_json::to_value(CoinType::Ethereum).unwrap(),
+        serde_json::json!(60)
+    );
+    assert_eq!(
+        serde_json::to_value(CoinType::Unknown).unwrap(),
+        serde_json::json!(-1)
+    );
+
+    for (json, coin_type) in [
+        (serde_json::json!(501), CoinType::Solana),
+        (serde_json::json!(-1), CoinType::Unknown),
+        (serde_json::json!(4242), CoinType::Other(4242)),
+        (serde_json::json!("btc"), CoinType::Bitcoin),
+    ] {
+        assert_eq!(serde_json::from_value::<CoinType>(json).unwrap(), coin_type);
+    }
+    assert!(serde_json::from_value::<CoinType>(serde_json::json!(-2)).is_err());
+}
@@ -0,0 +1,122 @@
+use crate::models::cosmos_transaction::{CosmosTransactionRequest, SignDoc, MSG_SEND_TYPE_URL};
+use crate::models::error::Error;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::TransactionInfo;
+
+const FROM: &str = "cosmos1syavy2npfyt9tcncdtsdzf7kny9lh777pahuux";
+const TO: &str = "cosmos1zt50azupanqlfam5afhv3hexwyutnukeh4c573";
+
+/// A `TxBody` with a `MsgSend` of 1000uatom from `FROM` to `TO`.
+const BODY_BYTES: &str = "Co0BChwvY29zbW9zLmJhbmsudjFiZXRhMS5Nc2dTZW5kEm0KLWNvc21vczFzeWF2eTJucGZ5dDl0Y25jZHRzZHpmN2tueTlsaDc3N3BhaHV1eBItY29zbW9zMXp0NTBhenVwYW5xbGZhbTVhZmh2M2hleHd5dXRudWtlaDRjNTczGg0KBXVhdG9tEgQxMDAwEgRtZW1v";
+/// An `AuthInfo` with a single signer at sequence 5 and a fee of 500uatom.
+const AUTH_INFO_BYTES: &str = "ClAKRgofL2Nvc21vcy5jcnlwdG8uc2VjcDI1NmsxLlB1YktleRIjCiECAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAASBAoCCAEYBRISCgwKBXVhdG9tEgM1MDAQwJoM";
+/// The `SignDoc` of the body and auth info on `cosmoshub-4`.
+const SIGN_DOC: &str = "0a96010a8d010a1c2f636f736d6f732e62616e6b2e763162657461312e4d736753656e64126d0a2d636f736d6f73317379617679326e706679743974636e63647473647a66376b6e79396c68373737706168757578122d636f736d6f73317a743530617a7570616e716c66616d356166687633686578777975746e756b656834633537331a0d0a057561746f6d12043130303012046d656d6f12660a500a460a1f2f636f736d6f732e63727970746f2e736563703235366b312e5075624b657912230a2102000000000000000000000000000000000000000000000000000000000000000012040a020801180512120a0c0a057561746f6d120335303010c09a0c1a0b636f736d6f736875622d34202a";
+const SIGN_DOC_HASH: &str = "3cdaa1766c92f954e12011da2fb1e1a318ec49120d4c8da84870fa07dbe1086d";
+
+fn sign_doc_json() -> serde_json::Value {
+    serde_json::json!({
+        "bodyBytes": BODY_BYTES,
+        "authInfoBytes": AUTH_INFO_BYTES,
+        "chainId": "cosmoshub-4",
+        "accountNumber": "42",
+    })
+}
+
+#[test]
+fn test_cosmos_transaction_from_json() {
+    let request = CosmosTransactionRequest::from_json(sign_doc_json()).unwrap();
+
+    assert_eq!(request.sign_doc.chain_id, "cosmoshub-4");
+    assert_eq!(request.sign_doc.account_number, 42);
+    assert_eq!(request.body.memo, "memo");
+    assert_eq!(request.body.messages[0].type_url, MSG_SEND_TYPE_URL);
+    assert_eq!(request.auth_info.signer_infos[0].sequence, 5);
+
+    let fee = request.auth_info.fee.as_ref().unwrap();
+    assert_eq!(fee.gas_limit, 200_000);
+    assert_eq!(fee.amount[0].amount, "500");
+    assert_eq!(
+        request,
+        CosmosTransactionRequest::from_raw(&hex::decode(SIGN_DOC).unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn test_cosmos_sign_doc_encoding() {
+    let sign_doc = SignDoc::decode(&hex::decode(SIGN_DOC).unwrap()).unwrap();
+
+    assert_eq!(hex::encode(sign_doc.encode()), SIGN_DOC);
+    assert_eq!(hex::encode(sign_doc.signing_hash()), SIGN_DOC_HASH);
+}
+
+#[test]
+fn test_cosmos_msg_send_info() {
+    let request = CosmosTransactionRequest::from_json(sign_doc_json()).unwrap();
+
+    assert_eq!(
+        request.transaction_info(),
+        TransactionInfo::TokenTransfer {
+            from: FROM.to_owned(),
+            to: TO.to_owned(),
+            amount: "0x3e8".to_owned(),
+            token_id: Some("uatom".to_owned()),
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_unknown_cosmos_message_info() {
+    let mut request = CosmosTransactionRequest::from_json(sign_doc_json()).unwrap();
+    request.body.messages[0].type_url = "/cosmos.staking.v1beta1.MsgDelegate".to_owned();
+
+    assert_eq!(
+        request.transaction_info(),
+        TransactionInfo::Unknown {
+            value: None,
+            function: Some("/cosmos.staking.v1beta1.MsgDelegate".to_owned()),
+        }
+    );
+}
+
+#[test]
+fn test_invalid_cosmos_transaction() {
+    let mut json = sign_doc_json();
+    json["bodyBytes"] = serde_json::json!("not base64!");
+    assert!(matches!(
+        CosmosTransactionRequest::from_json(json),
+        Err(Error::InvalidField { field, .. }) if field == "bodyBytes"
+    ));
+
+    let truncated = hex::decode(&SIGN_DOC[..SIGN_DOC.len() - 6]).unwrap();
+    assert!(matches!(
+        CosmosTransactionRequest::from_raw(&truncated),
+        Err(Error::InvalidData)
+    ));
+}
+
+#[cfg(feature = "all-chains")]
+#[test]
+fn test_known_cosmos_transaction() {
+    use crate::models::account::AccountId;
+    use crate::models::coin_type::CoinType;
+
+    let known =
+        crate::known_transaction_request_type_from_json(sign_doc_json(), CoinType::Cosmos, None)
+            .expect("Could not identify transaction");
+
+    assert_eq!(known.sender(), Some(AccountId::Cosmos(FROM.to_owned())));
+    assert_eq!(known.fee_payer(), Some(AccountId::Cosmos(FROM.to_owned())));
+}
+
+#[cfg(feature = "signing")]
+#[test]
+fn test_cosmos_transaction_message_hash() {
+    use crate::models::transaction::SignableTransactionRequest;
+
+    let request = CosmosTransactionRequest::from_json(sign_doc_json()).unwrap();
+
+    assert_eq!(hex::encode(request.message_hash(0).unwrap()), SIGN_DOC_HASH);
+}
@@ -0,0 +1,94 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use web3::types::Address;
+
+use crate::models::ens_resolver::{
+    addresses, resolve_names, CachingEnsResolver, EnsName, EnsResolver,
+};
+use crate::models::error::Error;
+use crate::models::transaction_info::TransactionInfo;
+
+/// Names `0x0101…01` and fails for `0x0303…03`, counting lookups.
+#[derive(Default)]
+struct TestResolver {
+    lookups: AtomicUsize,
+}
+
+#[async_trait]
+impl EnsResolver for TestResolver {
+    async fn lookup(&self, address: Address) -> Result<Option<EnsName>, Error> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        if address == Address::repeat_byte(1) {
+            Ok(Some(EnsName {
+                name: "alice.eth".to_owned(),
+                avatar: Some("https://example.com/alice.png".to_owned()),
+            }))
+        } else if address == Address::repeat_byte(3) {
+            Err(Error::InvalidData)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn address(byte: u8) -> String {
+    format!("{:#x}", Address::repeat_byte(byte))
+}
+
+fn safe_transfer() -> TransactionInfo {
+    TransactionInfo::SafeTransaction {
+        safe: address(3),
+        to: address(4),
+        value: "0x0".to_owned(),
+        operation: 0,
+        info: Box::new(TransactionInfo::TokenTransfer {
+            from: address(3),
+            to: address(1),
+            amount: "0x1".to_owned(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }),
+    }
+}
+
+#[test]
+fn test_transaction_addresses() {
+    assert_eq!(
+        addresses(&safe_transfer()).into_iter().collect::<Vec<_>>(),
+        vec![
+            Address::repeat_byte(1),
+            Address::repeat_byte(3),
+            Address::repeat_byte(4)
+        ]
+    );
+    assert!(addresses(&TransactionInfo::Unknown {
+        value: None,
+        function: None
+    })
+    .is_empty());
+}
+
+#[tokio::test]
+async fn test_resolve_names() {
+    let names = resolve_names(&safe_transfer(), &TestResolver::default()).await;
+
+    assert_eq!(names.len(), 1);
+    assert_eq!(names[&Address::repeat_byte(1)].name, "alice.eth");
+}
+
+#[tokio::test]
+async fn test_caching_resolver() {
+    let resolver = CachingEnsResolver::new(TestResolver::default());
+
+    resolve_names(&safe_transfer(), &resolver).await;
+    resolve_names(&safe_transfer(), &resolver).await;
+
+    // Only the failed lookup of 0x0303…03 is repeated.
+    assert_eq!(resolver.inner().lookups.load(Ordering::SeqCst), 4);
+
+    resolver.clear();
+    resolve_names(&safe_transfer(), &resolver).await;
+    assert_eq!(resolver.inner().lookups.load(Ordering::SeqCst), 7);
+}
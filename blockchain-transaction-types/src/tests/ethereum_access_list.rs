@@ -0,0 +1,146 @@
+use web3::types::{AccessListItem, Address, TransactionRequest, H256};
+
+use crate::models::ethereum_access_list::{with_access_list, AccessListBuilder};
+
+fn address(byte: u8) -> Address {
+    Address::repeat_byte(byte)
+}
+
+fn storage_key(index: u64) -> H256 {
+    H256::from_low_u64_be(index)
+}
+
+#[test]
+fn test_access_list_builder() {
+    let mut builder = AccessListBuilder::new();
+    builder
+        .add_storage_key(address(2), storage_key(2))
+        .add_address(address(1))
+        .add_storage_key(address(2), storage_key(1))
+        .add_storage_key(address(2), storage_key(2))
+        .add_address(address(2));
+
+    assert_eq!(
+        builder.build(),
+        vec![
+            AccessListItem {
+                address: address(1),
+                storage_keys: vec![],
+            },
+            AccessListItem {
+                address: address(2),
+                storage_keys: vec![storage_key(1), storage_key(2)],
+            },
+        ]
+    );
+
+    builder.remove_empty(&address(1)).remove_empty(&address(2));
+    assert_eq!(builder.build().len(), 1);
+}
+
+#[test]
+fn test_access_list_builder_extend() {
+    let mut builder = AccessListBuilder::new();
+    builder.add_storage_key(address(1), storage_key(1));
+    builder.extend(&[AccessListItem {
+        address: address(1),
+        storage_keys: vec![storage_key(1), storage_key(3)],
+    }]);
+
+    assert_eq!(
+        builder.build(),
+        vec![AccessListItem {
+            address: address(1),
+            storage_keys: vec![storage_key(1), storage_key(3)],
+        }]
+    );
+}
+
+#[test]
+fn test_with_access_list() {
+    let legacy = TransactionRequest {
+        to: Some(address(1)),
+        gas_price: Some(1.into()),
+        ..Default::default()
+    };
+    let eip_1559 = TransactionRequest {
+        transaction_type: Some(2.into()),
+        max_fee_per_gas: Some(1.into()),
+        ..legacy.clone()
+    };
+    let mut builder = AccessListBuilder::new();
+    builder.add_address(address(2));
+
+    let request = with_access_list(&legacy, builder.build());
+    assert_eq!(request.transaction_type, Some(1.into()));
+    assert_eq!(request.access_list, Some(builder.build()));
+    assert_eq!(
+        with_access_list(&eip_1559, builder.build()).transaction_type,
+        Some(2.into())
+    );
+}
+
+#[cfg(feature = "signing")]
+#[test]
+fn test_with_access_list_encodes_eip_2930() {
+    use crate::models::transaction::SignableTransactionRequest;
+
+    let legacy = TransactionRequest {
+        to: Some(address(1)),
+        gas_price: Some(1.into()),
+        ..Default::default()
+    };
+    let mut builder = AccessListBuilder::new();
+    builder.add_storage_key(address(2), storage_key(1));
+
+    let request = with_access_list(&legacy, builder.build());
+    assert_ne!(
+        request.message_hash(1).unwrap(),
+        legacy.message_hash(1).unwrap()
+    );
+}
+
+#[cfg(feature = "access-list-provider")]
+#[tokio::test]
+async fn test_fill_access_list() {
+    use crate::models::ethereum_access_list::{fill_access_list, Web3AccessListProvider};
+    use crate::tests::helpers::transport::TestTransport;
+
+    // Answers `eth_createAccessList` with a single storage key
+    let transport = TestTransport::new().with_handler("eth_createAccessList", |params| {
+        assert_eq!(params[1], "latest");
+        Ok(serde_json::json!({
+            "accessList": [{
+                "address": address(3),
+                "storageKeys": [storage_key(7)],
+            }],
+            "gasUsed": "0x5208",
+        }))
+    });
+
+    let mut builder = AccessListBuilder::new();
+    builder.add_address(address(2));
+    let request = TransactionRequest {
+        to: Some(address(1)),
+        transaction_type: Some(2.into()),
+        access_list: Some(builder.build()),
+        ..Default::default()
+    };
+
+    let provider = Web3AccessListProvider::new(transport);
+    let request = fill_access_list(&request, &provider).await.unwrap();
+
+    assert_eq!(
+        request.access_list,
+        Some(vec![
+            AccessListItem {
+                address: address(2),
+                storage_keys: vec![],
+            },
+            AccessListItem {
+                address: address(3),
+                storage_keys: vec![storage_key(7)],
+            },
+        ])
+    );
+}
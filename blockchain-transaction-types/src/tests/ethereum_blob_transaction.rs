@@ -0,0 +1,124 @@
+use web3::types::{Address, H256};
+
+use crate::models::coin_type::CoinType;
+use crate::models::error::Error;
+use crate::models::ethereum_blob_transaction::{BlobSidecar, BlobTransactionRequest};
+use crate::models::known_transaction_type::KnownTransactionRequestType;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::TransactionInfo;
+
+fn sidecar() -> BlobSidecar {
+    BlobSidecar {
+        commitments: vec![vec![0xc0; 48].into()],
+        proofs: vec![vec![0xc0; 48].into()],
+    }
+}
+
+fn blob_request_json(versioned_hash: H256) -> serde_json::Value {
+    serde_json::json!({
+        "type": "0x3",
+        "from": Address::zero(),
+        "to": Address::random(),
+        "gas": "0x5208",
+        "maxPriorityFeePerGas": "0x3b9aca00",
+        "maxFeePerGas": "0x4a817c800",
+        "maxFeePerBlobGas": "0x1",
+        "value": "0x1",
+        "nonce": "0x0",
+        "blobVersionedHashes": [versioned_hash],
+        "sidecar": sidecar(),
+    })
+}
+
+#[test]
+fn test_blob_sidecar_versioned_hashes() {
+    let hashes = sidecar().versioned_hashes();
+
+    assert_eq!(hashes.len(), 1);
+    assert_eq!(hashes[0].0[0], 0x01);
+    assert!(sidecar().matches(&hashes));
+    assert!(!sidecar().matches(&[H256::zero()]));
+    assert!(!BlobSidecar {
+        proofs: vec![],
+        ..sidecar()
+    }
+    .matches(&hashes));
+}
+
+#[test]
+fn test_blob_transaction_from_json() {
+    let json = blob_request_json(sidecar().versioned_hashes()[0]);
+    let known = crate::known_transaction_request_type_from_json(json, CoinType::Ethereum, Some(1))
+        .expect("Could not identify transaction");
+
+    match &known {
+        KnownTransactionRequestType::EthereumBlob(request) => {
+            assert_eq!(request.max_fee_per_blob_gas, 1.into());
+            assert_eq!(request.sidecar, Some(sidecar()));
+        }
+        _ => panic!("Transaction should be a blob transaction"),
+    }
+    assert!(matches!(
+        known.transaction_request().transaction_info(),
+        TransactionInfo::TokenTransfer { .. }
+    ));
+}
+
+#[test]
+fn test_blob_transaction_sidecar_mismatch() {
+    let json = blob_request_json(H256::zero());
+
+    assert!(matches!(
+        BlobTransactionRequest::from_json(json),
+        Err(Error::InvalidData)
+    ));
+}
+
+#[cfg(feature = "signing")]
+#[tokio::test]
+async fn test_blob_transaction_encode_signed() {
+    use crate::models::transaction::{SignableTransactionRequest, Transaction};
+    use crate::tests::helpers::signer::TestSigner;
+
+    let chain_id = 1;
+    let signer = TestSigner::new();
+    let sender_address = signer.ethereum_address();
+    let request =
+        BlobTransactionRequest::from_json(blob_request_json(sidecar().versioned_hashes()[0]))
+            .unwrap();
+
+    let transaction: Box<dyn SignableTransactionRequest> = Box::new(request.clone());
+    let (signature, recovery_id) = transaction
+        .sign_transaction(chain_id, move |message| {
+            signer.sign_recoverable(message, Some(chain_id))
+        })
+        .await
+        .expect("Could not sign transaction");
+    let raw = transaction
+        .encode_signed(chain_id, &signature, recovery_id)
+        .expect("Could not encode transaction");
+
+    assert_eq!(raw[0], 0x03);
+    let decoded = BlobTransactionRequest::from_raw(&raw).unwrap();
+    assert_eq!(decoded.request.from, sender_address);
+    assert_eq!(decoded.request.to, request.request.to);
+    assert_eq!(decoded.max_fee_per_blob_gas, request.max_fee_per_blob_gas);
+    assert_eq!(decoded.blob_versioned_hashes, request.blob_versioned_hashes);
+    assert_eq!(
+        web3::types::Transaction::from_raw(&raw).unwrap().from,
+        Some(sender_address)
+    );
+}
+
+#[test]
+fn test_blob_transaction_fee_info() {
+    let json = blob_request_json(sidecar().versioned_hashes()[0]);
+    let request = BlobTransactionRequest::from_json(json).unwrap();
+    let fee_info = request.fee_info().unwrap();
+
+    assert_eq!(fee_info.max_fee_per_blob_gas.as_deref(), Some("0x1"));
+    assert_eq!(
+        fee_info.max_total_fee,
+        Some(format!("{:#x}", 0x5208u64 * 0x4a817c800 + 1 + (1 << 17)))
+    );
+}
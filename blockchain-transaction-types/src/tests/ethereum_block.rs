@@ -0,0 +1,110 @@
+use serde_json::json;
+use web3::types::{Address, H256};
+
+use crate::models::abi_registry::AbiRegistry;
+use crate::models::error::Error;
+use crate::models::ethereum_block::classify_block;
+use crate::models::transaction_info::TransactionInfo;
+
+const MINT_ABI: &[u8] = br#"[{
+    "type": "function",
+    "name": "mint",
+    "inputs": [{ "name": "amount", "type": "uint256" }],
+    "outputs": [],
+    "stateMutability": "nonpayable"
+}]"#;
+
+fn address(byte: u8) -> String {
+    format!("{:#x}", Address::repeat_byte(byte))
+}
+
+fn transaction(index: u64, to: &str, value: &str, input: String) -> serde_json::Value {
+    json!({
+        "hash": H256::from_low_u64_be(index + 1),
+        "nonce": format!("{:#x}", index),
+        "blockHash": H256::repeat_byte(0xbb),
+        "blockNumber": "0x10",
+        "transactionIndex": format!("{:#x}", index),
+        "from": address(1),
+        "to": to,
+        "value": value,
+        "gasPrice": "0x1",
+        "gas": "0x5208",
+        "input": input,
+    })
+}
+
+fn block() -> serde_json::Value {
+    json!({
+        "hash": H256::repeat_byte(0xbb),
+        "number": "0x10",
+        "transactions": [
+            transaction(
+                0,
+                &address(0xaa),
+                "0x0",
+                format!("0xa9059cbb{:0>64}{:064x}", &address(2)[2..], 42)
+            ),
+            transaction(1, &address(2), "0xde0b6b3a7640000", "0x".to_owned()),
+            transaction(2, &address(0xcc), "0x0", format!("0xa0712d68{:064x}", 7)),
+        ],
+    })
+}
+
+#[test]
+fn test_classify_block() {
+    let classified = classify_block(block()).unwrap();
+    let hashes: Vec<H256> = classified.iter().map(|(hash, _)| *hash).collect();
+
+    assert_eq!(
+        hashes,
+        (1..=3).map(H256::from_low_u64_be).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        classified[0].1,
+        TransactionInfo::TokenTransfer {
+            from: address(1),
+            to: address(2),
+            amount: "0x2a".to_owned(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+    assert_eq!(
+        classified[1].1,
+        TransactionInfo::Unknown {
+            value: Some("0xde0b6b3a7640000".to_owned()),
+            function: None,
+        }
+    );
+    assert_eq!(
+        classified[2].1,
+        TransactionInfo::Unknown {
+            value: Some("0x0".to_owned()),
+            function: None,
+        }
+    );
+}
+
+#[test]
+fn test_classify_block_with_registry() {
+    let mut registry = AbiRegistry::new();
+    registry.register_abi(MINT_ABI).unwrap();
+
+    let classified = registry.classify_block(block()).unwrap();
+    assert!(matches!(
+        &classified[2].1,
+        TransactionInfo::ContractCall { function, .. } if function == "mint(uint256)"
+    ));
+}
+
+#[test]
+fn test_classify_block_without_full_transactions() {
+    let block = json!({ "transactions": [H256::from_low_u64_be(1)] });
+
+    match classify_block(block) {
+        Err(Error::Decode { path, .. }) => assert_eq!(path, "transactions[0]"),
+        result => panic!("unexpected result {:?}", result),
+    }
+}
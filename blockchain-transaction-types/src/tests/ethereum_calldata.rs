@@ -0,0 +1,122 @@
+use serde_json::json;
+use web3::ethabi::{encode, Token};
+use web3::types::{Address, Bytes, TransactionRequest, H256, U256};
+
+use crate::models::ethereum_calldata::{decoded_call, DecodedCall};
+use crate::models::transaction_info::CallParameter;
+
+const RECIPIENT: &str = "0x0d4a03b23ae95409a4ecfe9396a9d39ca4f0fed1";
+
+fn calldata(selector: [u8; 4], tokens: &[Token]) -> Vec<u8> {
+    [&selector[..], &encode(tokens)].concat()
+}
+
+fn parameter(index: usize, kind: &str, value: serde_json::Value) -> CallParameter {
+    CallParameter {
+        name: format!("[{}]", index),
+        kind: kind.to_owned(),
+        value,
+    }
+}
+
+#[test]
+fn test_decoded_call_static_parameters() {
+    let hash = H256::repeat_byte(0xab);
+    let data = calldata(
+        [0x12, 0x34, 0x56, 0x78],
+        &[
+            Token::Address(RECIPIENT.parse().unwrap()),
+            Token::Uint(1000.into()),
+            Token::FixedBytes(hash.as_bytes().to_vec()),
+            Token::Uint(U256::MAX),
+        ],
+    );
+
+    assert_eq!(
+        DecodedCall::from_calldata(&data).unwrap(),
+        DecodedCall {
+            selector: "0x12345678".to_owned(),
+            parameters: vec![
+                parameter(0, "address", json!(RECIPIENT)),
+                parameter(1, "uint256", json!("0x3e8")),
+                parameter(2, "bytes32", json!(format!("{:#x}", hash))),
+                parameter(3, "uint256", json!(format!("{:#x}", U256::MAX))),
+            ],
+        }
+    );
+}
+
+#[test]
+fn test_decoded_call_dynamic_parameters() {
+    let data = calldata(
+        [0xde, 0xad, 0xbe, 0xef],
+        &[
+            Token::Bytes(b"hello".to_vec()),
+            Token::Uint(7.into()),
+            Token::Array(vec![
+                Token::Address(RECIPIENT.parse().unwrap()),
+                Token::Address(Address::repeat_byte(0x11)),
+            ]),
+            Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+        ],
+    );
+
+    assert_eq!(
+        DecodedCall::from_calldata(&data).unwrap().parameters,
+        vec![
+            parameter(0, "bytes", json!("0x68656c6c6f")),
+            parameter(1, "uint256", json!("0x7")),
+            parameter(
+                2,
+                "address[]",
+                json!([RECIPIENT, "0x1111111111111111111111111111111111111111"])
+            ),
+            parameter(3, "uint256[]", json!(["0x1", "0x2"])),
+        ]
+    );
+}
+
+#[test]
+fn test_decoded_call_truncated() {
+    assert_eq!(DecodedCall::from_calldata(&[0x12, 0x34]), None);
+
+    let mut data = calldata([0x12, 0x34, 0x56, 0x78], &[Token::Uint(64.into())]);
+    data.extend([0xff; 5]);
+    assert_eq!(
+        DecodedCall::from_calldata(&data).unwrap().parameters,
+        vec![parameter(0, "uint256", json!("0x40"))]
+    );
+}
+
+#[test]
+fn test_decoded_call_of_unknown_requests_only() {
+    let data = calldata([0x12, 0x34, 0x56, 0x78], &[Token::Uint(1.into())]);
+    let request = TransactionRequest {
+        to: Some(Address::repeat_byte(0x22)),
+        data: Some(Bytes(data)),
+        ..Default::default()
+    };
+    assert_eq!(
+        decoded_call(&request).unwrap().selector,
+        "0x12345678".to_owned()
+    );
+
+    let transfer = TransactionRequest {
+        data: Some(Bytes(calldata(
+            [0xa9, 0x05, 0x9c, 0xbb],
+            &[
+                Token::Address(RECIPIENT.parse().unwrap()),
+                Token::Uint(1.into()),
+            ],
+        ))),
+        ..request.clone()
+    };
+    assert_eq!(decoded_call(&transfer), None);
+    assert_eq!(
+        decoded_call(&TransactionRequest {
+            data: None,
+            ..request
+        }),
+        None
+    );
+}
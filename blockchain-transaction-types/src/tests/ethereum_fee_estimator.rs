@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use web3::types::{Address, TransactionRequest, U256};
+
+use crate::models::error::Error;
+use crate::models::ethereum_fee_estimator::{
+    prepare, FeeEstimator, FeeSuggestion, Web3FeeEstimator,
+};
+use crate::tests::helpers::transport::TestTransport;
+
+/// Estimates a fixed gas and fees.
+struct TestEstimator;
+
+#[async_trait]
+impl FeeEstimator for TestEstimator {
+    async fn estimate_gas(&self, _request: &TransactionRequest) -> Result<U256, Error> {
+        Ok(21_000.into())
+    }
+
+    async fn suggest_fees(&self) -> Result<FeeSuggestion, Error> {
+        Ok(FeeSuggestion {
+            max_fee_per_gas: 30.into(),
+            max_priority_fee_per_gas: 2.into(),
+        })
+    }
+}
+
+fn request() -> TransactionRequest {
+    TransactionRequest {
+        to: Some(Address::random()),
+        value: Some(1.into()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_prepare_eip_1559() {
+    let prepared = prepare(&request(), &TestEstimator).await.unwrap();
+
+    assert_eq!(prepared.transaction_type, Some(2.into()));
+    assert_eq!(prepared.gas, Some(21_000.into()));
+    assert_eq!(prepared.max_fee_per_gas, Some(30.into()));
+    assert_eq!(prepared.max_priority_fee_per_gas, Some(2.into()));
+    assert_eq!(prepared.gas_price, None);
+}
+
+#[tokio::test]
+async fn test_prepare_keeps_set_fields() {
+    let legacy = TransactionRequest {
+        gas: Some(50_000.into()),
+        gas_price: Some(7.into()),
+        ..request()
+    };
+    assert_eq!(prepare(&legacy, &TestEstimator).await.unwrap(), legacy);
+
+    let tip = TransactionRequest {
+        max_priority_fee_per_gas: Some(40.into()),
+        ..request()
+    };
+    let prepared = prepare(&tip, &TestEstimator).await.unwrap();
+    assert_eq!(prepared.max_priority_fee_per_gas, Some(40.into()));
+    assert_eq!(prepared.max_fee_per_gas, Some(40.into()));
+}
+
+#[tokio::test]
+async fn test_prepare_eip_2930() {
+    let request = TransactionRequest {
+        transaction_type: Some(1.into()),
+        ..request()
+    };
+    let prepared = prepare(&request, &TestEstimator).await.unwrap();
+
+    assert_eq!(prepared.gas_price, Some(30.into()));
+    assert_eq!(prepared.max_fee_per_gas, None);
+}
+
+/// Answers `eth_estimateGas` and `eth_feeHistory` with fixed results.
+fn transport() -> TestTransport {
+    TestTransport::new()
+        .with_handler("eth_estimateGas", |_| Ok(serde_json::json!("0x5208")))
+        .with_handler("eth_feeHistory", |params| {
+            assert_eq!(params[0], "0x2");
+            assert_eq!(params[2], serde_json::json!([25.0]));
+            Ok(serde_json::json!({
+                "oldestBlock": "0x10",
+                "baseFeePerGas": ["0x64", "0x6e", "0x78"],
+                "gasUsedRatio": [0.5, 0.9],
+                "reward": [["0x2"], ["0x4"]],
+            }))
+        })
+}
+
+#[tokio::test]
+async fn test_web3_fee_estimator() {
+    let estimator = Web3FeeEstimator::new(transport()).with_fee_history(2, 25.0);
+
+    assert_eq!(
+        estimator.estimate_gas(&request()).await.unwrap(),
+        21_000.into()
+    );
+    assert_eq!(
+        estimator.suggest_fees().await.unwrap(),
+        FeeSuggestion {
+            max_fee_per_gas: 243.into(),
+            max_priority_fee_per_gas: 3.into(),
+        }
+    );
+}
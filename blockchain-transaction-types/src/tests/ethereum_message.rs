@@ -0,0 +1,93 @@
+use crate::models::error::Error;
+use crate::models::ethereum_message::{hash_personal_message, Message};
+use crate::models::message::{Message as _, MessageInfo, SignableMessage};
+
+#[test]
+fn test_hash_personal_message() {
+    let hash = hash_personal_message(b"hello world");
+    assert_eq!(
+        hex::encode(hash.as_bytes()),
+        "d9eba16ed0ecae432b71fe008c98cc872bb4cc214d3220a36f365326cf807d68"
+    );
+}
+
+#[test]
+fn test_hash_empty_personal_message() {
+    let hash = hash_personal_message(b"");
+    assert_eq!(
+        hex::encode(hash.as_bytes()),
+        "5f35dce98ba4fba25530a026ed80b2cecdaa31091ba4958b99b52ea1d068adad"
+    );
+}
+
+#[test]
+fn test_string_message_info() {
+    let message = Message::from_json(serde_json::json!("hello world")).unwrap();
+
+    assert_eq!(message, Message::String("hello world".to_owned()));
+    assert_eq!(
+        message.message_info(),
+        MessageInfo::String("hello world".to_owned())
+    );
+}
+
+#[test]
+fn test_hex_message_info() {
+    let message = Message::from_json(serde_json::json!("0x68656c6c6f")).unwrap();
+    assert_eq!(
+        message.message_info(),
+        MessageInfo::Bytes {
+            hex: "0x68656c6c6f".to_owned(),
+            text: Some("hello".to_owned()),
+        }
+    );
+
+    let message = Message::from_json(serde_json::json!("0xff00")).unwrap();
+    assert_eq!(
+        message.message_info(),
+        MessageInfo::Bytes {
+            hex: "0xff00".to_owned(),
+            text: None,
+        }
+    );
+
+    let message = Message::from_json(serde_json::json!("0xnot hex")).unwrap();
+    assert_eq!(
+        message.message_info(),
+        MessageInfo::String("0xnot hex".to_owned())
+    );
+}
+
+#[test]
+fn test_hex_message_hash() {
+    let hex = Message::String("0x68656c6c6f".to_owned());
+    let text = Message::String("hello".to_owned());
+
+    assert_eq!(hex.message_hash(1).unwrap(), text.message_hash(1).unwrap());
+    assert_eq!(
+        Message::String("0xff00".to_owned())
+            .message_hash(1)
+            .unwrap(),
+        hash_personal_message(&[0xff, 0x00]).as_bytes()
+    );
+}
+
+#[test]
+fn test_message_from_raw() {
+    assert_eq!(
+        Message::from_raw(b"hello").unwrap(),
+        Message::String("hello".to_owned())
+    );
+    assert_eq!(
+        Message::from_raw(&[0xff, 0x00]).unwrap(),
+        Message::String("0xff00".to_owned())
+    );
+}
+
+#[test]
+fn test_invalid_message() {
+    assert!(matches!(
+        Message::from_json(serde_json::json!({ "message": "hello" })),
+        Err(Error::InvalidData)
+    ));
+}
@@ -0,0 +1,233 @@
+use web3::ethabi::{self, Token};
+use web3::types::{Address, Log, TransactionReceipt, H256, U256};
+
+use crate::models::ethereum_receipt::{decode_log, ReceiptInfo};
+use crate::models::keccak::keccak256;
+use crate::models::transaction_info::{TokenTransferInfo, TransactionInfo};
+
+fn topic(signature: &str) -> H256 {
+    H256(keccak256(signature.as_bytes()))
+}
+
+fn address_topic(byte: u8) -> H256 {
+    Address::repeat_byte(byte).into()
+}
+
+fn address(byte: u8) -> String {
+    format!("{:#x}", Address::repeat_byte(byte))
+}
+
+fn log(topics: Vec<H256>, data: Vec<u8>) -> Log {
+    serde_json::from_value(serde_json::json!({
+        "address": Address::repeat_byte(0xaa),
+        "topics": topics,
+        "data": web3::types::Bytes(data),
+        "logIndex": "0x3",
+    }))
+    .unwrap()
+}
+
+fn erc20_transfer() -> Log {
+    log(
+        vec![
+            topic("Transfer(address,address,uint256)"),
+            address_topic(1),
+            address_topic(2),
+        ],
+        ethabi::encode(&[Token::Uint(42.into())]),
+    )
+}
+
+fn token_transfer(amount: &str, token_id: Option<&str>) -> TransactionInfo {
+    TransactionInfo::TokenTransfer {
+        from: address(1),
+        to: address(2),
+        amount: amount.to_owned(),
+        token_id: token_id.map(str::to_owned),
+        token_info: None,
+        fiat_value: None,
+    }
+}
+
+#[test]
+fn test_decode_transfer_logs() {
+    assert_eq!(
+        decode_log(&erc20_transfer()),
+        Some(token_transfer("0x2a", None))
+    );
+
+    let erc721 = log(
+        vec![
+            topic("Transfer(address,address,uint256)"),
+            address_topic(1),
+            address_topic(2),
+            H256::from_low_u64_be(7),
+        ],
+        vec![],
+    );
+    assert_eq!(
+        decode_log(&erc721),
+        Some(token_transfer("0x1", Some("0x7")))
+    );
+}
+
+#[test]
+fn test_decode_approval_log() {
+    let approval = log(
+        vec![
+            topic("Approval(address,address,uint256)"),
+            address_topic(1),
+            address_topic(2),
+        ],
+        ethabi::encode(&[Token::Uint(U256::MAX)]),
+    );
+
+    assert_eq!(
+        decode_log(&approval),
+        Some(TransactionInfo::Approval {
+            owner: address(1),
+            spender: address(2),
+            amount: Some(format!("{:#x}", U256::MAX)),
+            token: None,
+            is_unlimited: true,
+        })
+    );
+}
+
+#[test]
+fn test_decode_mint_and_burn_logs() {
+    let mint = log(
+        vec![
+            topic("Transfer(address,address,uint256)"),
+            H256::zero(),
+            address_topic(2),
+        ],
+        ethabi::encode(&[Token::Uint(42.into())]),
+    );
+    let burn = log(
+        vec![
+            topic("TransferSingle(address,address,address,uint256,uint256)"),
+            address_topic(9),
+            address_topic(1),
+            H256::zero(),
+        ],
+        ethabi::encode(&[Token::Uint(7.into()), Token::Uint(3.into())]),
+    );
+
+    assert_eq!(
+        decode_log(&mint),
+        Some(TransactionInfo::TokenMint {
+            to: address(2),
+            amount: "0x2a".to_owned(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        })
+    );
+    assert_eq!(
+        decode_log(&burn),
+        Some(TransactionInfo::TokenBurn {
+            from: address(1),
+            amount: "0x3".to_owned(),
+            token_id: Some("0x7".to_owned()),
+            token_info: None,
+            fiat_value: None,
+        })
+    );
+}
+
+#[test]
+fn test_decode_erc1155_logs() {
+    let topics = |signature| {
+        vec![
+            topic(signature),
+            address_topic(9),
+            address_topic(1),
+            address_topic(2),
+        ]
+    };
+    let single = log(
+        topics("TransferSingle(address,address,address,uint256,uint256)"),
+        ethabi::encode(&[Token::Uint(7.into()), Token::Uint(3.into())]),
+    );
+    let batch = log(
+        topics("TransferBatch(address,address,address,uint256[],uint256[])"),
+        ethabi::encode(&[
+            Token::Array(vec![Token::Uint(7.into()), Token::Uint(8.into())]),
+            Token::Array(vec![Token::Uint(3.into()), Token::Uint(4.into())]),
+        ]),
+    );
+
+    assert_eq!(
+        decode_log(&single),
+        Some(token_transfer("0x3", Some("0x7")))
+    );
+    assert_eq!(
+        decode_log(&batch),
+        Some(TransactionInfo::TokenBatchTransfer {
+            transfers: vec![
+                TokenTransferInfo {
+                    from: address(1),
+                    to: address(2),
+                    amount: "0x3".to_owned(),
+                    token_id: Some("0x7".to_owned()),
+                    token_info: None,
+                    fiat_value: None,
+                },
+                TokenTransferInfo {
+                    from: address(1),
+                    to: address(2),
+                    amount: "0x4".to_owned(),
+                    token_id: Some("0x8".to_owned()),
+                    token_info: None,
+                    fiat_value: None,
+                },
+            ]
+        })
+    );
+}
+
+#[test]
+fn test_decode_unknown_log() {
+    assert_eq!(
+        decode_log(&log(vec![topic("Deposit(address,uint256)")], vec![])),
+        None
+    );
+    assert_eq!(decode_log(&log(vec![], vec![])), None);
+}
+
+fn receipt(logs: Vec<Log>) -> TransactionReceipt {
+    serde_json::from_value(serde_json::json!({
+        "transactionHash": H256::repeat_byte(0x11),
+        "transactionIndex": "0x0",
+        "cumulativeGasUsed": "0x5208",
+        "gasUsed": "0x5208",
+        "logs": logs,
+        "status": "0x1",
+        "logsBloom": format!("0x{}", "0".repeat(512)),
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_receipt_info() {
+    let info = ReceiptInfo::from(&receipt(vec![
+        erc20_transfer(),
+        log(vec![topic("Deposit(address,uint256)")], vec![]),
+    ]));
+
+    assert_eq!(info.succeeded, Some(true));
+    assert_eq!(info.gas_used.as_deref(), Some("0x5208"));
+    assert_eq!(info.events.len(), 1);
+    assert_eq!(info.events[0].contract, address(0xaa));
+    assert_eq!(info.events[0].log_index.as_deref(), Some("0x3"));
+    assert_eq!(info.transaction_info(), token_transfer("0x2a", None));
+
+    let info = ReceiptInfo::from(&receipt(vec![erc20_transfer(), erc20_transfer()]));
+    assert_eq!(
+        info.transaction_info(),
+        TransactionInfo::Batch {
+            calls: vec![token_transfer("0x2a", None), token_transfer("0x2a", None)]
+        }
+    );
+}
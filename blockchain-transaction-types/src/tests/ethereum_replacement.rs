@@ -0,0 +1,95 @@
+use web3::types::{Address, TransactionRequest, U256};
+
+use crate::models::error::Error;
+use crate::models::ethereum_replacement::{cancel, speed_up, validate_replacement, BumpStrategy};
+
+fn eip_1559_request() -> TransactionRequest {
+    TransactionRequest {
+        from: Address::repeat_byte(1),
+        to: Some(Address::repeat_byte(2)),
+        gas: Some(50_000.into()),
+        value: Some(1_000.into()),
+        data: Some(vec![0xd0, 0xe3, 0x0d, 0xb0].into()),
+        nonce: Some(7.into()),
+        max_fee_per_gas: Some(100.into()),
+        max_priority_fee_per_gas: Some(5.into()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_speed_up_by_percent() {
+    let original = eip_1559_request();
+    let replacement = speed_up(&original, BumpStrategy::Percent(10)).unwrap();
+
+    assert_eq!(replacement.nonce, original.nonce);
+    assert_eq!(replacement.data, original.data);
+    assert_eq!(replacement.max_fee_per_gas, Some(110.into()));
+    // 5.5 is rounded up, since rounding down would be under the 10% rule.
+    assert_eq!(replacement.max_priority_fee_per_gas, Some(6.into()));
+}
+
+#[test]
+fn test_speed_up_legacy_with_fees() {
+    let original = TransactionRequest {
+        gas_price: Some(20.into()),
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        ..eip_1559_request()
+    };
+    let strategy = |gas_price: u64| BumpStrategy::Fees {
+        max_fee_per_gas: gas_price.into(),
+        max_priority_fee_per_gas: U256::zero(),
+    };
+
+    assert_eq!(
+        speed_up(&original, strategy(22)).unwrap().gas_price,
+        Some(22.into())
+    );
+    assert!(matches!(
+        speed_up(&original, strategy(21)),
+        Err(Error::InvalidData)
+    ));
+}
+
+#[test]
+fn test_cancel() {
+    let original = eip_1559_request();
+    let replacement = cancel(&original, BumpStrategy::Percent(25)).unwrap();
+
+    assert_eq!(replacement.to, Some(original.from));
+    assert_eq!(replacement.value, Some(U256::zero()));
+    assert_eq!(replacement.data, None);
+    assert_eq!(replacement.gas, Some(21_000.into()));
+    assert_eq!(replacement.nonce, Some(7.into()));
+    assert_eq!(replacement.max_fee_per_gas, Some(125.into()));
+}
+
+#[test]
+fn test_invalid_replacements() {
+    let original = eip_1559_request();
+
+    assert!(speed_up(&original, BumpStrategy::Percent(5)).is_err());
+    assert!(speed_up(
+        &TransactionRequest {
+            nonce: None,
+            ..original.clone()
+        },
+        BumpStrategy::Percent(10)
+    )
+    .is_err());
+    assert!(speed_up(
+        &original,
+        BumpStrategy::Fees {
+            max_fee_per_gas: 200.into(),
+            max_priority_fee_per_gas: 5.into(),
+        }
+    )
+    .is_err());
+
+    let replacement = TransactionRequest {
+        nonce: Some(8.into()),
+        ..speed_up(&original, BumpStrategy::Percent(10)).unwrap()
+    };
+    assert!(validate_replacement(&original, &replacement).is_err());
+}
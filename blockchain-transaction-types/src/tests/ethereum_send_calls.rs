@@ -0,0 +1,159 @@
+use web3::types::Address;
+
+use crate::models::error::Error;
+use crate::models::ethereum_send_calls::SendCallsRequest;
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::TransactionInfo;
+
+const SENDER: &str = "0xd46e8dd67c5d32be8058bb8eb970870f07244567";
+const TOKEN: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+const RECIPIENT: &str = "0x5bd5a8b2b9e4d5c2b7f9e3f5a3e7c3d2c1b0a9f8";
+
+/// An ether transfer and an ERC-20 transfer of 1000 tokens, as sent by
+/// `wallet_sendCalls`.
+fn send_calls_json() -> serde_json::Value {
+    serde_json::json!({
+        "version": "2.0.0",
+        "id": "batch-1",
+        "from": SENDER,
+        "chainId": "0x1",
+        "atomicRequired": true,
+        "calls": [
+            {
+                "to": RECIPIENT,
+                "value": "0x9184e72a",
+            },
+            {
+                "to": TOKEN,
+                "data": format!(
+                    "0xa9059cbb{:0>64}{:0>64}",
+                    RECIPIENT.trim_start_matches("0x"),
+                    "3e8"
+                ),
+            },
+        ],
+        "capabilities": {
+            "paymasterService": { "url": "https://paymaster.example.com" },
+        },
+    })
+}
+
+#[test]
+fn test_send_calls_from_json() {
+    let request = SendCallsRequest::from_json(send_calls_json()).unwrap();
+
+    assert_eq!(request.version, "2.0.0");
+    assert_eq!(request.id.as_deref(), Some("batch-1"));
+    assert_eq!(request.from, Some(SENDER.parse().unwrap()));
+    assert_eq!(request.chain_id, 1.into());
+    assert!(request.atomic_required);
+    assert_eq!(request.calls.len(), 2);
+    assert!(request.capabilities.is_some());
+
+    let transaction_requests = request.transaction_requests();
+    assert_eq!(transaction_requests[0].from, SENDER.parse().unwrap());
+    assert_eq!(transaction_requests[0].value, Some(0x9184e72a_u64.into()));
+    assert_eq!(transaction_requests[1].to, Some(TOKEN.parse().unwrap()));
+}
+
+#[test]
+fn test_send_calls_transaction_info() {
+    let request = SendCallsRequest::from_json(send_calls_json()).unwrap();
+
+    let calls = match request.transaction_info() {
+        TransactionInfo::Batch { calls } => calls,
+        info => panic!("unexpected transaction info {:?}", info),
+    };
+    assert_eq!(calls.len(), 2);
+    match &calls[0] {
+        TransactionInfo::TokenTransfer {
+            to,
+            amount,
+            token_id,
+            ..
+        } => {
+            assert_eq!(to, RECIPIENT);
+            assert_eq!(amount, "0x9184e72a");
+            assert_eq!(token_id, &None);
+        }
+        info => panic!("unexpected transaction info {:?}", info),
+    }
+    match &calls[1] {
+        TransactionInfo::TokenTransfer { from, to, .. } => {
+            assert_eq!(from, SENDER);
+            assert_eq!(to, RECIPIENT);
+        }
+        info => panic!("unexpected transaction info {:?}", info),
+    }
+}
+
+#[test]
+fn test_send_calls_without_sender() {
+    let mut json = send_calls_json();
+    json.as_object_mut().unwrap().remove("from");
+    json.as_object_mut().unwrap().remove("atomicRequired");
+
+    let request = SendCallsRequest::from_json(json).unwrap();
+
+    assert_eq!(request.from, None);
+    assert!(!request.atomic_required);
+    assert_eq!(request.transaction_requests()[0].from, Address::zero());
+}
+
+#[test]
+fn test_invalid_send_calls() {
+    let mut json = send_calls_json();
+    json["calls"] = serde_json::json!([]);
+    assert!(matches!(
+        SendCallsRequest::from_json(json),
+        Err(Error::InvalidField { field, .. }) if field == "calls"
+    ));
+
+    let mut json = send_calls_json();
+    json["calls"][0]["value"] = serde_json::json!("lots");
+    assert!(matches!(
+        SendCallsRequest::from_json(json),
+        Err(Error::Decode { path, .. }) if path == "calls[0].value"
+    ));
+}
+
+#[cfg(feature = "all-chains")]
+#[test]
+fn test_known_transaction_type_detects_send_calls() {
+    use crate::models::account::AccountId;
+    use crate::models::coin_type::CoinType;
+    use crate::models::known_transaction_type::KnownTransactionRequestType;
+
+    let known = crate::known_transaction_request_type_from_json(
+        send_calls_json(),
+        CoinType::Ethereum,
+        Some(1),
+    )
+    .expect("Could not identify transaction");
+
+    assert_eq!(
+        known.sender(),
+        Some(AccountId::Ethereum(SENDER.parse().unwrap()))
+    );
+    assert!(matches!(
+        known,
+        KnownTransactionRequestType::EthereumBatch(_)
+    ));
+    #[cfg(feature = "signing")]
+    assert!(matches!(
+        known.signable_transaction_request(),
+        Err(Error::InvalidData)
+    ));
+
+    assert!(matches!(
+        crate::known_transaction_request_type_from_json(
+            send_calls_json(),
+            CoinType::Ethereum,
+            Some(10)
+        ),
+        Err(Error::ChainIdMismatch {
+            payload: 1,
+            expected: 10
+        })
+    ));
+}
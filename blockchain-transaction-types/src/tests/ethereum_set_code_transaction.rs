@@ -0,0 +1,146 @@
+use web3::types::{Address, U256};
+
+use crate::models::account::AccountId;
+use crate::models::coin_type::CoinType;
+use crate::models::error::Error;
+use crate::models::ethereum_set_code_transaction::{
+    authorization_signing_hash, Authorization, SetCodeTransactionRequest,
+};
+use crate::models::keccak::keccak256;
+use crate::models::known_transaction_type::KnownTransactionRequestType;
+use crate::models::transaction::TransactionRequest;
+
+const DELEGATE: &str = "0x3535353535353535353535353535353535353535";
+
+fn authorization() -> Authorization {
+    Authorization {
+        chain_id: 1.into(),
+        address: DELEGATE.parse().unwrap(),
+        nonce: 0.into(),
+        y_parity: 1.into(),
+        r: U256::from(2),
+        s: U256::from(3),
+    }
+}
+
+fn set_code_request_json(authorization_list: Vec<Authorization>) -> serde_json::Value {
+    serde_json::json!({
+        "type": "0x4",
+        "from": Address::zero(),
+        "to": Address::random(),
+        "gas": "0x186a0",
+        "maxPriorityFeePerGas": "0x3b9aca00",
+        "maxFeePerGas": "0x4a817c800",
+        "value": "0x0",
+        "nonce": "0x1",
+        "authorizationList": authorization_list,
+    })
+}
+
+#[test]
+fn test_authorization_signing_hash() {
+    let message = hex::decode(format!("05d70194{}80", DELEGATE.trim_start_matches("0x"))).unwrap();
+
+    assert_eq!(
+        authorization_signing_hash(1.into(), DELEGATE.parse().unwrap(), 0.into()).0,
+        keccak256(&message)
+    );
+    assert_eq!(authorization().signing_hash().0, keccak256(&message));
+}
+
+#[test]
+fn test_authorization_json() {
+    let json = serde_json::to_value(authorization()).unwrap();
+
+    assert_eq!(json["chainId"], "0x1");
+    assert_eq!(json["yParity"], "0x1");
+    assert_eq!(
+        serde_json::from_value::<Authorization>(json).unwrap(),
+        authorization()
+    );
+}
+
+#[test]
+fn test_set_code_transaction_from_json() {
+    let known = crate::known_transaction_request_type_from_json(
+        set_code_request_json(vec![authorization()]),
+        CoinType::Ethereum,
+        Some(1),
+    )
+    .expect("Could not identify transaction");
+
+    assert_eq!(known.sender(), Some(AccountId::Ethereum(Address::zero())));
+    match known {
+        KnownTransactionRequestType::EthereumSetCode(request) => {
+            assert_eq!(request.authorization_list, vec![authorization()]);
+        }
+        _ => panic!("Transaction should be a set code transaction"),
+    }
+}
+
+#[test]
+fn test_set_code_transaction_without_authorizations() {
+    assert!(matches!(
+        SetCodeTransactionRequest::from_json(set_code_request_json(vec![])),
+        Err(Error::InvalidData)
+    ));
+}
+
+#[test]
+fn test_set_code_transaction_decode_error_path() {
+    let mut json = set_code_request_json(vec![authorization()]);
+    json["authorizationList"][0]["chainId"] = serde_json::json!("one");
+
+    match SetCodeTransactionRequest::from_json(json) {
+        Err(Error::Decode { path, .. }) => assert_eq!(path, "authorizationList[0].chainId"),
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+#[cfg(feature = "signing")]
+#[tokio::test]
+async fn test_set_code_transaction_encode_signed() {
+    use crate::models::transaction::{SignableTransactionRequest, Transaction};
+    use crate::tests::helpers::signer::TestSigner;
+
+    let chain_id = 1;
+    let authority = TestSigner::new();
+    let authority_address = authority.ethereum_address();
+    let mut authorization = authorization();
+    let (signature, y_parity) = authority
+        .sign_recoverable(authorization.signing_hash().0.to_vec(), None)
+        .await
+        .unwrap();
+    authorization.y_parity = y_parity.into();
+    authorization.r = U256::from_big_endian(&signature[..32]);
+    authorization.s = U256::from_big_endian(&signature[32..]);
+    assert_eq!(authorization.authority().unwrap(), authority_address);
+
+    let signer = TestSigner::new();
+    let sender_address = signer.ethereum_address();
+    let request =
+        SetCodeTransactionRequest::from_json(set_code_request_json(vec![authorization])).unwrap();
+    let transaction: Box<dyn SignableTransactionRequest> = Box::new(request.clone());
+    let (signature, recovery_id) = transaction
+        .sign_transaction(chain_id, move |message| {
+            signer.sign_recoverable(message, Some(chain_id))
+        })
+        .await
+        .expect("Could not sign transaction");
+    let raw = transaction
+        .encode_signed(chain_id, &signature, recovery_id)
+        .expect("Could not encode transaction");
+
+    assert_eq!(raw[0], 0x04);
+    let decoded = SetCodeTransactionRequest::from_raw(&raw).unwrap();
+    assert_eq!(decoded.request.from, sender_address);
+    assert_eq!(decoded.authorization_list, request.authorization_list);
+    assert_eq!(
+        decoded.authorization_list[0].authority().unwrap(),
+        authority_address
+    );
+    assert_eq!(
+        web3::types::Transaction::from_raw(&raw).unwrap().from,
+        Some(sender_address)
+    );
+}
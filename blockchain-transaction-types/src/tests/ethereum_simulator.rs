@@ -0,0 +1,183 @@
+use web3::types::{Address, TransactionRequest, H160};
+
+use crate::models::ethereum_simulator::{
+    BalanceChange, Simulator, Web3CallSimulator, Web3TraceSimulator,
+};
+use crate::models::transaction_info::TransactionInfo;
+use crate::tests::helpers::transport::TestTransport;
+
+const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+fn address(byte: u8) -> Address {
+    H160::repeat_byte(byte)
+}
+
+fn topic(address: Address) -> String {
+    format!("0x{:0>64}", hex::encode(address))
+}
+
+/// Answers `method` at the latest block with a fixed result.
+fn transport(method: &str, result: web3::Result<serde_json::Value>) -> TestTransport {
+    TestTransport::new().with_handler(method, move |params| {
+        assert_eq!(params[1], "latest");
+        result.clone()
+    })
+}
+
+#[tokio::test]
+async fn test_trace_simulator() {
+    let transport = transport(
+        "debug_traceCall",
+        Ok(serde_json::json!({
+            "type": "CALL",
+            "from": address(1),
+            "to": address(2),
+            "value": "0x64",
+            "gasUsed": "0x1d4c0",
+            "calls": [
+                {
+                    "type": "CALL",
+                    "from": address(2),
+                    "to": address(3),
+                    "logs": [{
+                        "address": address(3),
+                        "topics": [TRANSFER_TOPIC, topic(address(3)), topic(address(1))],
+                        "data": format!("0x{:0>64}", "3e8"),
+                    }],
+                },
+                {
+                    "type": "CALL",
+                    "from": address(2),
+                    "to": address(4),
+                    "value": "0xa",
+                    "error": "execution reverted",
+                    "logs": [{
+                        "address": address(4),
+                        "topics": [TRANSFER_TOPIC, topic(address(4)), topic(address(1))],
+                        "data": format!("0x{:0>64}", "1"),
+                    }],
+                },
+                {
+                    "type": "DELEGATECALL",
+                    "from": address(2),
+                    "to": address(5),
+                    "value": "0x64",
+                },
+            ],
+        })),
+    );
+    let request = TransactionRequest {
+        from: address(1),
+        to: Some(address(2)),
+        value: Some(100.into()),
+        ..Default::default()
+    };
+
+    let effects = Web3TraceSimulator::new(transport)
+        .simulate(&request)
+        .await
+        .unwrap();
+
+    assert!(effects.succeeded);
+    assert_eq!(effects.gas_used.as_deref(), Some("0x1d4c0"));
+    assert_eq!(effects.transfers.len(), 1);
+    assert_eq!(effects.events.len(), 1);
+    assert_eq!(
+        effects.balance_changes(&address(1)),
+        vec![
+            BalanceChange {
+                token: None,
+                token_id: None,
+                received: false,
+                amount: "0x64".to_owned(),
+            },
+            BalanceChange {
+                token: Some(format!("{:#x}", address(3))),
+                token_id: None,
+                received: true,
+                amount: "0x3e8".to_owned(),
+            },
+        ]
+    );
+    match effects.transaction_info() {
+        TransactionInfo::Batch { calls } => assert_eq!(calls.len(), 2),
+        info => panic!("unexpected transaction info {:?}", info),
+    }
+}
+
+#[tokio::test]
+async fn test_trace_simulator_reverted() {
+    let transport = transport(
+        "debug_traceCall",
+        Ok(serde_json::json!({
+            "type": "CALL",
+            "from": address(1),
+            "to": address(2),
+            "value": "0x64",
+            "gasUsed": "0x5208",
+            "error": "execution reverted",
+            "revertReason": "Ownable: caller is not the owner",
+        })),
+    );
+
+    let effects = Web3TraceSimulator::new(transport)
+        .simulate(&TransactionRequest::default())
+        .await
+        .unwrap();
+
+    assert!(!effects.succeeded);
+    assert_eq!(
+        effects.error.as_deref(),
+        Some("Ownable: caller is not the owner")
+    );
+    assert!(effects.transfers.is_empty());
+    assert!(effects.balance_changes(&address(1)).is_empty());
+}
+
+#[tokio::test]
+async fn test_call_simulator() {
+    let transport = transport("eth_call", Ok(serde_json::json!("0x")));
+    let data = format!("a9059cbb{:0>64}{:0>64}", hex::encode(address(5)), "3e8");
+    let request = TransactionRequest {
+        from: address(1),
+        to: Some(address(3)),
+        data: Some(hex::decode(data).unwrap().into()),
+        ..Default::default()
+    };
+
+    let effects = Web3CallSimulator::new(transport)
+        .simulate(&request)
+        .await
+        .unwrap();
+
+    assert!(effects.succeeded);
+    assert_eq!(
+        effects.balance_changes(&address(1)),
+        vec![BalanceChange {
+            token: Some(format!("{:#x}", address(3))),
+            token_id: None,
+            received: false,
+            amount: "0x3e8".to_owned(),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_call_simulator_reverted() {
+    let transport = transport(
+        "eth_call",
+        Err(web3::Error::Rpc(jsonrpc_core::Error {
+            code: jsonrpc_core::ErrorCode::ServerError(3),
+            message: "execution reverted".to_owned(),
+            data: None,
+        })),
+    );
+
+    let effects = Web3CallSimulator::new(transport)
+        .simulate(&TransactionRequest::default())
+        .await
+        .unwrap();
+
+    assert!(!effects.succeeded);
+    assert_eq!(effects.error.as_deref(), Some("execution reverted"));
+}
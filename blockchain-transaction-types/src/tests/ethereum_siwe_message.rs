@@ -0,0 +1,175 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::models::error::Error;
+use crate::models::ethereum_message::hash_personal_message;
+use crate::models::ethereum_siwe_message::SiweMessage;
+use crate::models::message::{Message, MessageInfo, SignableMessage};
+
+/// The example message of EIP-4361, with an expiration time.
+const MESSAGE: &str = "service.org wants you to sign in with your Ethereum account:
+0xe5A12547fe4E872D192E3eCecb76F2Ce1aeA4946
+
+I accept the ServiceOrg Terms of Service: https://service.org/tos
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Expiration Time: 2021-10-01T16:25:24.000+02:00
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+/// 2021-09-30T16:25:24Z
+const ISSUED_AT: u64 = 1_633_019_124;
+
+fn at(seconds: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(seconds)
+}
+
+#[test]
+fn test_parse_siwe_message() {
+    let message: SiweMessage = MESSAGE.parse().unwrap();
+
+    assert_eq!(message.scheme, None);
+    assert_eq!(message.domain, "service.org");
+    assert_eq!(
+        message.address,
+        "e5a12547fe4e872d192e3ececb76f2ce1aea4946".parse().unwrap()
+    );
+    assert_eq!(
+        message.statement.as_deref(),
+        Some("I accept the ServiceOrg Terms of Service: https://service.org/tos")
+    );
+    assert_eq!(message.uri, "https://service.org/login");
+    assert_eq!(message.chain_id, 1);
+    assert_eq!(message.nonce, "32891756");
+    assert_eq!(message.not_before, None);
+    assert_eq!(message.resources.len(), 2);
+    assert_eq!(message.as_str(), MESSAGE);
+}
+
+#[test]
+fn test_parse_siwe_message_without_statement() {
+    let text = "https://service.org wants you to sign in with your Ethereum account:
+0xe5A12547fe4E872D192E3eCecb76F2Ce1aeA4946
+
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 10
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Request ID: 1";
+    let message: SiweMessage = text.parse().unwrap();
+
+    assert_eq!(message.scheme.as_deref(), Some("https"));
+    assert_eq!(message.statement, None);
+    assert_eq!(message.chain_id, 10);
+    assert_eq!(message.request_id.as_deref(), Some("1"));
+    assert!(message.resources.is_empty());
+}
+
+#[test]
+fn test_invalid_siwe_message() {
+    assert!(matches!(
+        "hello world".parse::<SiweMessage>(),
+        Err(Error::InvalidData)
+    ));
+    assert!(MESSAGE
+        .replace("Version: 1", "Version: 2")
+        .parse::<SiweMessage>()
+        .is_err());
+    assert!(MESSAGE
+        .replace("Nonce: 32891756", "Nonce: 1234")
+        .parse::<SiweMessage>()
+        .is_err());
+    assert!(MESSAGE
+        .replace("2021-09-30T16:25:24Z", "yesterday")
+        .parse::<SiweMessage>()
+        .is_err());
+    assert!(matches!(
+        MESSAGE.replace("URI: ", "Url: ").parse::<SiweMessage>(),
+        Err(Error::InvalidField { field, .. }) if field == "URI"
+    ));
+}
+
+#[test]
+fn test_validate_siwe_message() {
+    let message: SiweMessage = MESSAGE.parse().unwrap();
+
+    assert!(message
+        .validate("service.org", "32891756", at(ISSUED_AT))
+        .is_ok());
+    assert!(message
+        .validate("evil.org", "32891756", at(ISSUED_AT))
+        .is_err());
+    assert!(message
+        .validate("service.org", "12345678", at(ISSUED_AT))
+        .is_err());
+
+    // The expiration time is 2021-10-01T14:25:24Z.
+    let expiration = ISSUED_AT + 22 * 3600;
+    assert!(message
+        .validate("service.org", "32891756", at(expiration - 1))
+        .is_ok());
+    assert!(message
+        .validate("service.org", "32891756", at(expiration))
+        .is_err());
+}
+
+#[test]
+fn test_validate_siwe_message_not_before() {
+    let message: SiweMessage = MESSAGE
+        .replace(
+            "Expiration Time: 2021-10-01T16:25:24.000+02:00",
+            "Not Before: 2021-09-30T16:30:24Z",
+        )
+        .parse()
+        .unwrap();
+
+    assert!(message
+        .validate("service.org", "32891756", at(ISSUED_AT))
+        .is_err());
+    assert!(message
+        .validate("service.org", "32891756", at(ISSUED_AT + 300))
+        .is_ok());
+}
+
+#[test]
+fn test_siwe_message_hash_and_info() {
+    let message = SiweMessage::from_json(serde_json::json!(MESSAGE)).unwrap();
+
+    assert_eq!(
+        message.message_hash(1).unwrap(),
+        hash_personal_message(MESSAGE.as_bytes()).as_bytes()
+    );
+    match message.message_info() {
+        MessageInfo::Json(json) => {
+            assert_eq!(json["domain"], "service.org");
+            assert_eq!(json["chainId"], 1);
+        }
+        info => panic!("unexpected message info {:?}", info),
+    }
+}
+
+#[cfg(feature = "all-chains")]
+#[test]
+fn test_known_message_type_detects_siwe() {
+    use crate::models::coin_type::CoinType;
+    use crate::models::known_message_type::KnownMessageType;
+
+    assert!(matches!(
+        KnownMessageType::from_json(serde_json::json!(MESSAGE), CoinType::Ethereum, None),
+        Ok(KnownMessageType::EthereumSignIn(_))
+    ));
+    assert!(matches!(
+        KnownMessageType::from_json(
+            serde_json::json!(format!("0x{}", hex::encode(MESSAGE))),
+            CoinType::Ethereum,
+            None
+        ),
+        Ok(KnownMessageType::EthereumSignIn(_))
+    ));
+}
@@ -0,0 +1,1135 @@
+use web3::types::{
+    Address, Transaction as Web3Transaction, TransactionParameters, TransactionRequest, H256, U256,
+};
+
+use crate::models::coin_type::CoinType;
+use crate::models::error::Error;
+use crate::models::keccak::keccak256;
+use crate::models::transaction::TransactionRequest as _;
+use crate::models::transaction_info::{FeeInfo, TokenTransferInfo, TransactionInfo};
+
+const SENDER: &str = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+const TOKEN: &str = "0x6b175474e89094c44da98b954eedeac495271d0f";
+const RECIPIENT: &str = "0x0d4a03b23ae95409a4ecfe9396a9d39ca4f0fed1";
+
+fn transaction_info(data: String) -> TransactionInfo {
+    let transaction_json = serde_json::json!({
+        "from": SENDER,
+        "to": TOKEN,
+        "data": data,
+    });
+
+    crate::known_transaction_request_type_from_json(transaction_json, CoinType::Ethereum, Some(1))
+        .expect("Could not identify transaction")
+        .transaction_request()
+        .transaction_info()
+}
+
+fn address_word(address: &str) -> String {
+    format!("{:0>64}", address.trim_start_matches("0x"))
+}
+
+#[test]
+fn test_erc20_transfer_token_info() {
+    let data = format!("0xa9059cbb{}{:064x}", address_word(RECIPIENT), 1_000_000u64);
+
+    assert_eq!(
+        transaction_info(data),
+        TransactionInfo::TokenTransfer {
+            from: SENDER.to_owned(),
+            to: RECIPIENT.to_owned(),
+            amount: "0xf4240".to_owned(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_erc20_burn_and_mint_token_info() {
+    let zero = format!("{:#x}", Address::zero());
+    let burn = format!("0xa9059cbb{}{:064x}", address_word(&zero), 42u64);
+    let mint = format!(
+        "0x23b872dd{}{}{:064x}",
+        address_word(&zero),
+        address_word(RECIPIENT),
+        42u64
+    );
+
+    assert_eq!(
+        transaction_info(burn),
+        TransactionInfo::TokenBurn {
+            from: SENDER.to_owned(),
+            amount: "0x2a".to_owned(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+    assert_eq!(
+        transaction_info(mint),
+        TransactionInfo::TokenMint {
+            to: RECIPIENT.to_owned(),
+            amount: "0x2a".to_owned(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_erc20_transfer_without_sender_token_info() {
+    let parameters = TransactionParameters::from_json(serde_json::json!({
+        "from": SENDER,
+        "to": TOKEN,
+        "gas": "0x5208",
+        "value": "0x0",
+        "data": format!("0xa9059cbb{}{:064x}", address_word(RECIPIENT), 1000u64),
+    }))
+    .unwrap();
+
+    assert_eq!(
+        parameters.transaction_info(),
+        TransactionInfo::TokenTransfer {
+            from: String::new(),
+            to: RECIPIENT.to_owned(),
+            amount: "0x3e8".to_owned(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_erc20_transfer_from_token_info() {
+    let owner = format!("{:#x}", Address::random());
+    let data = format!(
+        "0x23b872dd{}{}{:064x}",
+        address_word(&owner),
+        address_word(RECIPIENT),
+        42u64
+    );
+
+    assert_eq!(
+        transaction_info(data),
+        TransactionInfo::TokenTransfer {
+            from: owner,
+            to: RECIPIENT.to_owned(),
+            amount: "0x2a".to_owned(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_erc20_approve_info() {
+    let data = format!("0x095ea7b3{}{:064x}", address_word(RECIPIENT), 42u64);
+
+    assert_eq!(
+        transaction_info(data),
+        TransactionInfo::Approval {
+            owner: SENDER.to_owned(),
+            spender: RECIPIENT.to_owned(),
+            amount: Some("0x2a".to_owned()),
+            token: None,
+            is_unlimited: false,
+        }
+    );
+}
+
+#[test]
+fn test_erc20_unlimited_approve_info() {
+    let data = format!("0x095ea7b3{}{}", address_word(RECIPIENT), "f".repeat(64));
+
+    assert_eq!(
+        transaction_info(data),
+        TransactionInfo::Approval {
+            owner: SENDER.to_owned(),
+            spender: RECIPIENT.to_owned(),
+            amount: Some(format!("0x{}", "f".repeat(64))),
+            token: None,
+            is_unlimited: true,
+        }
+    );
+}
+
+#[test]
+fn test_erc20_permit_info() {
+    let owner = format!("{:#x}", Address::random());
+    let data = format!(
+        "0xd505accf{}{}{:064x}{:064x}{:064x}{:064x}{:064x}",
+        address_word(&owner),
+        address_word(RECIPIENT),
+        42u64,
+        1_700_000_000u64,
+        27,
+        1,
+        2
+    );
+
+    assert_eq!(
+        transaction_info(data),
+        TransactionInfo::Approval {
+            owner,
+            spender: RECIPIENT.to_owned(),
+            amount: Some("0x2a".to_owned()),
+            token: None,
+            is_unlimited: false,
+        }
+    );
+}
+
+#[test]
+fn test_set_approval_for_all_info() {
+    let approve = format!("0xa22cb465{}{:064x}", address_word(RECIPIENT), 1);
+    let revoke = format!("0xa22cb465{}{:064x}", address_word(RECIPIENT), 0);
+
+    assert_eq!(
+        transaction_info(approve),
+        TransactionInfo::Approval {
+            owner: SENDER.to_owned(),
+            spender: RECIPIENT.to_owned(),
+            amount: None,
+            token: None,
+            is_unlimited: true,
+        }
+    );
+    assert_eq!(
+        transaction_info(revoke),
+        TransactionInfo::Approval {
+            owner: SENDER.to_owned(),
+            spender: RECIPIENT.to_owned(),
+            amount: Some("0x0".to_owned()),
+            token: None,
+            is_unlimited: false,
+        }
+    );
+}
+
+#[test]
+fn test_permit2_approve_info() {
+    let data = format!(
+        "0x87517c45{}{}{:0>64}{:064x}",
+        address_word(TOKEN),
+        address_word(RECIPIENT),
+        "f".repeat(40),
+        1_700_000_000u64
+    );
+
+    assert_eq!(
+        transaction_info(data),
+        TransactionInfo::Approval {
+            owner: SENDER.to_owned(),
+            spender: RECIPIENT.to_owned(),
+            amount: Some(format!("0x{}", "f".repeat(40))),
+            token: Some(TOKEN.to_owned()),
+            is_unlimited: true,
+        }
+    );
+}
+
+#[test]
+fn test_erc721_safe_transfer_from_token_info() {
+    let owner = format!("{:#x}", Address::random());
+    let data = format!(
+        "0x42842e0e{}{}{:064x}",
+        address_word(&owner),
+        address_word(RECIPIENT),
+        1234u64
+    );
+
+    assert_eq!(
+        transaction_info(data),
+        TransactionInfo::TokenTransfer {
+            from: owner,
+            to: RECIPIENT.to_owned(),
+            amount: "0x1".to_owned(),
+            token_id: Some("0x4d2".to_owned()),
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_erc721_safe_transfer_from_with_data_token_info() {
+    let owner = format!("{:#x}", Address::random());
+    let data = format!(
+        "0xb88d4fde{}{}{:064x}{:064x}{:064x}",
+        address_word(&owner),
+        address_word(RECIPIENT),
+        1234u64,
+        0x80,
+        0
+    );
+
+    assert_eq!(
+        transaction_info(data),
+        TransactionInfo::TokenTransfer {
+            from: owner,
+            to: RECIPIENT.to_owned(),
+            amount: "0x1".to_owned(),
+            token_id: Some("0x4d2".to_owned()),
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_erc1155_safe_batch_transfer_from_token_info() {
+    let owner = format!("{:#x}", Address::random());
+    let words: Vec<u64> = vec![0xa0, 0x100, 0x160, 2, 7, 8, 2, 1, 5, 0];
+    let data = format!(
+        "0x2eb2c2d6{}{}{}",
+        address_word(&owner),
+        address_word(RECIPIENT),
+        words
+            .iter()
+            .map(|word| format!("{:064x}", word))
+            .collect::<String>()
+    );
+
+    let transfer = |token_id: &str, amount: &str| TokenTransferInfo {
+        from: owner.clone(),
+        to: RECIPIENT.to_owned(),
+        amount: amount.to_owned(),
+        token_id: Some(token_id.to_owned()),
+        token_info: None,
+        fiat_value: None,
+    };
+    assert_eq!(
+        transaction_info(data),
+        TransactionInfo::TokenBatchTransfer {
+            transfers: vec![transfer("0x7", "0x1"), transfer("0x8", "0x5")],
+        }
+    );
+}
+
+#[test]
+fn test_erc1155_safe_batch_mint_and_burn_token_info() {
+    let zero = format!("{:#x}", Address::zero());
+    let words: String = [0xa0u64, 0xe0, 0x120, 1, 7, 1, 3]
+        .iter()
+        .map(|word| format!("{:064x}", word))
+        .collect();
+    let batch = |from: &str, to: &str| {
+        format!(
+            "0x2eb2c2d6{}{}{}",
+            address_word(from),
+            address_word(to),
+            words
+        )
+    };
+    let transfers = |from: &str, to: &str| {
+        vec![TokenTransferInfo {
+            from: from.to_owned(),
+            to: to.to_owned(),
+            amount: "0x3".to_owned(),
+            token_id: Some("0x7".to_owned()),
+            token_info: None,
+            fiat_value: None,
+        }]
+    };
+
+    assert_eq!(
+        transaction_info(batch(&zero, RECIPIENT)),
+        TransactionInfo::TokenBatchMint {
+            transfers: transfers(&zero, RECIPIENT),
+        }
+    );
+    assert_eq!(
+        transaction_info(batch(SENDER, &zero)),
+        TransactionInfo::TokenBatchBurn {
+            transfers: transfers(SENDER, &zero),
+        }
+    );
+}
+
+#[test]
+fn test_erc1155_safe_batch_transfer_from_mismatched_arrays() {
+    let words: Vec<u64> = vec![0xa0, 0x100, 0x140, 2, 7, 8, 1, 1, 0];
+    let data = format!(
+        "0x2eb2c2d6{}{}{}",
+        address_word(SENDER),
+        address_word(RECIPIENT),
+        words
+            .iter()
+            .map(|word| format!("{:064x}", word))
+            .collect::<String>()
+    );
+
+    assert!(matches!(
+        transaction_info(data),
+        TransactionInfo::Unknown { .. }
+    ));
+}
+
+#[test]
+fn test_truncated_calldata_token_info() {
+    let data = format!("0xa9059cbb{}", address_word(RECIPIENT));
+
+    assert!(matches!(
+        transaction_info(data),
+        TransactionInfo::Unknown { .. }
+    ));
+    assert!(matches!(
+        transaction_info("0xf242432a".to_owned()),
+        TransactionInfo::Unknown { .. }
+    ));
+}
+
+#[test]
+fn test_contract_deployment_info() {
+    let transaction_json = serde_json::json!({
+        "from": SENDER,
+        "data": "0x6080604052",
+    });
+
+    let info = crate::known_transaction_request_type_from_json(
+        transaction_json,
+        CoinType::Ethereum,
+        Some(1),
+    )
+    .expect("Could not identify transaction")
+    .transaction_request()
+    .transaction_info();
+
+    assert_eq!(
+        info,
+        TransactionInfo::ContractDeployment {
+            bytecode_size: 5,
+            init_code_hash: format!(
+                "0x{}",
+                hex::encode(keccak256(&hex::decode("6080604052").unwrap()))
+            ),
+        }
+    );
+}
+
+/// Encodes a Safe `execTransaction` call with empty gas refund parameters and
+/// signatures.
+fn exec_transaction_data(to: &str, value: u64, data: &str, operation: u8) -> String {
+    let data = data.trim_start_matches("0x");
+    let data_words = data.len().saturating_add(63) / 64;
+    let signatures_offset = 0x140 + 0x20 + data_words * 0x20;
+    format!(
+        "0x6a761202{}{:064x}{:064x}{:064x}{:064x}{:064x}{:064x}{:064x}{:064x}{:064x}{:064x}{:0<width$}{:064x}",
+        address_word(to),
+        value,
+        0x140,
+        operation,
+        0,
+        0,
+        0,
+        0,
+        0,
+        signatures_offset,
+        data.len() / 2,
+        data,
+        0,
+        width = data_words * 64,
+    )
+}
+
+#[test]
+fn test_safe_exec_transaction_info() {
+    let transfer = format!("0xa9059cbb{}{:064x}", address_word(RECIPIENT), 42u64);
+    let data = exec_transaction_data(TOKEN, 0, &transfer, 0);
+
+    assert_eq!(
+        transaction_info(data),
+        TransactionInfo::SafeTransaction {
+            safe: TOKEN.to_owned(),
+            to: TOKEN.to_owned(),
+            value: "0x0".to_owned(),
+            operation: 0,
+            info: Box::new(TransactionInfo::TokenTransfer {
+                from: TOKEN.to_owned(),
+                to: RECIPIENT.to_owned(),
+                amount: "0x2a".to_owned(),
+                token_id: None,
+                token_info: None,
+                fiat_value: None,
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_safe_exec_transaction_ether_transfer_info() {
+    let data = exec_transaction_data(RECIPIENT, 1_000, "", 0);
+
+    assert!(matches!(
+        transaction_info(data),
+        TransactionInfo::SafeTransaction { info, .. } if matches!(
+            *info,
+            TransactionInfo::TokenTransfer { ref to, ref amount, .. }
+                if to == RECIPIENT && amount == "0x3e8"
+        )
+    ));
+}
+
+#[test]
+fn test_safe_exec_transaction_invalid_operation() {
+    let data = exec_transaction_data(RECIPIENT, 1_000, "", 2);
+
+    assert!(matches!(
+        transaction_info(data),
+        TransactionInfo::Unknown { .. }
+    ));
+}
+
+#[test]
+fn test_deeply_nested_safe_exec_transaction_info() {
+    let mut data = format!("0xa9059cbb{}{:064x}", address_word(RECIPIENT), 42u64);
+    for _ in 0..8 {
+        data = exec_transaction_data(TOKEN, 0, &data, 0);
+    }
+
+    let mut info = transaction_info(data);
+    let mut depth = 0;
+    while let TransactionInfo::SafeTransaction { info: nested, .. } = info {
+        info = *nested;
+        depth += 1;
+    }
+    assert_eq!(depth, 5);
+    assert!(matches!(info, TransactionInfo::Unknown { .. }));
+}
+
+fn word(value: usize) -> String {
+    format!("{:064x}", value)
+}
+
+/// Encodes the tail of a `bytes` value.
+fn bytes_tail(data: &str) -> String {
+    let data = data.trim_start_matches("0x");
+    let width = data.len().saturating_add(63) / 64 * 64;
+    format!("{}{:0<width$}", word(data.len() / 2), data, width = width)
+}
+
+/// Encodes the tail of an array of dynamic elements.
+fn dynamic_array(elements: &[String]) -> String {
+    let mut offset = elements.len() * 32;
+    let mut encoded = word(elements.len());
+    for element in elements {
+        encoded.push_str(&word(offset));
+        offset += element.len() / 2;
+    }
+    encoded + &elements.concat()
+}
+
+#[test]
+fn test_multicall_aggregate3_info() {
+    let other_token = format!("{:#x}", Address::random());
+    let transfer = format!("0xa9059cbb{}{:064x}", address_word(RECIPIENT), 42u64);
+    let calls = [
+        format!(
+            "{}{}{}{}",
+            address_word(&other_token),
+            word(1),
+            word(0x60),
+            bytes_tail(&transfer)
+        ),
+        format!(
+            "{}{}{}{}",
+            address_word(RECIPIENT),
+            word(0),
+            word(0x60),
+            bytes_tail("0x12345678")
+        ),
+    ];
+    let data = format!("0x82ad56cb{}{}", word(0x20), dynamic_array(&calls));
+
+    match transaction_info(data) {
+        TransactionInfo::Batch { calls } => {
+            assert_eq!(calls.len(), 2);
+            assert_eq!(
+                calls[0],
+                TransactionInfo::TokenTransfer {
+                    from: TOKEN.to_owned(),
+                    to: RECIPIENT.to_owned(),
+                    amount: "0x2a".to_owned(),
+                    token_id: None,
+                    token_info: None,
+                    fiat_value: None,
+                }
+            );
+            assert!(matches!(calls[1], TransactionInfo::Unknown { .. }));
+        }
+        info => panic!("Unexpected transaction info {:?}", info),
+    }
+}
+
+#[test]
+fn test_router_multicall_info() {
+    let transfer = format!("0xa9059cbb{}{:064x}", address_word(RECIPIENT), 42u64);
+    let approve = format!("0x095ea7b3{}{:064x}", address_word(RECIPIENT), 7u64);
+    let calls = [bytes_tail(&transfer), bytes_tail(&approve)];
+    let data = format!(
+        "0x5ae401dc{}{}{}",
+        word(1_700_000_000),
+        word(0x40),
+        dynamic_array(&calls)
+    );
+
+    assert_eq!(
+        transaction_info(data),
+        TransactionInfo::Batch {
+            calls: vec![
+                TransactionInfo::TokenTransfer {
+                    from: SENDER.to_owned(),
+                    to: RECIPIENT.to_owned(),
+                    amount: "0x2a".to_owned(),
+                    token_id: None,
+                    token_info: None,
+                    fiat_value: None,
+                },
+                TransactionInfo::Approval {
+                    owner: SENDER.to_owned(),
+                    spender: RECIPIENT.to_owned(),
+                    amount: Some("0x7".to_owned()),
+                    token: None,
+                    is_unlimited: false,
+                },
+            ]
+        }
+    );
+}
+
+#[test]
+fn test_truncated_multicall_info() {
+    let data = format!("0xac9650d8{}{}{}", word(0x20), word(2), word(0x40));
+
+    assert!(matches!(
+        transaction_info(data),
+        TransactionInfo::Unknown { .. }
+    ));
+}
+
+/// Encodes a router `multicall(bytes[])` call of `calls`.
+fn router_multicall(calls: &[String]) -> String {
+    let calls: Vec<_> = calls.iter().map(|call| bytes_tail(call)).collect();
+    format!("0xac9650d8{}{}", word(0x20), dynamic_array(&calls))
+}
+
+#[test]
+fn test_nested_aliased_multicall_info() {
+    let transfer = format!("0xa9059cbb{}{:064x}", address_word(RECIPIENT), 42u64);
+    let mut data = transfer;
+    for _ in 0..8 {
+        // Both elements point at the same `bytes` value.
+        let call = bytes_tail(&data);
+        data = format!(
+            "0xac9650d8{}{}{}{}{}",
+            word(0x20),
+            word(2),
+            word(0x40),
+            word(0x40),
+            call
+        );
+    }
+
+    assert!(matches!(
+        transaction_info(data),
+        TransactionInfo::Unknown { .. }
+    ));
+}
+
+#[test]
+fn test_deeply_nested_multicall_info() {
+    let transfer = format!("0xa9059cbb{}{:064x}", address_word(RECIPIENT), 42u64);
+    let mut data = transfer;
+    for _ in 0..8 {
+        data = router_multicall(&[data]);
+    }
+
+    let mut info = transaction_info(data);
+    let mut depth = 0;
+    while let TransactionInfo::Batch { mut calls } = info {
+        assert_eq!(calls.len(), 1);
+        info = calls.remove(0);
+        depth += 1;
+    }
+    assert_eq!(depth, 5);
+    assert!(matches!(info, TransactionInfo::Unknown { value: None, .. }));
+}
+
+#[test]
+fn test_multicall_call_budget_info() {
+    let transfer = format!("0xa9059cbb{}{:064x}", address_word(RECIPIENT), 42u64);
+    let data = router_multicall(&vec![transfer; 300]);
+
+    match transaction_info(data) {
+        TransactionInfo::Batch { calls } => {
+            assert_eq!(calls.len(), 300);
+            assert!(calls[..256]
+                .iter()
+                .all(|call| matches!(call, TransactionInfo::TokenTransfer { .. })));
+            assert!(calls[256..]
+                .iter()
+                .all(|call| matches!(call, TransactionInfo::Unknown { .. })));
+        }
+        info => panic!("Unexpected transaction info {:?}", info),
+    }
+}
+
+const WETH: &str = "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
+
+fn token_swap(amount_in: &str, min_amount_out: &str, recipient: &str) -> TransactionInfo {
+    TransactionInfo::TokenSwap {
+        token_in: WETH.to_owned(),
+        token_out: TOKEN.to_owned(),
+        amount_in: amount_in.to_owned(),
+        min_amount_out: min_amount_out.to_owned(),
+        recipient: recipient.to_owned(),
+    }
+}
+
+/// Encodes a Uniswap V3 path through a 0.3% pool.
+fn v3_path() -> String {
+    format!(
+        "{}000bb8{}",
+        WETH.trim_start_matches("0x"),
+        TOKEN.trim_start_matches("0x")
+    )
+}
+
+#[test]
+fn test_v2_swap_exact_tokens_for_tokens_info() {
+    let path = [address_word(WETH), address_word(TOKEN)];
+    let data = format!(
+        "0x38ed1739{}{}{}{}{}{}",
+        word(1_000),
+        word(900),
+        word(0xa0),
+        address_word(RECIPIENT),
+        word(1_700_000_000),
+        word(2) + &path.concat()
+    );
+
+    assert_eq!(
+        transaction_info(data),
+        token_swap("0x3e8", "0x384", RECIPIENT)
+    );
+}
+
+#[test]
+fn test_v2_swap_exact_eth_for_tokens_info() {
+    let path = [address_word(WETH), address_word(TOKEN)];
+    let transaction_json = serde_json::json!({
+        "from": SENDER,
+        "to": RECIPIENT,
+        "value": "0x3e8",
+        "data": format!(
+            "0x7ff36ab5{}{}{}{}{}",
+            word(900),
+            word(0x80),
+            address_word(SENDER),
+            word(1_700_000_000),
+            word(2) + &path.concat()
+        ),
+    });
+
+    let info = crate::known_transaction_request_type_from_json(
+        transaction_json,
+        CoinType::Ethereum,
+        Some(1),
+    )
+    .expect("Could not identify transaction")
+    .transaction_request()
+    .transaction_info();
+    assert_eq!(info, token_swap("0x3e8", "0x384", SENDER));
+}
+
+#[test]
+fn test_v3_exact_input_single_info() {
+    let data = format!(
+        "0x04e45aaf{}{}{}{}{}{}{}",
+        address_word(WETH),
+        address_word(TOKEN),
+        word(3_000),
+        word(1),
+        word(1_000),
+        word(900),
+        word(0)
+    );
+
+    assert_eq!(transaction_info(data), token_swap("0x3e8", "0x384", SENDER));
+}
+
+#[test]
+fn test_v3_exact_input_info() {
+    let data = format!(
+        "0xc04b8d59{}{}{}{}{}{}{}",
+        word(0x20),
+        word(0xa0),
+        address_word(RECIPIENT),
+        word(1_700_000_000),
+        word(1_000),
+        word(900),
+        bytes_tail(&v3_path())
+    );
+
+    assert_eq!(
+        transaction_info(data),
+        token_swap("0x3e8", "0x384", RECIPIENT)
+    );
+}
+
+#[test]
+fn test_v3_exact_input_invalid_path() {
+    let data = format!(
+        "0xb858183f{}{}{}{}{}{}",
+        word(0x20),
+        word(0x80),
+        address_word(RECIPIENT),
+        word(1_000),
+        word(900),
+        bytes_tail(WETH)
+    );
+
+    assert!(matches!(
+        transaction_info(data),
+        TransactionInfo::Unknown { .. }
+    ));
+}
+
+#[test]
+fn test_universal_router_swap_info() {
+    let wrap_eth = format!("{}{}", word(2), word(1_000));
+    let swap = format!(
+        "{}{}{}{}{}{}",
+        word(2),
+        word(1_000),
+        word(900),
+        word(0xa0),
+        word(0),
+        bytes_tail(&v3_path())
+    );
+    let inputs = [bytes_tail(&wrap_eth), bytes_tail(&swap)];
+    let data = format!(
+        "0x3593564c{}{}{}{}{}",
+        word(0x60),
+        word(0xa0),
+        word(1_700_000_000),
+        bytes_tail("0b00"),
+        dynamic_array(&inputs)
+    );
+
+    assert_eq!(transaction_info(data), token_swap("0x3e8", "0x384", TOKEN));
+}
+
+fn weth_transaction_info(value: &str, data: &str) -> TransactionInfo {
+    let transaction_json = serde_json::json!({
+        "from": SENDER,
+        "to": WETH,
+        "value": value,
+        "data": data,
+    });
+
+    crate::known_transaction_request_type_from_json(transaction_json, CoinType::Ethereum, Some(1))
+        .expect("Could not identify transaction")
+        .transaction_request()
+        .transaction_info()
+}
+
+#[test]
+fn test_weth_deposit_info() {
+    let wrap = TransactionInfo::Wrap {
+        token: WETH.to_owned(),
+        amount: "0x3e8".to_owned(),
+    };
+
+    assert_eq!(weth_transaction_info("0x3e8", "0xd0e30db0"), wrap);
+    assert_eq!(weth_transaction_info("0x3e8", "0x"), wrap);
+}
+
+#[test]
+fn test_weth_withdraw_info() {
+    let data = format!("0x2e1a7d4d{}", word(1_000));
+
+    assert_eq!(
+        weth_transaction_info("0x0", &data),
+        TransactionInfo::Unwrap {
+            token: WETH.to_owned(),
+            amount: "0x3e8".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_deposit_to_other_contract_info() {
+    let transaction_json = serde_json::json!({
+        "from": SENDER,
+        "to": TOKEN,
+        "value": "0x3e8",
+        "data": "0xd0e30db0",
+    });
+
+    let info = crate::known_transaction_request_type_from_json(
+        transaction_json,
+        CoinType::Ethereum,
+        Some(1),
+    )
+    .expect("Could not identify transaction")
+    .transaction_request()
+    .transaction_info();
+    assert!(matches!(info, TransactionInfo::Unknown { .. }));
+}
+
+#[test]
+fn test_wrapped_native_token() {
+    use crate::models::ethereum_transaction::wrapped_native_token;
+
+    assert_eq!(wrapped_native_token(1), WETH.parse().ok());
+    assert_eq!(wrapped_native_token(0), None);
+}
+
+/// The signer of the raw transactions, whose private key is `0x4646...46`.
+const RAW_SENDER: &str = "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f";
+const RAW_RECIPIENT: &str = "0x3535353535353535353535353535353535353535";
+/// The example of EIP-155.
+const EIP_155_SIGNED: &str = "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+const EIP_155_UNSIGNED: &str =
+    "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080";
+const EIP_2930_SIGNED: &str = "01f8a701098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080f838f7943535353535353535353535353535353535353535e1a0000000000000000000000000000000000000000000000000000000000000000180a0381f5272732aaecaa36e57234061be5c675ad65be3a7a8900421c6fe9060965fa01dc3c104e6b5f480132d393594bf20f1375e716a60a449b3bf855cd51f61b870";
+const EIP_1559_SIGNED: &str = "02f8ac0109843b9aca008504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080f838f7943535353535353535353535353535353535353535e1a0000000000000000000000000000000000000000000000000000000000000000180a0864e594585fd82fb8051e8d392017a52a21b839b6cdee779fa75f4668b2adf70a04cb347d800f1eabd8432ffcdf50a67ded4ff0f3e6776174c6fa04875d8ecb152";
+
+fn raw_transaction(raw: &str) -> Result<Web3Transaction, Error> {
+    use crate::models::transaction::Transaction;
+
+    Web3Transaction::from_raw(&hex::decode(raw).unwrap())
+}
+
+fn raw_transaction_request(raw: &str) -> Result<TransactionRequest, Error> {
+    use crate::models::transaction::TransactionRequest;
+
+    TransactionRequest::from_raw(&hex::decode(raw).unwrap())
+}
+
+#[test]
+fn test_legacy_raw_transaction() {
+    let transaction = raw_transaction(EIP_155_SIGNED).unwrap();
+
+    assert_eq!(
+        format!("{:#x}", transaction.hash),
+        "0x33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788"
+    );
+    assert_eq!(transaction.from, RAW_SENDER.parse().ok());
+    assert_eq!(transaction.to, RAW_RECIPIENT.parse().ok());
+    assert_eq!(transaction.nonce, U256::from(9));
+    assert_eq!(transaction.gas_price, Some(U256::from(20_000_000_000u64)));
+    assert_eq!(transaction.gas, U256::from(21_000));
+    assert_eq!(transaction.value, U256::exp10(18));
+    assert_eq!(transaction.v, Some(37.into()));
+    assert_eq!(transaction.transaction_type, None);
+}
+
+#[test]
+fn test_legacy_unsigned_raw_transaction_request() {
+    let request = raw_transaction_request(EIP_155_UNSIGNED).unwrap();
+
+    assert_eq!(request.from, Address::zero());
+    assert_eq!(request.to, RAW_RECIPIENT.parse().ok());
+    assert_eq!(request.nonce, Some(U256::from(9)));
+    assert_eq!(request.value, Some(U256::exp10(18)));
+    assert!(matches!(
+        raw_transaction(EIP_155_UNSIGNED),
+        Err(Error::InvalidData)
+    ));
+}
+
+#[test]
+fn test_raw_transaction_parameters() {
+    use crate::models::transaction::TransactionRequest;
+
+    let parameters =
+        TransactionParameters::from_raw(&hex::decode(EIP_1559_SIGNED).unwrap()).unwrap();
+    assert_eq!(parameters.chain_id, Some(1));
+    assert_eq!(parameters.nonce, Some(U256::from(9)));
+    assert_eq!(parameters.to, RAW_RECIPIENT.parse().ok());
+    assert_eq!(parameters.value, U256::exp10(18));
+
+    let parameters =
+        TransactionParameters::from_raw(&hex::decode(EIP_155_UNSIGNED).unwrap()).unwrap();
+    assert_eq!(parameters.chain_id, Some(1));
+    assert_eq!(parameters.transaction_type, None);
+}
+
+#[test]
+fn test_eip_2930_raw_transaction_request() {
+    let request = raw_transaction_request(EIP_2930_SIGNED).unwrap();
+
+    assert_eq!(request.from, RAW_SENDER.parse().unwrap());
+    assert_eq!(request.transaction_type, Some(1.into()));
+    assert_eq!(request.gas_price, Some(U256::from(20_000_000_000u64)));
+    let access_list = request.access_list.unwrap();
+    assert_eq!(access_list.len(), 1);
+    assert_eq!(access_list[0].address, RAW_RECIPIENT.parse().unwrap());
+    assert_eq!(access_list[0].storage_keys, vec![H256::from_low_u64_be(1)]);
+}
+
+#[test]
+fn test_eip_1559_raw_transaction() {
+    let transaction = raw_transaction(EIP_1559_SIGNED).unwrap();
+
+    assert_eq!(transaction.from, RAW_SENDER.parse().ok());
+    assert_eq!(transaction.transaction_type, Some(2.into()));
+    assert_eq!(transaction.gas_price, None);
+    assert_eq!(
+        transaction.max_priority_fee_per_gas,
+        Some(U256::from(1_000_000_000u64))
+    );
+    assert_eq!(
+        transaction.max_fee_per_gas,
+        Some(U256::from(20_000_000_000u64))
+    );
+    assert_eq!(transaction.v, Some(0.into()));
+    assert_eq!(
+        transaction.raw.unwrap().0,
+        hex::decode(EIP_1559_SIGNED).unwrap()
+    );
+}
+
+#[test]
+fn test_invalid_raw_transaction() {
+    assert!(raw_transaction_request("").is_err());
+    assert!(raw_transaction_request(&format!("{}00", EIP_155_SIGNED)).is_err());
+    assert!(raw_transaction_request(&format!("03{}", &EIP_1559_SIGNED[2..])).is_err());
+    assert!(raw_transaction_request(&EIP_1559_SIGNED[..EIP_1559_SIGNED.len() - 2]).is_err());
+}
+
+#[test]
+fn test_recover_sender() {
+    use crate::models::ethereum_transaction::recover_sender;
+
+    for raw in [EIP_155_SIGNED, EIP_2930_SIGNED, EIP_1559_SIGNED] {
+        assert_eq!(
+            recover_sender(&hex::decode(raw).unwrap()).unwrap(),
+            RAW_SENDER.parse().unwrap()
+        );
+    }
+    assert!(matches!(
+        recover_sender(&hex::decode(EIP_155_UNSIGNED).unwrap()),
+        Err(Error::InvalidData)
+    ));
+}
+
+#[test]
+fn test_eip_1559_fee_info() {
+    let request = TransactionRequest {
+        from: Address::zero(),
+        gas: Some(21_000.into()),
+        max_fee_per_gas: Some(100.into()),
+        max_priority_fee_per_gas: Some(2.into()),
+        value: Some(1_000.into()),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        request.fee_info(),
+        Some(FeeInfo {
+            gas_limit: Some("0x5208".to_owned()),
+            gas_price: None,
+            max_fee_per_gas: Some("0x64".to_owned()),
+            max_priority_fee_per_gas: Some("0x2".to_owned()),
+            max_fee_per_blob_gas: None,
+            max_total_fee: Some(format!("{:#x}", 21_000 * 100 + 1_000)),
+        })
+    );
+}
+
+#[test]
+fn test_legacy_fee_info() {
+    let request = TransactionRequest {
+        from: Address::zero(),
+        gas: Some(21_000.into()),
+        gas_price: Some(7.into()),
+        ..Default::default()
+    };
+    let fee_info = request.fee_info().unwrap();
+
+    assert_eq!(fee_info.gas_price.as_deref(), Some("0x7"));
+    assert_eq!(fee_info.max_total_fee, Some(format!("{:#x}", 21_000 * 7)));
+
+    let request = TransactionRequest {
+        gas: None,
+        ..request
+    };
+    assert_eq!(request.fee_info().unwrap().max_total_fee, None);
+}
+
+#[cfg(feature = "all-chains")]
+#[test]
+fn test_chain_id_mismatch() {
+    use crate::models::known_transaction_type::KnownTransactionRequestType;
+
+    let json = |chain_id: serde_json::Value| {
+        serde_json::json!({
+            "from": SENDER,
+            "to": RECIPIENT,
+            "value": "0x1",
+            "chainId": chain_id,
+        })
+    };
+
+    for chain_id in [serde_json::json!("0x1"), serde_json::json!(1)] {
+        assert!(crate::known_transaction_request_type_from_json(
+            json(chain_id),
+            CoinType::Ethereum,
+            Some(1)
+        )
+        .is_ok());
+    }
+    assert!(matches!(
+        crate::known_transaction_request_type_from_json(
+            json(serde_json::json!("0x5")),
+            CoinType::Ethereum,
+            Some(1)
+        ),
+        Err(Error::ChainIdMismatch {
+            payload: 5,
+            expected: 1
+        })
+    ));
+    assert!(crate::known_transaction_request_type_from_json(
+        json(serde_json::json!("0x5")),
+        CoinType::Ethereum,
+        None
+    )
+    .is_ok());
+    assert!(KnownTransactionRequestType::from_json_ignoring_chain_id(
+        json(serde_json::json!("0x5")),
+        CoinType::Ethereum
+    )
+    .is_ok());
+}
+
+#[cfg(feature = "all-chains")]
+#[test]
+fn test_malformed_chain_id() {
+    for chain_id in [
+        serde_json::json!("0xzz"),
+        serde_json::json!("0x10000000000000000"),
+        serde_json::json!(-1),
+        serde_json::json!(true),
+    ] {
+        let json = serde_json::json!({
+            "from": SENDER,
+            "to": RECIPIENT,
+            "value": "0x1",
+            "chainId": chain_id,
+        });
+
+        assert!(matches!(
+            crate::known_transaction_request_type_from_json(json, CoinType::Ethereum, Some(1)),
+            Err(Error::InvalidField { field, .. }) if field == "chainId"
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::models::error::Error;
+use crate::models::ethereum_typed_data::TypedData;
+use crate::models::message::{Message, MessageInfo, SignableMessage};
+
+/// The `Mail` example of EIP-712.
+fn mail_json() -> serde_json::Value {
+    serde_json::json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {
+                "name": "Cow",
+                "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+            },
+            "to": {
+                "name": "Bob",
+                "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+            },
+            "contents": "Hello, Bob!"
+        }
+    })
+}
+
+#[test]
+fn test_typed_data_encode_type() {
+    let typed_data = TypedData::from_json(mail_json()).unwrap();
+
+    assert_eq!(
+        typed_data.encode_type("Mail").unwrap(),
+        "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+    );
+}
+
+#[test]
+fn test_typed_data_hash() {
+    let typed_data = TypedData::from_json(mail_json()).unwrap();
+
+    assert_eq!(
+        hex::encode(typed_data.domain_separator().unwrap()),
+        "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+    );
+    assert_eq!(
+        hex::encode(typed_data.message_hash(1).unwrap()),
+        "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+    );
+}
+
+#[test]
+fn test_typed_data_from_string() {
+    let typed_data = TypedData::from_json(serde_json::json!(mail_json().to_string())).unwrap();
+
+    assert_eq!(typed_data, TypedData::from_json(mail_json()).unwrap());
+    assert_eq!(typed_data.message_info(), MessageInfo::Json(mail_json()));
+}
+
+#[test]
+fn test_typed_data_arrays_and_integers() {
+    let mut json = mail_json();
+    json["types"]["Mail"] = serde_json::json!([
+        { "name": "to", "type": "Person[]" },
+        { "name": "amount", "type": "int256" },
+        { "name": "salt", "type": "bytes32" }
+    ]);
+    json["message"] = serde_json::json!({
+        "to": [json["message"]["to"].clone()],
+        "amount": "-1",
+        "salt": "0x01"
+    });
+    let typed_data = TypedData::from_json(json.clone()).unwrap();
+
+    json["message"]["amount"] = serde_json::json!(-1);
+    let number = TypedData::from_json(json).unwrap();
+
+    assert_eq!(
+        typed_data.encode_type("Mail").unwrap(),
+        "Mail(Person[] to,int256 amount,bytes32 salt)Person(string name,address wallet)"
+    );
+    assert_eq!(
+        typed_data.signing_hash().unwrap(),
+        number.signing_hash().unwrap()
+    );
+}
+
+#[test]
+fn test_typed_data_missing_type() {
+    let mut json = mail_json();
+    json["types"].as_object_mut().unwrap().remove("Person");
+    let typed_data = TypedData::from_json(json).unwrap();
+
+    match typed_data.signing_hash() {
+        Err(Error::InvalidField { field, expected }) => {
+            assert_eq!(field, "from");
+            assert_eq!(expected, "Person");
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+#[test]
+fn test_typed_data_invalid_json() {
+    assert!(matches!(
+        TypedData::from_raw(b"typed data"),
+        Err(Error::Json(_))
+    ));
+    assert!(matches!(
+        TypedData::from_json(serde_json::json!({ "types": [] })),
+        Err(Error::Decode { path, .. }) if path == "types"
+    ));
+}
+
+#[cfg(feature = "all-chains")]
+#[test]
+fn test_known_message_type_detects_typed_data() {
+    use crate::models::coin_type::CoinType;
+    use crate::models::known_message_type::KnownMessageType;
+
+    assert!(matches!(
+        KnownMessageType::from_json(mail_json(), CoinType::Ethereum, None),
+        Ok(KnownMessageType::EthereumTypedData(_))
+    ));
+    assert!(matches!(
+        KnownMessageType::from_json(
+            serde_json::json!(mail_json().to_string()),
+            CoinType::Ethereum,
+            None
+        ),
+        Ok(KnownMessageType::EthereumTypedData(_))
+    ));
+    assert!(matches!(
+        KnownMessageType::from_json(serde_json::json!("hello"), CoinType::Ethereum, None),
+        Ok(KnownMessageType::Ethereum(_))
+    ));
+}
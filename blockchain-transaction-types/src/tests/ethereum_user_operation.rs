@@ -0,0 +1,278 @@
+use web3::ethabi::{self, Token};
+use web3::types::U256;
+
+use crate::models::error::Error;
+use crate::models::ethereum_user_operation::{
+    UserOperation, UserOperationRequest, ENTRY_POINT_V06, ENTRY_POINT_V07,
+};
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::TransactionInfo;
+
+fn user_operation_v06_json() -> serde_json::Value {
+    serde_json::json!({
+        "sender": "0x1111111111111111111111111111111111111111",
+        "nonce": "0x1",
+        "initCode": "0x",
+        "callData": "0xdeadbeef",
+        "callGasLimit": "0x10000",
+        "verificationGasLimit": "0x20000",
+        "preVerificationGas": "0xc350",
+        "maxFeePerGas": "0x3b9aca00",
+        "maxPriorityFeePerGas": "0x5f5e100",
+        "paymasterAndData": "0x",
+        "signature": "0x",
+    })
+}
+
+fn user_operation_v07_json() -> serde_json::Value {
+    serde_json::json!({
+        "sender": "0x1111111111111111111111111111111111111111",
+        "nonce": "0x1",
+        "factory": "0x2222222222222222222222222222222222222222",
+        "factoryData": "0x1234",
+        "callData": "0xdeadbeef",
+        "callGasLimit": "0x10000",
+        "verificationGasLimit": "0x20000",
+        "preVerificationGas": "0xc350",
+        "maxFeePerGas": "0x3b9aca00",
+        "maxPriorityFeePerGas": "0x5f5e100",
+        "paymaster": "0x3333333333333333333333333333333333333333",
+        "paymasterVerificationGasLimit": "0x8000",
+        "paymasterPostOpGasLimit": "0x4000",
+        "paymasterData": "0xabcd",
+        "signature": "0x",
+    })
+}
+
+#[test]
+fn test_user_operation_v06_hash() {
+    let request = UserOperationRequest::from_json(serde_json::json!([
+        user_operation_v06_json(),
+        ENTRY_POINT_V06
+    ]))
+    .unwrap();
+
+    assert!(matches!(request.user_operation, UserOperation::V06(_)));
+    assert_eq!(
+        hex::encode(request.user_op_hash(1)),
+        "0ad47f169ed5aea8764bd648a7b7c48e6aa2e1f811d1d82494b1e7963e6c9a7a"
+    );
+}
+
+#[test]
+fn test_user_operation_v07_hash() {
+    let request = UserOperationRequest::from_json(serde_json::json!([
+        user_operation_v07_json(),
+        ENTRY_POINT_V07
+    ]))
+    .unwrap();
+
+    let operation = match &request.user_operation {
+        UserOperation::V07(operation) => operation,
+        operation => panic!("unexpected user operation {:?}", operation),
+    };
+    assert_eq!(
+        hex::encode(operation.init_code()),
+        format!("{}1234", "22".repeat(20))
+    );
+    assert_eq!(
+        hex::encode(operation.paymaster_and_data()),
+        format!("{}{:0>32}{:0>32}abcd", "33".repeat(20), "8000", "4000")
+    );
+    assert_eq!(
+        hex::encode(request.user_op_hash(11_155_111)),
+        "59961ef80d9680da5340b67642b45a0e25925c713e5058cd2997cba6d2ad4ea9"
+    );
+    assert_ne!(request.user_op_hash(1), request.user_op_hash(11_155_111));
+}
+
+#[test]
+fn test_user_operation_json_round_trip() {
+    for json in [user_operation_v06_json(), user_operation_v07_json()] {
+        let operation = UserOperation::from_json(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&operation).unwrap(), json);
+    }
+}
+
+#[test]
+fn test_invalid_user_operation() {
+    let mut json = user_operation_v07_json();
+    json["callGasLimit"] = serde_json::json!("lots");
+    assert!(matches!(
+        UserOperation::from_json(json),
+        Err(Error::Decode { path, .. }) if path == "callGasLimit"
+    ));
+    assert!(UserOperationRequest::from_json(user_operation_v06_json()).is_err());
+    assert!(UserOperationRequest::from_raw(b"user operation").is_err());
+}
+
+const TOKEN: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+const RECIPIENT: &str = "0x5bd5a8b2b9e4d5c2b7f9e3f5a3e7c3d2c1b0a9f8";
+
+/// An ERC-20 `transfer` of 1000 tokens to the recipient.
+fn transfer_data() -> Vec<u8> {
+    let mut data = vec![0xa9, 0x05, 0x9c, 0xbb];
+    data.extend(ethabi::encode(&[
+        Token::Address(RECIPIENT.parse().unwrap()),
+        Token::Uint(1000.into()),
+    ]));
+    data
+}
+
+/// A v0.7 user operation request executing `call_data` with `selector`.
+fn execute_request(selector: [u8; 4], parameters: &[Token]) -> UserOperationRequest {
+    let mut call_data = selector.to_vec();
+    call_data.extend(ethabi::encode(parameters));
+    let mut json = user_operation_v07_json();
+    json["callData"] = serde_json::json!(format!("0x{}", hex::encode(call_data)));
+    UserOperationRequest::from_json(serde_json::json!([json, ENTRY_POINT_V07])).unwrap()
+}
+
+fn token_transfer() -> TransactionInfo {
+    TransactionInfo::TokenTransfer {
+        from: "0x1111111111111111111111111111111111111111".to_owned(),
+        to: RECIPIENT.to_owned(),
+        amount: "0x3e8".to_owned(),
+        token_id: None,
+        token_info: None,
+        fiat_value: None,
+    }
+}
+
+fn ether_transfer() -> TransactionInfo {
+    TransactionInfo::TokenTransfer {
+        from: "0x1111111111111111111111111111111111111111".to_owned(),
+        to: RECIPIENT.to_owned(),
+        amount: "0x64".to_owned(),
+        token_id: None,
+        token_info: None,
+        fiat_value: None,
+    }
+}
+
+#[test]
+fn test_user_operation_execute_info() {
+    let request = execute_request(
+        [0xb6, 0x1d, 0x27, 0xf6],
+        &[
+            Token::Address(TOKEN.parse().unwrap()),
+            Token::Uint(U256::zero()),
+            Token::Bytes(transfer_data()),
+        ],
+    );
+
+    let calls = request.user_operation.calls().unwrap();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].from, request.user_operation.sender());
+    assert_eq!(calls[0].to, Some(TOKEN.parse().unwrap()));
+    assert_eq!(request.transaction_info(), token_transfer());
+}
+
+#[test]
+fn test_user_operation_execute_batch_info() {
+    let expected = TransactionInfo::Batch {
+        calls: vec![ether_transfer(), token_transfer()],
+    };
+    let to = Token::Array(vec![
+        Token::Address(RECIPIENT.parse().unwrap()),
+        Token::Address(TOKEN.parse().unwrap()),
+    ]);
+    let data = Token::Array(vec![Token::Bytes(vec![]), Token::Bytes(transfer_data())]);
+
+    // SimpleAccount v0.7
+    let request = execute_request(
+        [0x47, 0xe1, 0xda, 0x2a],
+        &[
+            to.clone(),
+            Token::Array(vec![Token::Uint(100.into()), Token::Uint(U256::zero())]),
+            data.clone(),
+        ],
+    );
+    assert_eq!(request.transaction_info(), expected);
+
+    // Coinbase Smart Wallet
+    let request = execute_request(
+        [0x34, 0xfc, 0xd5, 0xbe],
+        &[Token::Array(vec![
+            Token::Tuple(vec![
+                Token::Address(RECIPIENT.parse().unwrap()),
+                Token::Uint(100.into()),
+                Token::Bytes(vec![]),
+            ]),
+            Token::Tuple(vec![
+                Token::Address(TOKEN.parse().unwrap()),
+                Token::Uint(U256::zero()),
+                Token::Bytes(transfer_data()),
+            ]),
+        ])],
+    );
+    assert_eq!(request.transaction_info(), expected);
+
+    // SimpleAccount v0.6 sends no ether.
+    let request = execute_request([0x18, 0xdf, 0xb3, 0xc7], &[to, data]);
+    let calls = request.user_operation.calls().unwrap();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].value, Some(U256::zero()));
+}
+
+#[test]
+fn test_user_operation_delegate_call_info() {
+    let parameters = [
+        Token::Address(TOKEN.parse().unwrap()),
+        Token::Uint(U256::zero()),
+        Token::Bytes(transfer_data()),
+    ];
+    let call = [parameters.to_vec(), vec![Token::Uint(0.into())]].concat();
+    let delegate_call = [parameters.to_vec(), vec![Token::Uint(1.into())]].concat();
+
+    // Safe 4337 module
+    let request = execute_request([0x7b, 0xb3, 0x74, 0x28], &call);
+    assert_eq!(request.transaction_info(), token_transfer());
+
+    // Kernel
+    let request = execute_request([0x51, 0x94, 0x54, 0x47], &delegate_call);
+    assert_eq!(request.user_operation.calls(), None);
+    assert_ne!(request.transaction_info(), token_transfer());
+}
+
+#[cfg(feature = "signing")]
+#[tokio::test]
+async fn test_user_operation_signing() {
+    use web3::types::Address;
+
+    use crate::models::ethereum_message::hash_personal_message;
+    use crate::models::transaction::SignableTransactionRequest;
+    use crate::tests::helpers::signer::TestSigner;
+
+    let signer = TestSigner::new();
+    let owner = signer.ethereum_address();
+    let request = UserOperationRequest {
+        user_operation: UserOperation::from_json(user_operation_v07_json()).unwrap(),
+        entry_point: ENTRY_POINT_V07.parse::<Address>().unwrap(),
+    };
+
+    let message = request.message_hash(1).unwrap();
+    assert_eq!(
+        message,
+        hash_personal_message(request.user_op_hash(1).as_bytes()).as_bytes()
+    );
+
+    let (signature, recovery_id) = signer
+        .sign_recoverable(message.clone(), None)
+        .await
+        .unwrap();
+    let signed = request.encode_signed(1, &signature, recovery_id).unwrap();
+    let signed = UserOperation::from_json(serde_json::from_slice(&signed).unwrap()).unwrap();
+    let signature = match &signed {
+        UserOperation::V07(operation) => operation.signature.0.clone(),
+        operation => panic!("unexpected user operation {:?}", operation),
+    };
+
+    assert_eq!(signature.len(), 65);
+    assert_eq!(
+        web3::signing::recover(&message, &signature[..64], signature[64] as i32 - 27).unwrap(),
+        owner
+    );
+    // The signature isn't part of the hash.
+    assert_eq!(signed.hash(request.entry_point, 1), request.user_op_hash(1));
+}
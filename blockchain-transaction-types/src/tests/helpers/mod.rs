@@ -1,2 +1,9 @@
 #[cfg(feature = "signing")]
 pub mod signer;
+#[cfg(any(
+    feature = "access-list-provider",
+    feature = "fee-estimator",
+    feature = "simulator",
+    feature = "token-info-provider"
+))]
+pub mod transport;
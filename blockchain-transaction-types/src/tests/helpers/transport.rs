@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Ready;
+use std::sync::Arc;
+
+use serde_json::Value;
+use web3::{helpers, RequestId, Transport};
+
+type Handler = Arc<dyn Fn(Vec<Value>) -> web3::Result<Value> + Send + Sync>;
+
+/// Answers JSON-RPC methods with the handlers registered for them, and
+/// panics on any other method.
+#[derive(Clone, Default)]
+pub struct TestTransport {
+    handlers: HashMap<String, Handler>,
+}
+
+impl fmt::Debug for TestTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.handlers.keys()).finish()
+    }
+}
+
+impl TestTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers `method` with the result of `handler` for the request
+    /// parameters.
+    pub fn with_handler<F>(mut self, method: &str, handler: F) -> Self
+    where
+        F: Fn(Vec<Value>) -> web3::Result<Value> + Send + Sync + 'static,
+    {
+        self.handlers.insert(method.to_owned(), Arc::new(handler));
+        self
+    }
+}
+
+impl Transport for TestTransport {
+    type Out = Ready<web3::Result<Value>>;
+
+    fn prepare(&self, method: &str, params: Vec<Value>) -> (RequestId, jsonrpc_core::Call) {
+        (1, helpers::build_request(1, method, params))
+    }
+
+    fn send(&self, _id: RequestId, request: jsonrpc_core::Call) -> Self::Out {
+        let call = match request {
+            jsonrpc_core::Call::MethodCall(call) => call,
+            request => panic!("unexpected request {:?}", request),
+        };
+        let handler = self
+            .handlers
+            .get(&call.method)
+            .unwrap_or_else(|| panic!("unexpected method {}", call.method));
+        std::future::ready(handler(call.params.parse().unwrap()))
+    }
+}
@@ -1,4 +1,59 @@
+#[cfg(feature = "ethereum")]
+pub mod abi_registry;
+#[cfg(feature = "bitcoin")]
+pub mod bitcoin_transaction;
+pub mod chain_info;
+pub mod coin_type;
+#[cfg(feature = "cosmos")]
+pub mod cosmos_transaction;
+#[cfg(feature = "ens-resolver")]
+pub mod ens_resolver;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_access_list;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_blob_transaction;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_block;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_calldata;
+#[cfg(feature = "fee-estimator")]
+pub mod ethereum_fee_estimator;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_message;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_receipt;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_replacement;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_send_calls;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_set_code_transaction;
+#[cfg(feature = "simulator")]
+pub mod ethereum_simulator;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_siwe_message;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_transaction;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_typed_data;
+#[cfg(feature = "ethereum")]
+pub mod ethereum_user_operation;
 pub mod helpers;
+#[cfg(feature = "price-provider")]
+pub mod price_provider;
 
+#[cfg(feature = "ethereum")]
+pub mod risk_assessment;
+#[cfg(feature = "selector-resolver")]
+pub mod selector_resolver;
 #[cfg(feature = "signing")]
 pub mod signing;
+#[cfg(feature = "solana")]
+pub mod solana_message;
+#[cfg(feature = "solana")]
+pub mod solana_transaction;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+#[cfg(feature = "token-info-provider")]
+pub mod token_info_provider;
+pub mod transaction_info;
@@ -0,0 +1,127 @@
+use web3::types::{Address, U256};
+
+use crate::models::price_provider::{attach_fiat_values, PriceProvider, UnitPriceProvider};
+use crate::models::transaction_info::{FiatValue, TokenInfo, TransactionInfo};
+
+fn usdc() -> TokenInfo {
+    TokenInfo {
+        name: "USD Coin".to_owned(),
+        symbol: Some("USDC".to_owned()),
+        decimals: 6,
+        ..Default::default()
+    }
+}
+
+fn provider() -> UnitPriceProvider {
+    let mut provider = UnitPriceProvider::new("USD");
+    provider.set_native_price(1, 2000.0);
+    provider.set_token_price(1, Address::repeat_byte(0xaa), 1.0);
+    provider
+}
+
+fn transfer(
+    amount: &str,
+    token_id: Option<&str>,
+    token_info: Option<TokenInfo>,
+) -> TransactionInfo {
+    TransactionInfo::TokenTransfer {
+        from: format!("{:#x}", Address::repeat_byte(1)),
+        to: format!("{:#x}", Address::repeat_byte(2)),
+        amount: amount.to_owned(),
+        token_id: token_id.map(str::to_owned),
+        token_info,
+        fiat_value: None,
+    }
+}
+
+fn fiat_value(info: &TransactionInfo) -> Option<f64> {
+    match info {
+        TransactionInfo::TokenTransfer { fiat_value, .. } => {
+            fiat_value.as_ref().map(|value| value.amount)
+        }
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn test_unit_price_provider() {
+    let provider = provider();
+
+    assert_eq!(
+        provider
+            .fiat_value(1, None, None, U256::exp10(18) / 2)
+            .await
+            .unwrap(),
+        Some(FiatValue {
+            currency: "USD".to_owned(),
+            amount: 1000.0,
+        })
+    );
+    assert_eq!(
+        provider
+            .fiat_value(
+                1,
+                Some(Address::repeat_byte(0xaa)),
+                Some(&usdc()),
+                2_500_000.into()
+            )
+            .await
+            .unwrap()
+            .map(|value| value.amount),
+        Some(2.5)
+    );
+    assert_eq!(
+        provider
+            .fiat_value(1, Some(Address::repeat_byte(0xbb)), Some(&usdc()), 1.into())
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        provider
+            .fiat_value(1, Some(Address::repeat_byte(0xaa)), None, 1.into())
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        provider.fiat_value(5, None, None, 1.into()).await.unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_attach_fiat_values() {
+    let provider = provider();
+    let token = Some(Address::repeat_byte(0xaa));
+
+    let mut info = transfer("0xf4240", None, Some(usdc()));
+    attach_fiat_values(&mut info, 1, token, &provider).await;
+    assert_eq!(fiat_value(&info), Some(1.0));
+
+    let mut info = transfer("0xde0b6b3a7640000", None, None);
+    attach_fiat_values(&mut info, 1, None, &provider).await;
+    assert_eq!(fiat_value(&info), Some(2000.0));
+
+    let mut info = transfer("0x1", Some("0x7"), Some(usdc()));
+    attach_fiat_values(&mut info, 1, token, &provider).await;
+    assert_eq!(fiat_value(&info), None);
+}
+
+#[tokio::test]
+async fn test_attach_fiat_values_of_safe_transactions() {
+    let provider = provider();
+    let mut info = TransactionInfo::SafeTransaction {
+        safe: format!("{:#x}", Address::repeat_byte(5)),
+        to: format!("{:#x}", Address::repeat_byte(0xaa)),
+        value: "0x0".to_owned(),
+        operation: 0,
+        info: Box::new(transfer("0x1e8480", None, Some(usdc()))),
+    };
+
+    attach_fiat_values(&mut info, 1, Some(Address::repeat_byte(5)), &provider).await;
+    match info {
+        TransactionInfo::SafeTransaction { info, .. } => assert_eq!(fiat_value(&info), Some(2.0)),
+        info => panic!("unexpected info {:?}", info),
+    }
+}
@@ -0,0 +1,170 @@
+use web3::types::Address;
+
+use crate::models::ethereum_typed_data::TypedData;
+use crate::models::message::Message;
+use crate::models::risk_assessment::{assess_transaction, assess_typed_data, RiskFlag};
+use crate::models::transaction_info::TransactionInfo;
+
+fn address(byte: u8) -> String {
+    format!("{:#x}", Address::repeat_byte(byte))
+}
+
+fn approval(spender: String, is_unlimited: bool) -> TransactionInfo {
+    TransactionInfo::Approval {
+        owner: address(1),
+        spender,
+        amount: None,
+        token: None,
+        is_unlimited,
+    }
+}
+
+fn transfer(to: &str) -> TransactionInfo {
+    TransactionInfo::TokenTransfer {
+        from: address(1),
+        to: to.to_owned(),
+        amount: "0x1".to_owned(),
+        token_id: None,
+        token_info: None,
+        fiat_value: None,
+    }
+}
+
+/// Treats `0x0202…02` as a contract and `0x0303…03` as an account.
+fn is_contract(address: &Address) -> Option<bool> {
+    if *address == Address::repeat_byte(2) {
+        Some(true)
+    } else if *address == Address::repeat_byte(3) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_approval_risks() {
+    assert!(assess_transaction(&approval(address(2), false), &is_contract).is_empty());
+    assert_eq!(
+        assess_transaction(&approval(address(2), true), &is_contract).flags,
+        vec![RiskFlag::UnlimitedApproval {
+            spender: address(2)
+        }]
+    );
+    assert_eq!(
+        assess_transaction(&approval(address(3), false), &is_contract).flags,
+        vec![RiskFlag::ApprovalToExternallyOwnedAccount {
+            spender: address(3)
+        }]
+    );
+    assert!(assess_transaction(&approval(address(4), false), &is_contract).is_empty());
+}
+
+#[test]
+fn test_burn_transfer_risks() {
+    for burn in [
+        "0x0000000000000000000000000000000000000000",
+        "0x000000000000000000000000000000000000dEaD",
+    ] {
+        assert_eq!(
+            assess_transaction(&transfer(burn), &is_contract).flags,
+            vec![RiskFlag::TransferToBurnAddress {
+                to: burn.to_owned()
+            }]
+        );
+    }
+    assert!(assess_transaction(&transfer(&address(2)), &is_contract).is_empty());
+
+    let burn = transfer("0x0000000000000000000000000000000000000000").into_supply_change();
+    assert_eq!(
+        assess_transaction(&burn, &is_contract).flags,
+        vec![RiskFlag::TransferToBurnAddress {
+            to: "0x0000000000000000000000000000000000000000".to_owned()
+        }]
+    );
+}
+
+#[test]
+fn test_nested_risks() {
+    let info = TransactionInfo::SafeTransaction {
+        safe: address(5),
+        to: address(6),
+        value: "0x0".to_owned(),
+        operation: 0,
+        info: Box::new(TransactionInfo::Batch {
+            calls: vec![
+                approval(address(2), true),
+                TransactionInfo::Unknown {
+                    value: None,
+                    function: Some("mint(uint256)".to_owned()),
+                },
+            ],
+        }),
+    };
+
+    assert_eq!(
+        assess_transaction(&info, &is_contract).flags,
+        vec![
+            RiskFlag::UnlimitedApproval {
+                spender: address(2)
+            },
+            RiskFlag::UnknownContractCall {
+                function: Some("mint(uint256)".to_owned())
+            },
+        ]
+    );
+}
+
+fn permit(chain_id: serde_json::Value) -> TypedData {
+    TypedData::from_json(serde_json::json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Permit": [{ "name": "spender", "type": "address" }]
+        },
+        "primaryType": "Permit",
+        "domain": { "chainId": chain_id, "verifyingContract": address(2) },
+        "message": { "spender": address(3) }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_typed_data_risks() {
+    let contract = Address::repeat_byte(2);
+    assert!(assess_typed_data(&permit(serde_json::json!(1)), 1, Some(&contract)).is_empty());
+    assert!(assess_typed_data(&permit(serde_json::json!("0x1")), 1, None).is_empty());
+
+    assert_eq!(
+        assess_typed_data(&permit(serde_json::json!("137")), 1, None).flags,
+        vec![RiskFlag::DomainChainMismatch {
+            chain_id: 1,
+            domain_chain_id: 137
+        }]
+    );
+    assert_eq!(
+        assess_typed_data(
+            &permit(serde_json::json!(1)),
+            1,
+            Some(&Address::repeat_byte(4))
+        )
+        .flags,
+        vec![RiskFlag::DomainContractMismatch {
+            expected: address(4),
+            verifying_contract: address(2)
+        }]
+    );
+}
+
+#[test]
+fn test_risk_flag_json() {
+    assert_eq!(
+        serde_json::to_value(RiskFlag::DomainChainMismatch {
+            chain_id: 1,
+            domain_chain_id: 5
+        })
+        .unwrap(),
+        serde_json::json!({ "type": "domainChainMismatch", "chainId": 1, "domainChainId": 5 })
+    );
+}
@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use web3::types::{Address, TransactionRequest};
+
+use crate::models::error::Error;
+#[cfg(feature = "reqwest-selector-resolver")]
+use crate::models::selector_resolver::{four_byte_signature, open_chain_signature};
+use crate::models::selector_resolver::{resolve_transaction_info, SelectorResolver};
+use crate::models::transaction_info::TransactionInfo;
+
+const SELECTOR: [u8; 4] = [0x40, 0xc1, 0x0f, 0x19];
+
+struct TestResolver;
+
+#[async_trait]
+impl SelectorResolver for TestResolver {
+    async fn resolve(&self, selector: [u8; 4]) -> Result<Option<String>, Error> {
+        if selector == SELECTOR {
+            Ok(Some("mint(address,uint256)".to_owned()))
+        } else {
+            Err(Error::InvalidData)
+        }
+    }
+}
+
+fn request(data: Vec<u8>) -> TransactionRequest {
+    TransactionRequest {
+        to: Some(Address::random()),
+        data: Some(data.into()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_resolve_unknown_function() {
+    let mut data = SELECTOR.to_vec();
+    data.extend_from_slice(&[0; 64]);
+
+    match resolve_transaction_info(&request(data), &TestResolver).await {
+        TransactionInfo::Unknown { function, .. } => {
+            assert_eq!(function.as_deref(), Some("mint(address,uint256)"))
+        }
+        info => panic!("Unexpected transaction info {:?}", info),
+    }
+}
+
+#[tokio::test]
+async fn test_resolve_ignores_errors() {
+    assert!(matches!(
+        resolve_transaction_info(&request(vec![1, 2, 3, 4]), &TestResolver).await,
+        TransactionInfo::Unknown { function: None, .. }
+    ));
+}
+
+#[cfg(feature = "reqwest-selector-resolver")]
+#[test]
+fn test_open_chain_signature() {
+    let response = serde_json::json!({
+        "ok": true,
+        "result": {
+            "event": {},
+            "function": {
+                "0x40c10f19": [
+                    { "name": "mint_spam(bytes)", "filtered": true },
+                    { "name": "mint(address,uint256)", "filtered": false }
+                ]
+            }
+        }
+    });
+
+    assert_eq!(
+        open_chain_signature(&response, SELECTOR),
+        Some("mint(address,uint256)".to_owned())
+    );
+    assert_eq!(open_chain_signature(&response, [0; 4]), None);
+}
+
+#[cfg(feature = "reqwest-selector-resolver")]
+#[test]
+fn test_four_byte_signature() {
+    let response = serde_json::json!({
+        "count": 2,
+        "results": [
+            { "id": 844293, "text_signature": "mint_collision(bytes)" },
+            { "id": 1384, "text_signature": "mint(address,uint256)" }
+        ]
+    });
+
+    assert_eq!(
+        four_byte_signature(&response),
+        Some("mint(address,uint256)".to_owned())
+    );
+    assert_eq!(four_byte_signature(&serde_json::json!({})), None);
+}
@@ -1,4 +1,5 @@
 use crate::models::coin_type::CoinType;
+use crate::models::error::Error;
 use crate::models::transaction_info::TransactionInfo;
 use crate::tests::helpers::signer::TestSigner;
 use web3::types::Address;
@@ -21,7 +22,8 @@ async fn test_ethereum_signing() {
         Some(chain_id),
     )
     .expect("Could not identify transaction")
-    .signable_transaction_request();
+    .signable_transaction_request()
+    .unwrap();
     let original_message = transaction.message_hash(chain_id).expect("hash succeeds");
 
     let (signature_bytes, recovery_id) = transaction
@@ -96,6 +98,7 @@ fn test_1155_transfer_token_info() {
         amount,
         token_id,
         token_info: None,
+        fiat_value: None,
     };
     assert_eq!(
         info, expected_info,
@@ -133,7 +136,8 @@ async fn test_2930_signature() {
     let transaction =
         crate::known_transaction_request_type_from_json(json, CoinType::Ethereum, Some(chain_id))
             .expect("Could not identify transaction")
-            .signable_transaction_request();
+            .signable_transaction_request()
+            .unwrap();
 
     let original_message = transaction.message_hash(chain_id).expect("hash succeeds");
 
@@ -170,7 +174,8 @@ async fn test_1559_signature() {
     let transaction =
         crate::known_transaction_request_type_from_json(json, CoinType::Ethereum, Some(chain_id))
             .expect("Could not identify transaction")
-            .signable_transaction_request();
+            .signable_transaction_request()
+            .unwrap();
 
     let original_message = transaction.message_hash(chain_id).expect("hash succeeds");
 
@@ -187,3 +192,289 @@ async fn test_1559_signature() {
 
     assert_eq!(recovered_address, sender_address, "Address should match");
 }
+
+#[test]
+fn test_eip_155_encode_signed() {
+    use crate::models::transaction::Transaction;
+
+    let chain_id = 1;
+    let json = serde_json::json!({
+      "from": "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+      "to": "0x3535353535353535353535353535353535353535",
+      "gasPrice": "0x4a817c800",
+      "gas": "0x5208",
+      "value": "0xde0b6b3a7640000",
+      "nonce": "0x9"
+    });
+
+    let transaction =
+        crate::known_transaction_request_type_from_json(json, CoinType::Ethereum, Some(chain_id))
+            .expect("Could not identify transaction")
+            .signable_transaction_request()
+            .unwrap();
+    assert_eq!(
+        hex::encode(transaction.message_hash(chain_id).unwrap()),
+        "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+    );
+
+    let signature = hex::decode("28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa63627667cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83").unwrap();
+    let raw = transaction
+        .encode_signed(chain_id, &signature, 0)
+        .expect("Could not encode transaction");
+    assert_eq!(hex::encode(&raw), "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83");
+    assert_eq!(
+        web3::types::Transaction::from_raw(&raw).unwrap().from,
+        "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f".parse().ok()
+    );
+    assert!(transaction.encode_signed(chain_id, &signature, 2).is_err());
+    assert!(transaction
+        .encode_signed(chain_id, &signature[1..], 0)
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_pre_eip_155_encode_signed() {
+    use crate::models::ethereum_transaction::{
+        encode_signed_with_mode, message_hash_with_mode, recover_sender, SigningMode,
+    };
+    use crate::models::transaction::Transaction;
+
+    let signer = TestSigner::new();
+    let sender_address = signer.ethereum_address();
+    let request: web3::types::TransactionRequest = serde_json::from_value(serde_json::json!({
+      "from": sender_address,
+      "to": "0x3535353535353535353535353535353535353535",
+      "gasPrice": "0x4a817c800",
+      "gas": "0x5208",
+      "value": "0xde0b6b3a7640000",
+      "nonce": "0x9"
+    }))
+    .unwrap();
+
+    let message = message_hash_with_mode(&request, SigningMode::PreEip155).unwrap();
+    assert_ne!(
+        message,
+        message_hash_with_mode(&request, SigningMode::Eip155(1)).unwrap()
+    );
+
+    let (signature, recovery_id) = signer.sign_recoverable(message, None).await.unwrap();
+    let raw = encode_signed_with_mode(&request, SigningMode::PreEip155, &signature, recovery_id)
+        .expect("Could not encode transaction");
+
+    let decoded = web3::types::Transaction::from_raw(&raw).expect("Could not decode");
+    assert_eq!(decoded.v, Some((27 + recovery_id).into()));
+    assert_eq!(recover_sender(&raw).unwrap(), sender_address);
+
+    let mut typed = request;
+    typed.transaction_type = Some(1.into());
+    assert!(matches!(
+        message_hash_with_mode(&typed, SigningMode::PreEip155),
+        Err(Error::InvalidData)
+    ));
+}
+
+#[tokio::test]
+async fn test_typed_transaction_encode_signed() {
+    use crate::models::transaction::Transaction;
+
+    let chain_id = 5;
+    for json in [
+        serde_json::json!({
+          "type": "0x1",
+          "from": Address::zero(),
+          "to": Address::random(),
+          "gasPrice": "0x09184e72a000",
+          "gas": "0x8AE0",
+          "value": "0x2933BC9",
+          "nonce": "0x333",
+          "accessList": [{
+            "address": Address::random(),
+            "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000001"]
+          }]
+        }),
+        serde_json::json!({
+          "type": "0x2",
+          "from": Address::zero(),
+          "to": Address::random(),
+          "gas": "0x8AE0",
+          "maxPriorityFeePerGas": "0x1284D",
+          "maxFeePerGas": "0x1D97C",
+          "value": "0x2933BC9",
+          "nonce": "0x333",
+          "data": "0x12345678"
+        }),
+    ] {
+        let signer = TestSigner::new();
+        let sender_address = signer.ethereum_address();
+        let request = crate::known_transaction_request_type_from_json(
+            json,
+            CoinType::Ethereum,
+            Some(chain_id),
+        )
+        .expect("Could not identify transaction");
+        let transaction = request.signable_transaction_request().unwrap();
+
+        let (signature_bytes, recovery_id) = transaction
+            .sign_transaction(chain_id, move |message| {
+                signer.sign_recoverable(message, Some(chain_id))
+            })
+            .await
+            .expect("Could not sign transaction");
+        let raw = transaction
+            .encode_signed(chain_id, &signature_bytes, recovery_id)
+            .expect("Could not encode transaction");
+
+        let decoded = web3::types::Transaction::from_raw(&raw).expect("Could not decode");
+        assert_eq!(decoded.from, Some(sender_address), "Address should match");
+        assert_eq!(decoded.v, Some(recovery_id.into()));
+    }
+}
+
+fn parameters_json(sender_address: Address) -> serde_json::Value {
+    serde_json::json!({
+      "type": "0x2",
+      "from": sender_address,
+      "to": Address::random(),
+      "gas": "0x8AE0",
+      "maxPriorityFeePerGas": "0x1284D",
+      "maxFeePerGas": "0x1D97C",
+      "value": "0x2933BC9",
+      "nonce": "0x333",
+      "data": "0x"
+    })
+}
+
+#[tokio::test]
+async fn test_ethereum_parameters_signing() {
+    use crate::models::transaction::{SignableTransactionRequest, TransactionRequest};
+
+    let chain_id = 0;
+    let signer = TestSigner::new();
+    let sender_address = signer.ethereum_address();
+    let json = parameters_json(sender_address);
+
+    let request = web3::types::TransactionRequest::from_json(json.clone()).unwrap();
+    let transaction: Box<dyn SignableTransactionRequest> =
+        Box::new(web3::types::TransactionParameters::from_json(json).unwrap());
+    let original_message = transaction.message_hash(chain_id).expect("hash succeeds");
+    assert_eq!(original_message, request.message_hash(chain_id).unwrap());
+
+    let (signature_bytes, recovery_id) = transaction
+        .sign_transaction(chain_id, move |message| {
+            signer.sign_recoverable(message, Some(chain_id))
+        })
+        .await
+        .expect("Could not sign transaction");
+
+    let recovered_address =
+        web3::signing::recover(&original_message, &signature_bytes, recovery_id as i32)
+            .expect("Could not recover signature");
+
+    assert_eq!(recovered_address, sender_address, "Address should match");
+}
+
+#[test]
+fn test_ethereum_parameters_transfer_token_info() {
+    use crate::models::transaction::TransactionRequest;
+
+    let parameters =
+        web3::types::TransactionParameters::from_json(parameters_json(Address::random())).unwrap();
+
+    assert_eq!(
+        parameters.transaction_info(),
+        TransactionInfo::TokenTransfer {
+            from: String::new(),
+            to: format!("{:#x}", parameters.to.unwrap()),
+            amount: "0x2933bc9".to_owned(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_sign_and_encode() {
+    use crate::models::transaction::{SignedTransaction, Transaction};
+
+    let chain_id = 5;
+    let signer = TestSigner::new();
+    let sender_address = signer.ethereum_address();
+    let transaction = crate::known_transaction_request_type_from_json(
+        parameters_json(sender_address),
+        CoinType::Ethereum,
+        Some(chain_id),
+    )
+    .expect("Could not identify transaction")
+    .signable_transaction_request()
+    .unwrap();
+
+    let signed = transaction
+        .sign_and_encode(chain_id, move |message| {
+            signer.sign_recoverable(message, Some(chain_id))
+        })
+        .await
+        .expect("Could not sign transaction");
+
+    let decoded = web3::types::Transaction::from_raw(&signed.raw.0).expect("Could not decode");
+    assert_eq!(decoded.from, Some(sender_address));
+    assert_eq!(signed.hash, decoded.hash);
+    assert_eq!(signed.signature.0.len(), 64);
+    assert_eq!(decoded.v, Some(signed.recovery_id.into()));
+    assert_eq!(signed.chain_id, chain_id);
+
+    let json = serde_json::to_value(&signed).unwrap();
+    assert_eq!(json["hash"], format!("{:#x}", decoded.hash));
+    assert_eq!(json["chainId"], chain_id);
+    assert_eq!(
+        serde_json::from_value::<SignedTransaction>(json).unwrap(),
+        signed
+    );
+}
+
+#[tokio::test]
+async fn test_blocking_signer() {
+    use crate::models::ethereum_message::Message;
+    use crate::models::message::{SignError, SignableMessage};
+    use crate::models::signer::BlockingSigner;
+
+    let chain_id = 5;
+    let key = TestSigner::new();
+    let sender_address = key.ethereum_address();
+    let signer = BlockingSigner(|hash: &[u8]| {
+        let signature = web3::signing::Key::sign(&key, hash, None)?;
+        Ok::<_, web3::signing::SigningError>((
+            [signature.r.as_bytes(), signature.s.as_bytes()].concat(),
+            signature.v - 27,
+        ))
+    });
+
+    let transaction = crate::known_transaction_request_type_from_json(
+        parameters_json(sender_address),
+        CoinType::Ethereum,
+        Some(chain_id),
+    )
+    .expect("Could not identify transaction")
+    .signable_transaction_request()
+    .unwrap();
+    let (signature, recovery_id) = transaction.sign_with(chain_id, &signer).await.unwrap();
+    let hash = transaction.message_hash(chain_id).unwrap();
+    assert_eq!(
+        web3::signing::recover(&hash, &signature, recovery_id as i32).unwrap(),
+        sender_address
+    );
+
+    let message: Box<dyn SignableMessage> = Box::new(Message::String("hello".to_owned()));
+    let (signature, recovery_id) = message.sign_with(chain_id, &signer).await.unwrap();
+    let hash = message.message_hash(chain_id).unwrap();
+    assert_eq!(
+        web3::signing::recover(&hash, &signature, recovery_id as i32).unwrap(),
+        sender_address
+    );
+
+    let failing = BlockingSigner(|_: &[u8]| Err("locked"));
+    assert!(matches!(
+        message.sign_with(chain_id, &failing).await,
+        Err(SignError::Sign("locked"))
+    ));
+}
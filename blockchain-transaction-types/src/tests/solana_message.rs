@@ -0,0 +1,64 @@
+use solana_sdk::offchain_message::{MessageFormat, OffchainMessage};
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::models::error::Error;
+use crate::models::message::{Message, MessageInfo, SignableMessage};
+
+#[test]
+fn test_offchain_message_from_json() {
+    let message = OffchainMessage::from_json(serde_json::json!("Hello, Solana!")).unwrap();
+
+    assert_eq!(message.get_version(), 0);
+    assert_eq!(message.get_format(), MessageFormat::RestrictedAscii);
+    assert_eq!(
+        message.message_info(),
+        MessageInfo::String("Hello, Solana!".to_owned())
+    );
+}
+
+#[test]
+fn test_offchain_message_hash() {
+    let message = OffchainMessage::from_json(serde_json::json!("Hello, Solana!")).unwrap();
+    let bytes = message.message_hash(0).unwrap();
+
+    assert_eq!(&bytes[..16], b"\xffsolana offchain");
+    assert_eq!(&bytes[16..20], &[0, 0, 14, 0]);
+    assert_eq!(&bytes[20..], b"Hello, Solana!");
+
+    let keypair = Keypair::new();
+    let signature = keypair.sign_message(&bytes);
+    assert!(message.verify(&keypair.pubkey(), &signature).unwrap());
+}
+
+#[test]
+fn test_offchain_message_from_raw() {
+    let message = OffchainMessage::from_raw("Привет".as_bytes()).unwrap();
+    assert_eq!(message.get_format(), MessageFormat::LimitedUtf8);
+
+    let serialized = message.message_hash(0).unwrap();
+    assert_eq!(OffchainMessage::from_raw(&serialized).unwrap(), message);
+}
+
+#[test]
+fn test_invalid_offchain_message() {
+    assert!(matches!(
+        OffchainMessage::from_json(serde_json::json!({ "message": "hello" })),
+        Err(Error::InvalidData)
+    ));
+    assert!(matches!(
+        OffchainMessage::from_raw(&[0xff, 0xfe]),
+        Err(Error::InvalidData)
+    ));
+}
+
+#[cfg(feature = "all-chains")]
+#[test]
+fn test_known_message_type_solana() {
+    use crate::models::coin_type::CoinType;
+    use crate::models::known_message_type::KnownMessageType;
+
+    assert!(matches!(
+        KnownMessageType::from_json(serde_json::json!("hello"), CoinType::Solana, None),
+        Ok(KnownMessageType::Solana(_))
+    ));
+}
@@ -0,0 +1,402 @@
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, CompiledInstruction, Instruction};
+use solana_sdk::message::v0::{self, MessageAddressTableLookup};
+use solana_sdk::message::{Message, MessageHeader, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction;
+use solana_sdk::system_program;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+
+use crate::models::account::AccountId;
+use crate::models::coin_type::CoinType;
+use crate::models::error::Error;
+use crate::models::known_transaction_type::KnownTransactionRequestType;
+use crate::models::solana_transaction::{versioned_transaction_info, AddressLookupTables};
+use crate::models::transaction::TransactionRequest;
+use crate::models::transaction_info::TransactionInfo;
+
+const SPL_TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const ATA_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+fn spl_token_instruction(data: Vec<u8>, accounts: &[Pubkey]) -> Instruction {
+    program_instruction(SPL_TOKEN_PROGRAM_ID, data, accounts)
+}
+
+fn program_instruction(program_id: &str, data: Vec<u8>, accounts: &[Pubkey]) -> Instruction {
+    Instruction {
+        program_id: program_id.parse().unwrap(),
+        accounts: accounts
+            .iter()
+            .map(|account| AccountMeta::new(*account, false))
+            .collect(),
+        data,
+    }
+}
+
+/// Builds a v0 system transfer from a static account to an account loaded
+/// from the given lookup table at index 1.
+fn v0_system_transfer(from: Pubkey, table: Pubkey, lamports: u64) -> VersionedTransaction {
+    let data = system_instruction::transfer(&from, &Pubkey::default(), lamports).data;
+    let message = v0::Message {
+        header: MessageHeader {
+            num_required_signatures: 1,
+            num_readonly_signed_accounts: 0,
+            num_readonly_unsigned_accounts: 1,
+        },
+        account_keys: vec![from, system_program::id()],
+        recent_blockhash: Hash::default(),
+        instructions: vec![CompiledInstruction::new_from_raw_parts(1, data, vec![0, 2])],
+        address_table_lookups: vec![MessageAddressTableLookup {
+            account_key: table,
+            writable_indexes: vec![1],
+            readonly_indexes: vec![],
+        }],
+    };
+
+    VersionedTransaction {
+        signatures: vec![Default::default()],
+        message: VersionedMessage::V0(message),
+    }
+}
+
+#[test]
+fn test_system_transfer_info() {
+    let from = Pubkey::new_unique();
+    let to = Pubkey::new_unique();
+    let instruction = system_instruction::transfer(&from, &to, 1_000_000);
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&from)));
+
+    assert_eq!(
+        transaction.transaction_info(),
+        TransactionInfo::TokenTransfer {
+            from: from.to_string(),
+            to: to.to_string(),
+            amount: "0xf4240".into(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_spl_token_transfer_info() {
+    let source = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let mut data = vec![3];
+    data.extend_from_slice(&42u64.to_le_bytes());
+    let instruction = spl_token_instruction(data, &[source, destination, authority]);
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&authority)));
+
+    assert_eq!(
+        transaction.transaction_info(),
+        TransactionInfo::TokenTransfer {
+            from: source.to_string(),
+            to: destination.to_string(),
+            amount: "0x2a".into(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_spl_token_transfer_checked_info() {
+    let source = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let mut data = vec![12];
+    data.extend_from_slice(&42u64.to_le_bytes());
+    data.push(6);
+    let instruction = spl_token_instruction(data, &[source, mint, destination, authority]);
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&authority)));
+
+    assert_eq!(
+        transaction.transaction_info(),
+        TransactionInfo::TokenTransfer {
+            from: source.to_string(),
+            to: destination.to_string(),
+            amount: "0x2a".into(),
+            token_id: Some(mint.to_string()),
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_token_2022_transfer_checked_with_fee_info() {
+    let source = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let mut data = vec![26, 1];
+    data.extend_from_slice(&1_000u64.to_le_bytes());
+    data.push(6);
+    data.extend_from_slice(&5u64.to_le_bytes());
+    let instruction = program_instruction(
+        TOKEN_2022_PROGRAM_ID,
+        data,
+        &[source, mint, destination, authority],
+    );
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&authority)));
+
+    assert_eq!(
+        transaction.transaction_info(),
+        TransactionInfo::TokenTransfer {
+            from: source.to_string(),
+            to: destination.to_string(),
+            amount: "0x3e8".into(),
+            token_id: Some(mint.to_string()),
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_token_account_creation_info() {
+    let payer = Pubkey::new_unique();
+    let account = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let source = Pubkey::new_unique();
+    let token_program: Pubkey = TOKEN_2022_PROGRAM_ID.parse().unwrap();
+    let create = program_instruction(
+        ATA_PROGRAM_ID,
+        vec![1],
+        &[
+            payer,
+            account,
+            owner,
+            mint,
+            system_program::id(),
+            token_program,
+        ],
+    );
+    let mut data = vec![12];
+    data.extend_from_slice(&42u64.to_le_bytes());
+    data.push(6);
+    let transfer =
+        program_instruction(TOKEN_2022_PROGRAM_ID, data, &[source, mint, account, payer]);
+    let creation = TransactionInfo::TokenAccountCreation {
+        payer: payer.to_string(),
+        account: account.to_string(),
+        owner: owner.to_string(),
+        mint: mint.to_string(),
+    };
+
+    let transaction =
+        Transaction::new_unsigned(Message::new(&[create.clone(), transfer], Some(&payer)));
+    assert_eq!(
+        transaction.transaction_info(),
+        TransactionInfo::Batch {
+            calls: vec![
+                TransactionInfo::TokenTransfer {
+                    from: source.to_string(),
+                    to: account.to_string(),
+                    amount: "0x2a".into(),
+                    token_id: Some(mint.to_string()),
+                    token_info: None,
+                    fiat_value: None,
+                },
+                creation.clone(),
+            ],
+        }
+    );
+
+    let transaction = Transaction::new_unsigned(Message::new(&[create], Some(&payer)));
+    assert_eq!(transaction.transaction_info(), creation);
+}
+
+#[test]
+fn test_unknown_solana_transaction_info() {
+    let payer = Pubkey::new_unique();
+    let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], vec![]);
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+
+    assert_eq!(
+        transaction.transaction_info(),
+        TransactionInfo::Unknown {
+            value: None,
+            function: None,
+        }
+    );
+}
+
+#[test]
+fn test_solana_transaction_from_json() {
+    let from = Pubkey::new_unique();
+    let to = Pubkey::new_unique();
+    let instruction = system_instruction::transfer(&from, &to, 1);
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&from)));
+
+    let known = crate::known_transaction_request_type_from_json(
+        serde_json::to_value(&transaction).unwrap(),
+        CoinType::Solana,
+        None,
+    )
+    .expect("Could not identify transaction");
+
+    assert!(matches!(
+        known.transaction_request().transaction_info(),
+        TransactionInfo::TokenTransfer { .. }
+    ));
+    assert_eq!(known.sender(), Some(AccountId::Solana(from)));
+    assert_eq!(known.fee_payer(), Some(AccountId::Solana(from)));
+}
+
+#[test]
+fn test_versioned_system_transfer_info() {
+    let from = Pubkey::new_unique();
+    let to = Pubkey::new_unique();
+    let table = Pubkey::new_unique();
+    let transaction = v0_system_transfer(from, table, 1_000_000);
+
+    let mut tables = AddressLookupTables::new();
+    tables.insert(table, vec![Pubkey::new_unique(), to]);
+
+    assert_eq!(
+        versioned_transaction_info(&transaction, &tables).unwrap(),
+        TransactionInfo::TokenTransfer {
+            from: from.to_string(),
+            to: to.to_string(),
+            amount: "0xf4240".into(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }
+    );
+}
+
+#[test]
+fn test_versioned_transaction_info_without_lookup_table() {
+    let transaction = v0_system_transfer(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+
+    assert!(matches!(
+        versioned_transaction_info(&transaction, &AddressLookupTables::new()),
+        Err(Error::InvalidData)
+    ));
+    assert_eq!(
+        transaction.transaction_info(),
+        TransactionInfo::Unknown {
+            value: None,
+            function: None,
+        }
+    );
+}
+
+#[test]
+fn test_versioned_transaction_info_with_index_out_of_bounds() {
+    let table = Pubkey::new_unique();
+    let transaction = v0_system_transfer(Pubkey::new_unique(), table, 1);
+
+    let mut tables = AddressLookupTables::new();
+    tables.insert(table, vec![Pubkey::new_unique()]);
+
+    assert!(matches!(
+        versioned_transaction_info(&transaction, &tables),
+        Err(Error::InvalidData)
+    ));
+}
+
+#[test]
+fn test_versioned_transaction_from_json() {
+    let from = Pubkey::new_unique();
+    let table = Pubkey::new_unique();
+    let transaction = v0_system_transfer(from, table, 1);
+
+    let known = crate::known_transaction_request_type_from_json(
+        serde_json::to_value(&transaction).unwrap(),
+        CoinType::Solana,
+        None,
+    )
+    .expect("Could not identify transaction");
+
+    assert!(matches!(
+        known,
+        KnownTransactionRequestType::SolanaVersioned(_)
+    ));
+    assert_eq!(known.sender(), Some(AccountId::Solana(from)));
+}
+
+#[cfg(feature = "signing")]
+#[test]
+fn test_versioned_transaction_message_hash() {
+    use crate::models::transaction::SignableTransactionRequest;
+
+    let transaction = v0_system_transfer(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    let message = transaction.message_hash(0).unwrap();
+
+    assert_eq!(message[0], 0x80);
+    assert_eq!(message, transaction.message.serialize());
+}
+
+#[test]
+fn test_durable_nonce_transaction() {
+    use crate::models::solana_transaction::DurableNonce;
+
+    let from = Pubkey::new_unique();
+    let to = Pubkey::new_unique();
+    let nonce_account = Pubkey::new_unique();
+    let nonce_authority = Pubkey::new_unique();
+    let nonce = Hash::new_unique();
+    let transfer = system_instruction::transfer(&from, &to, 1_000_000);
+    let mut message = Message::new_with_nonce(
+        vec![transfer.clone()],
+        Some(&from),
+        &nonce_account,
+        &nonce_authority,
+    );
+    message.recent_blockhash = nonce;
+    let mut transaction = Transaction::new_unsigned(message);
+
+    assert!(transaction.uses_durable_nonce());
+    assert_eq!(transaction.nonce_account(), Some(nonce_account));
+    assert_eq!(transaction.nonce_authority(), Some(nonce_authority));
+    assert!(!transaction.refresh_blockhash(Hash::new_unique()));
+    assert_eq!(transaction.message.recent_blockhash, nonce);
+    assert!(matches!(
+        transaction.transaction_info(),
+        TransactionInfo::TokenTransfer { amount, .. } if amount == "0xf4240"
+    ));
+
+    let mut transaction = Transaction::new_unsigned(Message::new(&[transfer], Some(&from)));
+    let blockhash = Hash::new_unique();
+    assert!(!transaction.uses_durable_nonce());
+    assert_eq!(transaction.nonce_authority(), None);
+    assert!(transaction.refresh_blockhash(blockhash));
+    assert_eq!(transaction.message.recent_blockhash, blockhash);
+}
+
+#[cfg(feature = "signing")]
+#[test]
+fn test_durable_nonce_message_hash() {
+    use crate::models::solana_transaction::DurableNonce;
+    use crate::models::transaction::SignableTransactionRequest;
+
+    let from = Pubkey::new_unique();
+    let nonce_account = Pubkey::new_unique();
+    let transfer = system_instruction::transfer(&from, &Pubkey::new_unique(), 1);
+    let message = Message::new_with_nonce(vec![transfer], Some(&from), &nonce_account, &from);
+    let mut transaction = VersionedTransaction {
+        signatures: vec![Default::default()],
+        message: VersionedMessage::Legacy(message),
+    };
+
+    assert!(transaction.uses_durable_nonce());
+    assert!(matches!(
+        transaction.message_hash(0),
+        Err(Error::InvalidData)
+    ));
+
+    transaction.set_blockhash(Hash::new_unique());
+    assert_eq!(
+        transaction.message_hash(0).unwrap(),
+        transaction.message.serialize()
+    );
+}
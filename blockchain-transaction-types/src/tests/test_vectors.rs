@@ -0,0 +1,110 @@
+use crate::models::test_vectors::{test_vector, test_vectors, TestVectorKind};
+
+#[test]
+fn test_test_vectors_load() {
+    let vectors = test_vectors().unwrap();
+    let names: Vec<&str> = vectors.iter().map(|vector| vector.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "eip155-transfer",
+            "eip1559-transfer",
+            "eip712-mail",
+            "erc1155-transfer"
+        ]
+    );
+
+    let mail = test_vector("eip712-mail").unwrap().unwrap();
+    assert_eq!(mail.kind, TestVectorKind::Message);
+    assert_eq!(test_vector("wyvern").unwrap(), None);
+}
+
+#[test]
+fn test_test_vectors_transaction_info() {
+    for vector in test_vectors().unwrap() {
+        let expected = match vector.transaction_info {
+            Some(info) => info,
+            None => continue,
+        };
+        let request = crate::known_transaction_request_type_from_json(
+            vector.request,
+            vector.coin_type,
+            Some(vector.chain_id),
+        )
+        .unwrap();
+        assert_eq!(
+            request.transaction_request().transaction_info(),
+            expected,
+            "{}",
+            vector.name
+        );
+    }
+}
+
+#[cfg(feature = "signing")]
+#[test]
+fn test_test_vectors_signing() {
+    use web3::signing::Key;
+
+    use crate::models::message::SignableMessage;
+    use crate::models::transaction::SignableTransactionRequest;
+
+    for vector in test_vectors().unwrap() {
+        let (message_hash, private_key) = match (vector.message_hash, vector.private_key) {
+            (Some(message_hash), Some(private_key)) => (message_hash, private_key),
+            _ => continue,
+        };
+        let chain_id = vector.chain_id;
+        let (hash, transaction): (Vec<u8>, Option<Box<dyn SignableTransactionRequest>>) =
+            match vector.kind {
+                TestVectorKind::Transaction => {
+                    let transaction = crate::known_transaction_request_type_from_json(
+                        vector.request,
+                        vector.coin_type,
+                        Some(chain_id),
+                    )
+                    .unwrap()
+                    .signable_transaction_request()
+                    .unwrap();
+                    (
+                        transaction.message_hash(chain_id).unwrap(),
+                        Some(transaction),
+                    )
+                }
+                TestVectorKind::Message => {
+                    let message: Box<dyn SignableMessage> = crate::known_message_type_from_json(
+                        vector.request,
+                        vector.coin_type,
+                        Some(chain_id),
+                    )
+                    .unwrap()
+                    .signable_message();
+                    (message.message_hash(chain_id).unwrap(), None)
+                }
+            };
+        assert_eq!(hash, message_hash.as_bytes(), "{}", vector.name);
+
+        let key = secp256k1::SecretKey::from_slice(private_key.as_bytes()).unwrap();
+        let signature = (&key).sign_message(&hash).unwrap();
+        assert_eq!(
+            Some([signature.r.as_bytes(), signature.s.as_bytes()].concat()),
+            vector.signature.clone().map(|signature| signature.0),
+            "{}",
+            vector.name
+        );
+        assert_eq!(Some(signature.v), vector.recovery_id, "{}", vector.name);
+
+        if let (Some(transaction), Some(raw)) = (transaction, vector.raw) {
+            let encoded = transaction
+                .encode_signed(chain_id, &vector.signature.unwrap().0, signature.v)
+                .unwrap();
+            assert_eq!(encoded, raw.0, "{}", vector.name);
+            assert_eq!(
+                vector.transaction_hash.map(|hash| hash.as_bytes().to_vec()),
+                Some(transaction.transaction_hash(chain_id, &encoded).unwrap()),
+                "{}",
+                vector.name
+            );
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use web3::ethabi::{self, Token};
+use web3::types::{Address, U256};
+
+use crate::models::error::Error;
+use crate::models::keccak::keccak256;
+use crate::models::token_info_provider::{enrich, TokenInfoProvider, Web3TokenInfoProvider};
+use crate::models::transaction_info::{TokenInfo, TransactionInfo};
+use crate::tests::helpers::transport::TestTransport;
+
+/// Answers `eth_call`s with the output registered for their selector.
+fn transport(outputs: &[(&str, Vec<u8>)]) -> TestTransport {
+    let outputs: HashMap<String, Vec<u8>> = outputs
+        .iter()
+        .map(|(signature, output)| {
+            let selector = hex::encode(&keccak256(signature.as_bytes())[..4]);
+            (selector, output.clone())
+        })
+        .collect();
+    TestTransport::new().with_handler("eth_call", move |params| {
+        let data = params[0]["data"].as_str().unwrap();
+        outputs
+            .get(&data[2..10])
+            .map(|output| serde_json::json!(format!("0x{}", hex::encode(output))))
+            .ok_or_else(|| web3::Error::Decoder("execution reverted".to_owned()))
+    })
+}
+
+fn string(value: &str) -> Vec<u8> {
+    ethabi::encode(&[Token::String(value.to_owned())])
+}
+
+fn provider(transport: TestTransport) -> Web3TokenInfoProvider<TestTransport> {
+    let mut provider = Web3TokenInfoProvider::new();
+    provider.register_chain(1, transport);
+    provider
+}
+
+#[tokio::test]
+async fn test_web3_token_info() {
+    let transport = transport(&[
+        ("name()", string("Wrapped Ether")),
+        ("symbol()", string("WETH")),
+        ("decimals()", ethabi::encode(&[Token::Uint(18.into())])),
+    ]);
+
+    assert_eq!(
+        provider(transport)
+            .token_info(1, Address::random(), None)
+            .await
+            .unwrap(),
+        TokenInfo {
+            name: "Wrapped Ether".to_owned(),
+            symbol: Some("WETH".to_owned()),
+            decimals: 18,
+            ..Default::default()
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_web3_nft_info() {
+    let mut name = b"Maker".to_vec();
+    name.resize(32, 0);
+    let transport = transport(&[
+        ("name()", name),
+        ("tokenURI(uint256)", string("ipfs://token/7")),
+    ]);
+
+    let info = provider(transport)
+        .token_info(1, Address::random(), Some(U256::from(7)))
+        .await
+        .unwrap();
+
+    assert_eq!(info.name, "Maker");
+    assert_eq!(info.symbol, None);
+    assert_eq!(info.decimals, 0);
+    assert_eq!(info.uri.as_deref(), Some("ipfs://token/7"));
+}
+
+#[tokio::test]
+async fn test_web3_token_info_errors() {
+    let provider = provider(transport(&[]));
+
+    assert!(matches!(
+        provider.token_info(5, Address::random(), None).await,
+        Err(Error::UnknownChain(5))
+    ));
+    assert!(matches!(
+        provider.token_info(1, Address::random(), None).await,
+        Err(Error::Web3(_))
+    ));
+}
+
+struct TestProvider;
+
+#[async_trait]
+impl TokenInfoProvider for TestProvider {
+    async fn token_info(
+        &self,
+        chain_id: u64,
+        contract: Address,
+        token_id: Option<U256>,
+    ) -> Result<TokenInfo, Error> {
+        Ok(TokenInfo {
+            name: format!("{}:{:#x}", chain_id, contract),
+            uri: token_id.map(|token_id| token_id.to_string()),
+            ..Default::default()
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_enrich_token_transfer() {
+    let contract = Address::repeat_byte(1);
+    let mut info = TransactionInfo::TokenTransfer {
+        from: "0x01".to_owned(),
+        to: "0x02".to_owned(),
+        amount: "0x1".to_owned(),
+        token_id: Some("0x7".to_owned()),
+        token_info: None,
+        fiat_value: None,
+    };
+
+    enrich(&mut info, 1, contract, &TestProvider).await;
+
+    match info {
+        TransactionInfo::TokenTransfer { token_info, .. } => {
+            let token_info = token_info.unwrap();
+            assert_eq!(token_info.name, format!("1:{:#x}", contract));
+            assert_eq!(token_info.uri.as_deref(), Some("7"));
+        }
+        info => panic!("unexpected transaction info {:?}", info),
+    }
+}
+
+#[tokio::test]
+async fn test_enrich_safe_transaction() {
+    let token = Address::repeat_byte(2);
+    let mut info = TransactionInfo::SafeTransaction {
+        safe: "0x01".to_owned(),
+        to: format!("{:#x}", token),
+        value: "0x0".to_owned(),
+        operation: 0,
+        info: Box::new(TransactionInfo::TokenTransfer {
+            from: "0x01".to_owned(),
+            to: "0x02".to_owned(),
+            amount: "0x1".to_owned(),
+            token_id: None,
+            token_info: None,
+            fiat_value: None,
+        }),
+    };
+
+    enrich(&mut info, 1, Address::repeat_byte(1), &TestProvider).await;
+
+    match info {
+        TransactionInfo::SafeTransaction { info, .. } => match *info {
+            TransactionInfo::TokenTransfer { token_info, .. } => {
+                assert_eq!(token_info.unwrap().name, format!("1:{:#x}", token));
+            }
+            info => panic!("unexpected transaction info {:?}", info),
+        },
+        info => panic!("unexpected transaction info {:?}", info),
+    }
+}
@@ -0,0 +1,145 @@
+use crate::models::transaction_info::{
+    CallParameter, FiatValue, TokenInfo, TokenTransferInfo, TransactionInfo, TransactionOutput,
+};
+
+fn round_trip(info: &TransactionInfo) -> TransactionInfo {
+    serde_json::from_value(serde_json::to_value(info).unwrap()).unwrap()
+}
+
+fn token_info() -> TokenInfo {
+    TokenInfo {
+        name: "Wrapped Ether".to_owned(),
+        symbol: Some("WETH".to_owned()),
+        decimals: 18,
+        total_supply: None,
+        image: None,
+        uri: None,
+    }
+}
+
+#[test]
+fn test_token_transfer_json() {
+    let info = TransactionInfo::TokenTransfer {
+        from: "0x01".to_owned(),
+        to: "0x02".to_owned(),
+        amount: "0x2a".to_owned(),
+        token_id: None,
+        token_info: Some(token_info()),
+        fiat_value: Some(FiatValue {
+            currency: "USD".to_owned(),
+            amount: 105.5,
+        }),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&info).unwrap(),
+        serde_json::json!({
+            "type": "tokenTransfer",
+            "from": "0x01",
+            "to": "0x02",
+            "amount": "0x2a",
+            "tokenId": null,
+            "tokenInfo": {
+                "name": "Wrapped Ether",
+                "symbol": "WETH",
+                "decimals": 18,
+                "totalSupply": null,
+                "image": null,
+                "uri": null
+            },
+            "fiatValue": {
+                "currency": "USD",
+                "amount": 105.5
+            }
+        })
+    );
+    assert_eq!(round_trip(&info), info);
+}
+
+#[test]
+fn test_nested_transaction_info_round_trip() {
+    let transfer = TransactionInfo::TokenBatchTransfer {
+        transfers: vec![TokenTransferInfo {
+            from: "0x01".to_owned(),
+            to: "0x02".to_owned(),
+            amount: "0x1".to_owned(),
+            token_id: Some("0x7".to_owned()),
+            token_info: None,
+            fiat_value: None,
+        }],
+    };
+    let call = TransactionInfo::ContractCall {
+        function: "mint(uint256)".to_owned(),
+        parameters: vec![CallParameter {
+            name: "amount".to_owned(),
+            kind: "uint256".to_owned(),
+            value: serde_json::json!("0x1"),
+        }],
+    };
+    let info = TransactionInfo::SafeTransaction {
+        safe: "0x03".to_owned(),
+        to: "0x04".to_owned(),
+        value: "0x0".to_owned(),
+        operation: 1,
+        info: Box::new(TransactionInfo::Batch {
+            calls: vec![transfer, call],
+        }),
+    };
+
+    let json = serde_json::to_value(&info).unwrap();
+    assert_eq!(json["type"], "safeTransaction");
+    assert_eq!(json["info"]["type"], "batch");
+    assert_eq!(json["info"]["calls"][1]["parameters"][0]["type"], "uint256");
+    assert_eq!(round_trip(&info), info);
+}
+
+#[test]
+fn test_transaction_info_variants_round_trip() {
+    let infos = [
+        TransactionInfo::Approval {
+            owner: "0x01".to_owned(),
+            spender: "0x02".to_owned(),
+            amount: None,
+            token: None,
+            is_unlimited: true,
+        },
+        TransactionInfo::TokenSwap {
+            token_in: "0x01".to_owned(),
+            token_out: "0x02".to_owned(),
+            amount_in: "0x1".to_owned(),
+            min_amount_out: "0x2".to_owned(),
+            recipient: "0x03".to_owned(),
+        },
+        TransactionInfo::Wrap {
+            token: "0x01".to_owned(),
+            amount: "0x1".to_owned(),
+        },
+        TransactionInfo::ContractDeployment {
+            bytecode_size: 4,
+            init_code_hash: "0x00".to_owned(),
+        },
+        TransactionInfo::UtxoTransfer {
+            outputs: vec![TransactionOutput {
+                script_pubkey: "0014".to_owned(),
+                amount: "0x1".to_owned(),
+            }],
+            fee: Some("0x1".to_owned()),
+        },
+        TransactionInfo::Unknown {
+            value: None,
+            function: Some("foo()".to_owned()),
+        },
+    ];
+
+    for info in &infos {
+        assert_eq!(&round_trip(info), info);
+    }
+    assert_eq!(
+        serde_json::to_value(&infos[0]).unwrap()["isUnlimited"],
+        true
+    );
+    assert_eq!(
+        serde_json::to_value(&infos[1]).unwrap()["type"],
+        "tokenSwap"
+    );
+}